@@ -1,6 +1,6 @@
 use clap::Parser;
 use clap::{Args, Subcommand};
-use layer_climb_cli::command::{ContractCommand, WalletCommand};
+use layer_climb_cli::command::{ContractCommand, StakingCommand, WalletCommand};
 
 use crate::commands::faucet::FaucetCommand;
 use crate::commands::pool::PoolCommand;
@@ -26,6 +26,8 @@ pub enum Command {
     Wallet(WalletArgs),
     /// Contract subcommands
     Contract(ContractArgs),
+    /// Staking and rewards subcommands
+    Staking(StakingArgs),
     /// Faucet subcommands
     Faucet(FaucetArgs),
     /// Pool subcommands
@@ -44,6 +46,12 @@ pub struct ContractArgs {
     pub command: ContractCommand,
 }
 
+#[derive(Clone, Args)]
+pub struct StakingArgs {
+    #[command(subcommand)]
+    pub command: StakingCommand,
+}
+
 #[derive(Clone, Args)]
 pub struct FaucetArgs {
     #[command(subcommand)]
@@ -2,7 +2,7 @@
 
 use anyhow::{Context, Result};
 use layer_climb::prelude::*;
-use layer_climb_cli::command::WalletCommand;
+use layer_climb_cli::command::{ContractCommand, StakingCommand, WalletCommand};
 use rand::rngs::ThreadRng;
 
 use crate::{
@@ -80,6 +80,16 @@ impl AppContext {
                 WalletCommand::Transfer { .. } => true,
                 _ => false,
             },
+            Command::Contract(args) => match args.command {
+                ContractCommand::Upload { .. }
+                | ContractCommand::Instantiate { .. }
+                | ContractCommand::Execute { .. } => true,
+                _ => false,
+            },
+            Command::Staking(args) => match args.command {
+                StakingCommand::Show { .. } => false,
+                _ => true,
+            },
             _ => true,
         };
 
@@ -4,11 +4,11 @@ mod config;
 mod context;
 
 use anyhow::Result;
-use args::{CliArgs, Command, ContractArgs, FaucetArgs, PoolArgs, WalletArgs};
+use args::{CliArgs, Command, ContractArgs, FaucetArgs, PoolArgs, StakingArgs, WalletArgs};
 use clap::Parser;
 use commands::faucet::FaucetCommand;
 use context::AppContext;
-use layer_climb_cli::command::{ContractLog, WalletCommand, WalletLog};
+use layer_climb_cli::command::{ContractLog, StakingCommand, StakingLog, WalletCommand, WalletLog};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -106,9 +106,191 @@ async fn main() -> Result<()> {
                         tracing::info!("Executed contract");
                         tracing::info!("Tx hash: {}", tx_resp.txhash);
                     }
+                    ContractLog::Simulate { simulation } => {
+                        tracing::info!("Simulated tx (nothing was broadcast)");
+                        tracing::info!(
+                            "Gas used: {}, gas limit: {}",
+                            simulation.gas_info.gas_used,
+                            simulation.gas_units
+                        );
+                        tracing::info!("Fee: {:?}", simulation.fee.amount);
+                        for event in simulation.events().events_iter() {
+                            tracing::info!("{:?}", event);
+                        }
+                    }
                     ContractLog::Query { response } => {
                         tracing::info!("Contract query response: {}", response);
                     }
+                    ContractLog::Codes { codes } => {
+                        for code in codes {
+                            tracing::info!(
+                                "Code {} by {}, checksum {}",
+                                code.code_id,
+                                code.creator,
+                                cosmwasm_std::HexBinary::from(code.data_hash).to_hex()
+                            );
+                        }
+                    }
+                    ContractLog::Contracts { addrs } => {
+                        for addr in addrs {
+                            tracing::info!("{}", addr);
+                        }
+                    }
+                    ContractLog::History { entries } => {
+                        for entry in entries {
+                            tracing::info!(
+                                "{:?} to code {} at height {}, msg: {}",
+                                entry.operation(),
+                                entry.code_id,
+                                entry.updated.map(|u| u.block_height).unwrap_or_default(),
+                                String::from_utf8_lossy(&entry.msg)
+                            );
+                        }
+                    }
+                    ContractLog::Raw { value } => match value {
+                        Some(value) => tracing::info!("Value: {}", value),
+                        None => tracing::info!("Key not set"),
+                    },
+                    ContractLog::State { json } => {
+                        tracing::info!("{}", json);
+                    }
+                    ContractLog::StateSaved { path, entries } => {
+                        tracing::info!("Saved {} entries to {}", entries, path.display());
+                    }
+                    ContractLog::Download {
+                        path,
+                        checksum,
+                        size,
+                    } => {
+                        tracing::info!(
+                            "Saved {} bytes to {}, checksum {}",
+                            size,
+                            path.display(),
+                            checksum
+                        );
+                    }
+                })
+                .await?;
+        }
+        Command::Staking(StakingArgs { command }) => {
+            let command = match &command {
+                StakingCommand::Show { address } => {
+                    if address.is_none() {
+                        StakingCommand::Show {
+                            address: Some(ctx.client_addr().await?.to_string()),
+                        }
+                    } else {
+                        command.clone()
+                    }
+                }
+                _ => command.clone(),
+            };
+            command
+                .run(ctx.any_client().await?, |line| match line {
+                    StakingLog::Show {
+                        addr,
+                        delegations,
+                        unbonding_delegations,
+                        rewards,
+                    } => {
+                        tracing::info!("Delegator address: {}", addr);
+                        for delegation in delegations {
+                            if let (Some(info), Some(balance)) =
+                                (delegation.delegation, delegation.balance)
+                            {
+                                tracing::info!(
+                                    "Delegated {}{} to {}",
+                                    balance.amount,
+                                    balance.denom,
+                                    info.validator_address
+                                );
+                            }
+                        }
+                        for unbonding in unbonding_delegations {
+                            for entry in unbonding.entries {
+                                tracing::info!(
+                                    "Unbonding {} from {} (creation height {})",
+                                    entry.balance,
+                                    unbonding.validator_address,
+                                    entry.creation_height
+                                );
+                            }
+                        }
+                        for reward in rewards.total {
+                            tracing::info!("Pending rewards: {}{}", reward.amount, reward.denom);
+                        }
+                    }
+                    StakingLog::Delegate {
+                        validator,
+                        amount,
+                        tx_resp,
+                    } => {
+                        tracing::info!(
+                            "Delegated {}{} to {}",
+                            amount.amount,
+                            amount.denom,
+                            validator
+                        );
+                        tracing::info!("Tx hash: {}", tx_resp.txhash);
+                    }
+                    StakingLog::Undelegate {
+                        validator,
+                        amount,
+                        tx_resp,
+                    } => {
+                        tracing::info!(
+                            "Started unbonding {}{} from {}",
+                            amount.amount,
+                            amount.denom,
+                            validator
+                        );
+                        tracing::info!("Tx hash: {}", tx_resp.txhash);
+                    }
+                    StakingLog::Redelegate {
+                        from,
+                        to,
+                        amount,
+                        tx_resp,
+                    } => {
+                        tracing::info!(
+                            "Redelegated {}{} from {} to {}",
+                            amount.amount,
+                            amount.denom,
+                            from,
+                            to
+                        );
+                        tracing::info!("Tx hash: {}", tx_resp.txhash);
+                    }
+                    StakingLog::CancelUnbonding {
+                        validator,
+                        amount,
+                        tx_resp,
+                    } => {
+                        tracing::info!(
+                            "Cancelled unbonding {}{} from {}",
+                            amount.amount,
+                            amount.denom,
+                            validator
+                        );
+                        tracing::info!("Tx hash: {}", tx_resp.txhash);
+                    }
+                    StakingLog::WithdrawRewards { validator, tx_resp } => {
+                        match validator {
+                            Some(validator) => {
+                                tracing::info!("Withdrew rewards from {}", validator)
+                            }
+                            None => tracing::info!("Withdrew rewards from all validators"),
+                        }
+                        tracing::info!("Tx hash: {}", tx_resp.txhash);
+                    }
+                    StakingLog::SetWithdrawAddress { addr, tx_resp } => {
+                        tracing::info!("Set withdraw address to {}", addr);
+                        tracing::info!("Tx hash: {}", tx_resp.txhash);
+                    }
+                    StakingLog::WithdrawCommission { validator, tx_resp } => {
+                        tracing::info!("Withdrew commission for {}", validator);
+                        tracing::info!("Tx hash: {}", tx_resp.txhash);
+                    }
                 })
                 .await?;
         }
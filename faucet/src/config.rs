@@ -206,6 +206,7 @@ impl TryFrom<ConfigInit> for Config {
                     },
                     ConfigChainAddrKindName::Evm => AddrKind::Evm,
                 },
+                ..Default::default()
             },
             minimum_credit_balance_threshhold: config
                 .minimum_credit_balance_threshhold
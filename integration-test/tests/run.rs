@@ -31,6 +31,7 @@ async fn run() {
         address_kind: AddrKind::Cosmos {
             prefix: "wasm".to_string(),
         },
+        ..Default::default()
     };
 
     let mnemonic = std::env::var("CLIMB_TEST_MNEMONIC")
@@ -47,6 +47,19 @@ impl Address {
             AddrKind::Evm => EvmAddr::new_pub_key(pub_key).map(Self::Evm),
         }
     }
+
+    pub fn try_from_multisig_pub_key(
+        threshold: u32,
+        pub_keys: &[tendermint::PublicKey],
+        addr_kind: &AddrKind,
+    ) -> Result<Address> {
+        match addr_kind {
+            AddrKind::Cosmos { prefix } => {
+                CosmosAddr::new_multisig_pub_key(threshold, pub_keys, prefix).map(Self::Cosmos)
+            }
+            AddrKind::Evm => bail!("Multisig addresses are not supported for EVM"),
+        }
+    }
 }
 
 // the display impl ignores the kind
@@ -158,17 +171,31 @@ impl AddrKind {
     pub fn address_from_pub_key(&self, pub_key: &tendermint::PublicKey) -> Result<Address> {
         Address::try_from_pub_key(pub_key, self)
     }
+
+    pub fn address_from_multisig_pub_key(
+        &self,
+        threshold: u32,
+        pub_keys: &[tendermint::PublicKey],
+    ) -> Result<Address> {
+        Address::try_from_multisig_pub_key(threshold, pub_keys, self)
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::{Address, CosmosAddr, EvmAddr};
+    use super::{AddrKind, Address, CosmosAddr, EvmAddr};
 
     // TODO get addresses that are actually the same underlying public key
 
     const TEST_COSMOS_STR: &str = "osmo1h5qke5tzc0fgz93wcxg8da2en3advfect0gh4a";
     const TEST_COSMOS_PREFIX: &str = "osmo";
     const TEST_EVM_STR: &str = "0xb794f5ea0ba39494ce839613fffba74279579268";
+    // compressed secp256k1 points G, 2G, 3G
+    const TEST_PUB_KEYS_HEX: [&str; 3] = [
+        "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+        "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+    ];
 
     #[test]
     fn test_basic_roundtrip_evm() {
@@ -240,4 +267,46 @@ mod test {
         // let addr_string_evm = addr_string.convert_into_evm().unwrap();
         // assert_eq!(addr_string_evm.to_string(), "0xb794f5ea0ba39494ce839613fffba74279579268");
     }
+
+    #[test]
+    fn test_multisig_address_cosmos() {
+        let pub_keys: Vec<tendermint::PublicKey> = TEST_PUB_KEYS_HEX
+            .iter()
+            .map(|key| {
+                tendermint::PublicKey::from_raw_secp256k1(&const_hex::decode(key).unwrap()).unwrap()
+            })
+            .collect();
+
+        let addr_kind = AddrKind::Cosmos {
+            prefix: TEST_COSMOS_PREFIX.to_string(),
+        };
+
+        let addr = addr_kind
+            .address_from_multisig_pub_key(2, &pub_keys)
+            .unwrap();
+        assert_eq!(
+            addr.to_string(),
+            "osmo16nsuts7ccq7c64tat6sm4uar2ammtwgvjpd852"
+        );
+
+        // key order is part of the multisig
+        let reordered = [pub_keys[1], pub_keys[0], pub_keys[2]];
+        let addr = addr_kind
+            .address_from_multisig_pub_key(2, &reordered)
+            .unwrap();
+        assert_eq!(
+            addr.to_string(),
+            "osmo16jptz4qg7r43qkg2nruvrph5cfma9wpg6aafp4"
+        );
+
+        assert!(addr_kind
+            .address_from_multisig_pub_key(0, &pub_keys)
+            .is_err());
+        assert!(addr_kind
+            .address_from_multisig_pub_key(4, &pub_keys)
+            .is_err());
+        assert!(AddrKind::Evm
+            .address_from_multisig_pub_key(2, &pub_keys)
+            .is_err());
+    }
 }
@@ -59,6 +59,22 @@ impl CosmosAddr {
         }
     }
 
+    /// the address of a `LegacyAminoPubKey` threshold multisig made up of these keys
+    /// order matters, it must be the same order as the keys in the on-chain multisig
+    pub fn new_multisig_pub_key(
+        threshold: u32,
+        pub_keys: &[tendermint::PublicKey],
+        prefix: &str,
+    ) -> Result<Self> {
+        use tendermint::crypto::Sha256;
+
+        // multisig addresses are sha256(amino bytes)[..20], same as the cosmos-sdk
+        let digest =
+            tendermint::crypto::default::Sha256::digest(multisig_amino_bytes(threshold, pub_keys)?);
+
+        Self::new_bytes(digest[..20].to_vec(), prefix)
+    }
+
     // if the prefix is supplied, this will attempt to validate the address against the prefix to ensure they match
     // if you just have a public key, use new_cosmos_pub_key instead
     pub fn new_str(value: &str, prefix: Option<&str>) -> Result<Self> {
@@ -100,6 +116,53 @@ impl CosmosAddr {
     }
 }
 
+// amino encoding of a tendermint/PubKeyMultisigThreshold
+// see https://github.com/cosmos/cosmos-sdk/blob/main/crypto/keys/multisig/amino.go
+fn multisig_amino_bytes(threshold: u32, pub_keys: &[tendermint::PublicKey]) -> Result<Vec<u8>> {
+    if threshold == 0 || threshold as usize > pub_keys.len() {
+        bail!(
+            "invalid multisig threshold {} for {} keys",
+            threshold,
+            pub_keys.len()
+        );
+    }
+
+    // amino prefix for "tendermint/PubKeyMultisigThreshold"
+    let mut bytes = vec![0x22, 0xc1, 0xf7, 0xe2];
+
+    // field 1: threshold
+    bytes.push(0x08);
+    push_uvarint(&mut bytes, threshold as u64);
+
+    // field 2: each public key, amino-encoded with its own prefix
+    for pub_key in pub_keys {
+        let mut key_bytes = match pub_key {
+            // "tendermint/PubKeySecp256k1"
+            tendermint::PublicKey::Secp256k1(_) => vec![0xeb, 0x5a, 0xe9, 0x87],
+            // "tendermint/PubKeyEd25519"
+            tendermint::PublicKey::Ed25519(_) => vec![0x16, 0x24, 0xde, 0x64],
+            _ => bail!("Invalid public key type, multisig only supports secp256k1 and ed25519"),
+        };
+        let raw = pub_key.to_bytes();
+        push_uvarint(&mut key_bytes, raw.len() as u64);
+        key_bytes.extend(raw);
+
+        bytes.push(0x12);
+        push_uvarint(&mut bytes, key_bytes.len() as u64);
+        bytes.extend(key_bytes);
+    }
+
+    Ok(bytes)
+}
+
+fn push_uvarint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
 // the display impl ignores the prefix
 impl std::fmt::Display for CosmosAddr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
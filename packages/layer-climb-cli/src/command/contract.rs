@@ -1,6 +1,12 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::Subcommand;
-use layer_climb::{prelude::*, proto::abci::TxResponse};
+use layer_climb::{
+    prelude::*,
+    proto::{
+        abci::TxResponse,
+        wasm::{CodeInfoResponse, ContractCodeHistoryEntry, Model},
+    },
+};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Subcommand)]
@@ -29,6 +35,9 @@ pub enum ContractCommand {
         /// Optional funds to send, if not set no funds will be sent
         #[arg(long)]
         funds_amount: Option<String>,
+        /// Simulates the tx instead of broadcasting it
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
     },
 
     /// Executes a contract on the chain
@@ -45,6 +54,9 @@ pub enum ContractCommand {
         /// Optional funds to send, if not set no funds will be sent
         #[arg(long)]
         funds_amount: Option<String>,
+        /// Simulates the tx instead of broadcasting it
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
     },
 
     /// Queries a contract on the chain
@@ -56,6 +68,57 @@ pub enum ContractCommand {
         #[arg(long)]
         msg: Option<String>,
     },
+
+    /// Lists every stored code
+    Codes {},
+
+    /// Lists the contracts instantiated from a code
+    ContractsByCode {
+        #[arg(long)]
+        code_id: u64,
+    },
+
+    /// Lists the contracts instantiated by an address
+    ContractsByCreator {
+        #[arg(long)]
+        creator: String,
+    },
+
+    /// Shows a contract's instantiation and migrations
+    History {
+        #[arg(long)]
+        address: String,
+    },
+
+    /// Reads a single raw key from a contract's storage
+    Raw {
+        #[arg(long)]
+        address: String,
+        /// The storage key, as a utf8 string unless `--hex` is set
+        #[arg(long)]
+        key: String,
+        /// The key is hex-encoded
+        #[arg(long, default_value_t = false)]
+        hex: bool,
+    },
+
+    /// Dumps all of a contract's raw storage as json, e.g. to diff it between environments
+    State {
+        #[arg(long)]
+        address: String,
+        /// Writes the json to this file instead of logging it
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Downloads a code's wasm bytes, after checking them against the code's checksum
+    Download {
+        #[arg(long)]
+        code_id: u64,
+        /// Where to write the .wasm file
+        #[arg(long)]
+        out: PathBuf,
+    },
 }
 
 impl ContractCommand {
@@ -80,53 +143,85 @@ impl ContractCommand {
                 label,
                 funds_denom,
                 funds_amount,
+                dry_run,
             } => {
-                let (addr, tx_resp) = client
-                    .as_signing()
-                    .contract_instantiate(
-                        client.as_signing().addr.clone(),
-                        *code_id,
-                        label.clone().unwrap_or_default(),
-                        &contract_str_to_msg(msg.as_deref())?,
-                        get_funds(
-                            &client.as_querier().chain_config,
-                            funds_denom.clone(),
-                            funds_amount.clone(),
-                        ),
-                        None,
-                    )
-                    .await?;
+                let signing_client = client.as_signing();
+                let msg = contract_str_to_msg(msg.as_deref())?;
+                let funds = get_funds(
+                    &client.as_querier().chain_config,
+                    funds_denom.clone(),
+                    funds_amount.clone(),
+                );
 
-                log(ContractLog::Instantiate {
-                    addr,
-                    tx_resp: Box::new(tx_resp),
-                });
+                if *dry_run {
+                    let simulation = signing_client
+                        .tx_builder()
+                        .simulate([proto_into_any(&signing_client.contract_instantiate_msg(
+                            signing_client.addr.clone(),
+                            *code_id,
+                            label.clone().unwrap_or_default(),
+                            funds,
+                            &msg,
+                        )?)?])
+                        .await?;
+
+                    log(ContractLog::Simulate {
+                        simulation: Box::new(simulation),
+                    });
+                } else {
+                    let (addr, tx_resp) = signing_client
+                        .contract_instantiate(
+                            signing_client.addr.clone(),
+                            *code_id,
+                            label.clone().unwrap_or_default(),
+                            &msg,
+                            funds,
+                            None,
+                        )
+                        .await?;
+
+                    log(ContractLog::Instantiate {
+                        addr,
+                        tx_resp: Box::new(tx_resp),
+                    });
+                }
             }
             ContractCommand::Execute {
                 address,
                 msg,
                 funds_denom,
                 funds_amount,
+                dry_run,
             } => {
+                let signing_client = client.as_signing();
                 let address = client.as_querier().chain_config.parse_address(address)?;
+                let msg = contract_str_to_msg(msg.as_deref())?;
+                let funds = get_funds(
+                    &client.as_querier().chain_config,
+                    funds_denom.clone(),
+                    funds_amount.clone(),
+                );
 
-                let tx_resp = client
-                    .as_signing()
-                    .contract_execute(
-                        &address,
-                        &contract_str_to_msg(msg.as_deref())?,
-                        get_funds(
-                            &client.as_querier().chain_config,
-                            funds_denom.clone(),
-                            funds_amount.clone(),
-                        ),
-                        None,
-                    )
-                    .await?;
+                if *dry_run {
+                    let simulation = signing_client
+                        .tx_builder()
+                        .simulate([proto_into_any(
+                            &signing_client.contract_execute_msg(&address, funds, &msg)?,
+                        )?])
+                        .await?;
 
-                log(ContractLog::Execute {
-                    tx_resp: Box::new(tx_resp),
-                });
+                    log(ContractLog::Simulate {
+                        simulation: Box::new(simulation),
+                    });
+                } else {
+                    let tx_resp = signing_client
+                        .contract_execute(&address, &msg, funds, None)
+                        .await?;
+
+                    log(ContractLog::Execute {
+                        tx_resp: Box::new(tx_resp),
+                    });
+                }
             }
             ContractCommand::Query { address, msg } => {
                 let address = client.as_querier().chain_config.parse_address(address)?;
@@ -141,6 +236,84 @@ impl ContractCommand {
                     response: resp.to_string(),
                 });
             }
+            ContractCommand::Codes {} => {
+                let codes = client.as_querier().contract_codes(None).await?;
+
+                log(ContractLog::Codes { codes });
+            }
+            ContractCommand::ContractsByCode { code_id } => {
+                let addrs = client
+                    .as_querier()
+                    .contracts_by_code(*code_id, None)
+                    .await?;
+
+                log(ContractLog::Contracts { addrs });
+            }
+            ContractCommand::ContractsByCreator { creator } => {
+                let creator = client.as_querier().chain_config.parse_address(creator)?;
+                let addrs = client
+                    .as_querier()
+                    .contracts_by_creator(&creator, None)
+                    .await?;
+
+                log(ContractLog::Contracts { addrs });
+            }
+            ContractCommand::History { address } => {
+                let address = client.as_querier().chain_config.parse_address(address)?;
+                let entries = client.as_querier().contract_history(&address, None).await?;
+
+                log(ContractLog::History { entries });
+            }
+            ContractCommand::Raw { address, key, hex } => {
+                let address = client.as_querier().chain_config.parse_address(address)?;
+                let key = match hex {
+                    true => cosmwasm_std::HexBinary::from_hex(key)
+                        .map_err(|e| anyhow!("{e:?}"))?
+                        .to_vec(),
+                    false => key.as_bytes().to_vec(),
+                };
+
+                let value = client
+                    .as_querier()
+                    .contract_raw_state(&address, &key)
+                    .await?;
+
+                log(ContractLog::Raw {
+                    value: value.map(|value| display_bytes(&value)),
+                });
+            }
+            ContractCommand::State { address, out } => {
+                let address = client.as_querier().chain_config.parse_address(address)?;
+                let models = client
+                    .as_querier()
+                    .all_contract_state(&address, None)
+                    .await?;
+
+                let entries = models.len();
+                let json = serde_json::to_string_pretty(&state_json(models))?;
+
+                match out {
+                    Some(out) => {
+                        tokio::fs::write(out, json).await?;
+                        log(ContractLog::StateSaved {
+                            path: out.clone(),
+                            entries,
+                        });
+                    }
+                    None => log(ContractLog::State { json }),
+                }
+            }
+            ContractCommand::Download { code_id, out } => {
+                let wasm_byte_code = client.as_querier().contract_code_bytes(*code_id).await?;
+                let checksum = cosmwasm_std::Checksum::generate(&wasm_byte_code);
+                tokio::fs::write(out, &wasm_byte_code).await?;
+
+                log(ContractLog::Download {
+                    path: out.clone(),
+                    checksum: checksum.to_hex(),
+                    size: wasm_byte_code.len(),
+                });
+            }
         }
         Ok(())
     }
@@ -160,6 +333,31 @@ fn get_funds(
     }
 }
 
+// utf8 if possible (most contracts store json), otherwise hex
+fn display_bytes(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => s.to_string(),
+        Err(_) => cosmwasm_std::HexBinary::from(bytes).to_hex(),
+    }
+}
+
+// keys are hex since they're length-prefixed namespaces, values are json where they parse as json
+fn state_json(models: Vec<Model>) -> serde_json::Value {
+    models
+        .into_iter()
+        .map(|model| {
+            let value = serde_json::from_slice(&model.value).unwrap_or_else(|_| {
+                serde_json::Value::String(cosmwasm_std::Binary::from(model.value).to_base64())
+            });
+
+            serde_json::json!({
+                "key": cosmwasm_std::HexBinary::from(model.key).to_hex(),
+                "value": value,
+            })
+        })
+        .collect()
+}
+
 pub enum ContractLog {
     Upload {
         code_id: u64,
@@ -172,7 +370,36 @@ pub enum ContractLog {
     Execute {
         tx_resp: Box<TxResponse>,
     },
+    /// from `--dry-run`, nothing was broadcast
+    Simulate {
+        simulation: Box<SimulationResult>,
+    },
     Query {
         response: String,
     },
+    Codes {
+        codes: Vec<CodeInfoResponse>,
+    },
+    Contracts {
+        addrs: Vec<Address>,
+    },
+    History {
+        entries: Vec<ContractCodeHistoryEntry>,
+    },
+    /// None if the key isn't set
+    Raw {
+        value: Option<String>,
+    },
+    State {
+        json: String,
+    },
+    StateSaved {
+        path: PathBuf,
+        entries: usize,
+    },
+    Download {
+        path: PathBuf,
+        checksum: String,
+        size: usize,
+    },
 }
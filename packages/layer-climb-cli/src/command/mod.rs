@@ -1,5 +1,7 @@
 mod contract;
+mod staking;
 mod wallet;
 
 pub use contract::*;
+pub use staking::*;
 pub use wallet::*;
@@ -0,0 +1,280 @@
+use anyhow::Result;
+use clap::Subcommand;
+use layer_climb::{prelude::*, proto::abci::TxResponse};
+
+// validator addresses are the operator (e.g. `cosmosvaloper1...`) addresses
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum StakingCommand {
+    /// Shows the delegations, unbonding delegations and pending rewards for a given address
+    /// If no address is provided, the client's address will be used
+    Show {
+        #[arg(long)]
+        address: Option<String>,
+    },
+    /// Delegates funds to a validator
+    Delegate {
+        /// The validator to delegate to
+        #[arg(long)]
+        validator: String,
+        /// The amount to delegate
+        #[arg(long)]
+        amount: u128,
+        /// The denom of the funds to delegate, if not set will use the chain's bond denom
+        #[arg(long)]
+        denom: Option<String>,
+    },
+    /// Starts unbonding funds from a validator
+    Undelegate {
+        /// The validator to undelegate from
+        #[arg(long)]
+        validator: String,
+        /// The amount to undelegate
+        #[arg(long)]
+        amount: u128,
+        /// The denom of the funds to undelegate, if not set will use the chain's bond denom
+        #[arg(long)]
+        denom: Option<String>,
+    },
+    /// Moves a delegation from one validator to another, without unbonding
+    Redelegate {
+        /// The validator to move the delegation from
+        #[arg(long)]
+        from: String,
+        /// The validator to move the delegation to
+        #[arg(long)]
+        to: String,
+        /// The amount to redelegate
+        #[arg(long)]
+        amount: u128,
+        /// The denom of the funds to redelegate, if not set will use the chain's bond denom
+        #[arg(long)]
+        denom: Option<String>,
+    },
+    /// Cancels an unbonding delegation, delegating the funds back to the validator
+    CancelUnbonding {
+        /// The validator the funds are unbonding from
+        #[arg(long)]
+        validator: String,
+        /// The amount to cancel, at most the unbonding entry's balance
+        #[arg(long)]
+        amount: u128,
+        /// The denom of the unbonding funds, if not set will use the chain's bond denom
+        #[arg(long)]
+        denom: Option<String>,
+        /// The height the unbonding started at, as shown by `show`
+        #[arg(long)]
+        creation_height: u64,
+    },
+    /// Withdraws the pending staking rewards
+    WithdrawRewards {
+        /// The validator to withdraw the rewards from, if not set will withdraw from all of them
+        #[arg(long)]
+        validator: Option<String>,
+    },
+    /// Sets the address staking rewards and commission are withdrawn to
+    SetWithdrawAddress {
+        #[arg(long)]
+        address: String,
+    },
+    /// Withdraws a validator's accumulated commission, must be run by the validator's operator
+    WithdrawCommission {
+        #[arg(long)]
+        validator: String,
+    },
+}
+
+impl StakingCommand {
+    pub async fn run(&self, client: impl Into<AnyClient>, log: impl Fn(StakingLog)) -> Result<()> {
+        let client = client.into();
+        match self {
+            StakingCommand::Show { address } => {
+                let addr = match address {
+                    None => client.as_signing().addr.clone(),
+                    Some(address) => client.as_querier().chain_config.parse_address(address)?,
+                };
+
+                let querier = client.as_querier();
+                let delegations = querier.delegations(addr.clone(), None).await?;
+                let unbonding_delegations =
+                    querier.unbonding_delegations(addr.clone(), None).await?;
+                let rewards = querier.delegator_rewards(addr.clone()).await?;
+
+                log(StakingLog::Show {
+                    addr,
+                    delegations,
+                    unbonding_delegations,
+                    rewards: Box::new(rewards),
+                });
+            }
+            StakingCommand::Delegate {
+                validator,
+                amount,
+                denom,
+            } => {
+                let amount = staking_coin(&client, *amount, denom.as_deref()).await?;
+                let tx_resp = client
+                    .as_signing()
+                    .staking_delegate(validator, amount.clone(), None)
+                    .await?;
+                log(StakingLog::Delegate {
+                    validator: validator.clone(),
+                    amount,
+                    tx_resp: Box::new(tx_resp),
+                });
+            }
+            StakingCommand::Undelegate {
+                validator,
+                amount,
+                denom,
+            } => {
+                let amount = staking_coin(&client, *amount, denom.as_deref()).await?;
+                let tx_resp = client
+                    .as_signing()
+                    .staking_undelegate(validator, amount.clone(), None)
+                    .await?;
+                log(StakingLog::Undelegate {
+                    validator: validator.clone(),
+                    amount,
+                    tx_resp: Box::new(tx_resp),
+                });
+            }
+            StakingCommand::Redelegate {
+                from,
+                to,
+                amount,
+                denom,
+            } => {
+                let amount = staking_coin(&client, *amount, denom.as_deref()).await?;
+                let tx_resp = client
+                    .as_signing()
+                    .staking_redelegate(from, to, amount.clone(), None)
+                    .await?;
+                log(StakingLog::Redelegate {
+                    from: from.clone(),
+                    to: to.clone(),
+                    amount,
+                    tx_resp: Box::new(tx_resp),
+                });
+            }
+            StakingCommand::CancelUnbonding {
+                validator,
+                amount,
+                denom,
+                creation_height,
+            } => {
+                let amount = staking_coin(&client, *amount, denom.as_deref()).await?;
+                let tx_resp = client
+                    .as_signing()
+                    .staking_cancel_unbonding_delegation(
+                        validator,
+                        amount.clone(),
+                        *creation_height,
+                        None,
+                    )
+                    .await?;
+                log(StakingLog::CancelUnbonding {
+                    validator: validator.clone(),
+                    amount,
+                    tx_resp: Box::new(tx_resp),
+                });
+            }
+            StakingCommand::WithdrawRewards { validator } => {
+                let tx_resp = match validator {
+                    Some(validator) => {
+                        client
+                            .as_signing()
+                            .distribution_withdraw_delegator_reward(validator, None)
+                            .await?
+                    }
+                    None => {
+                        client
+                            .as_signing()
+                            .distribution_withdraw_all_delegator_rewards(None)
+                            .await?
+                    }
+                };
+                log(StakingLog::WithdrawRewards {
+                    validator: validator.clone(),
+                    tx_resp: Box::new(tx_resp),
+                });
+            }
+            StakingCommand::SetWithdrawAddress { address } => {
+                let addr = client.as_querier().chain_config.parse_address(address)?;
+                let tx_resp = client
+                    .as_signing()
+                    .distribution_set_withdraw_address(&addr, None)
+                    .await?;
+                log(StakingLog::SetWithdrawAddress {
+                    addr,
+                    tx_resp: Box::new(tx_resp),
+                });
+            }
+            StakingCommand::WithdrawCommission { validator } => {
+                let tx_resp = client
+                    .as_signing()
+                    .distribution_withdraw_validator_commission(validator, None)
+                    .await?;
+                log(StakingLog::WithdrawCommission {
+                    validator: validator.clone(),
+                    tx_resp: Box::new(tx_resp),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+// the bond denom isn't necessarily the gas denom, so it comes from the staking params
+async fn staking_coin(client: &AnyClient, amount: u128, denom: Option<&str>) -> Result<Coin> {
+    match denom {
+        Some(denom) => Ok(new_coin(amount, denom)),
+        None => {
+            let params = client.as_querier().staking_params().await?;
+            Ok(new_coin(amount, params.bond_denom))
+        }
+    }
+}
+
+pub enum StakingLog {
+    Show {
+        addr: Address,
+        delegations: Vec<layer_climb::proto::staking::DelegationResponse>,
+        unbonding_delegations: Vec<layer_climb::proto::staking::UnbondingDelegation>,
+        rewards: Box<layer_climb::proto::distribution::QueryDelegationTotalRewardsResponse>,
+    },
+    Delegate {
+        validator: String,
+        amount: Coin,
+        tx_resp: Box<TxResponse>,
+    },
+    Undelegate {
+        validator: String,
+        amount: Coin,
+        tx_resp: Box<TxResponse>,
+    },
+    Redelegate {
+        from: String,
+        to: String,
+        amount: Coin,
+        tx_resp: Box<TxResponse>,
+    },
+    CancelUnbonding {
+        validator: String,
+        amount: Coin,
+        tx_resp: Box<TxResponse>,
+    },
+    WithdrawRewards {
+        /// None if withdrawn from all validators
+        validator: Option<String>,
+        tx_resp: Box<TxResponse>,
+    },
+    SetWithdrawAddress {
+        addr: Address,
+        tx_resp: Box<TxResponse>,
+    },
+    WithdrawCommission {
+        validator: String,
+        tx_resp: Box<TxResponse>,
+    },
+}
@@ -10,12 +10,40 @@ pub struct ChainConfig {
     pub grpc_endpoint: Option<String>,
     // if not specified, will fallback to `grpc_endpoint`
     pub grpc_web_endpoint: Option<String>,
+    // additional endpoints for failover, tried in order after the single endpoint above (if set)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rpc_endpoints: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grpc_endpoints: Vec<String>,
+    // if empty (and `grpc_web_endpoint` isn't set), will fallback to the grpc endpoints
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grpc_web_endpoints: Vec<String>,
     // not micro-units, e.g. 0.025 would be a typical value
     pub gas_price: f32,
     pub gas_denom: String,
     pub address_kind: AddrKind,
 }
 
+// mostly so new fields don't break struct literals, i.e. `ChainConfig { chain_id, ..Default::default() }`
+impl Default for ChainConfig {
+    fn default() -> Self {
+        Self {
+            chain_id: ChainId::new(""),
+            rpc_endpoint: None,
+            grpc_endpoint: None,
+            grpc_web_endpoint: None,
+            rpc_endpoints: Vec::new(),
+            grpc_endpoints: Vec::new(),
+            grpc_web_endpoints: Vec::new(),
+            gas_price: 0.0,
+            gas_denom: String::new(),
+            address_kind: AddrKind::Cosmos {
+                prefix: "cosmos".to_string(),
+            },
+        }
+    }
+}
+
 impl ChainConfig {
     pub fn ibc_client_revision(&self) -> Result<u64> {
         // > Tendermint chains wishing to use revisions to maintain persistent IBC connections even across height-resetting upgrades
@@ -30,6 +58,28 @@ impl ChainConfig {
             .unwrap_or_default())
     }
 
+    /// All the rpc endpoints in priority order, i.e. `rpc_endpoint` followed by `rpc_endpoints`
+    pub fn all_rpc_endpoints(&self) -> Vec<&str> {
+        ordered_endpoints(self.rpc_endpoint.as_ref(), &self.rpc_endpoints)
+    }
+
+    /// All the grpc endpoints in priority order, i.e. `grpc_endpoint` followed by `grpc_endpoints`
+    pub fn all_grpc_endpoints(&self) -> Vec<&str> {
+        ordered_endpoints(self.grpc_endpoint.as_ref(), &self.grpc_endpoints)
+    }
+
+    /// All the grpc-web endpoints in priority order, falling back to the grpc endpoints if none are set
+    pub fn all_grpc_web_endpoints(&self) -> Vec<&str> {
+        let endpoints =
+            ordered_endpoints(self.grpc_web_endpoint.as_ref(), &self.grpc_web_endpoints);
+
+        if endpoints.is_empty() {
+            self.all_grpc_endpoints()
+        } else {
+            endpoints
+        }
+    }
+
     pub fn parse_address(&self, value: &str) -> Result<Address> {
         self.address_kind.parse_address(value)
     }
@@ -37,6 +87,27 @@ impl ChainConfig {
     pub fn address_from_pub_key(&self, pub_key: &tendermint::PublicKey) -> Result<Address> {
         self.address_kind.address_from_pub_key(pub_key)
     }
+
+    pub fn address_from_multisig_pub_key(
+        &self,
+        threshold: u32,
+        pub_keys: &[tendermint::PublicKey],
+    ) -> Result<Address> {
+        self.address_kind
+            .address_from_multisig_pub_key(threshold, pub_keys)
+    }
+}
+
+fn ordered_endpoints<'a>(primary: Option<&'a String>, rest: &'a [String]) -> Vec<&'a str> {
+    let mut endpoints: Vec<&str> = Vec::with_capacity(rest.len() + 1);
+
+    for endpoint in primary.into_iter().chain(rest) {
+        if !endpoints.contains(&endpoint.as_str()) {
+            endpoints.push(endpoint);
+        }
+    }
+
+    endpoints
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -64,3 +135,121 @@ impl Display for ChainId {
         write!(f, "{}", self.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoints(primary: Option<&str>, rest: &[&str]) -> ChainConfig {
+        ChainConfig {
+            rpc_endpoint: primary.map(ToString::to_string),
+            rpc_endpoints: rest.iter().map(ToString::to_string).collect(),
+            grpc_endpoint: primary.map(ToString::to_string),
+            grpc_endpoints: rest.iter().map(ToString::to_string).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn endpoint_order() {
+        let config = endpoints(Some("a"), &["b", "c"]);
+        assert_eq!(config.all_rpc_endpoints(), vec!["a", "b", "c"]);
+        assert_eq!(config.all_grpc_endpoints(), vec!["a", "b", "c"]);
+
+        let config = endpoints(None, &["b", "c"]);
+        assert_eq!(config.all_rpc_endpoints(), vec!["b", "c"]);
+
+        let config = endpoints(Some("a"), &[]);
+        assert_eq!(config.all_rpc_endpoints(), vec!["a"]);
+
+        assert!(endpoints(None, &[]).all_rpc_endpoints().is_empty());
+    }
+
+    #[test]
+    fn endpoint_duplicates() {
+        // the primary endpoint repeated in the list keeps its priority
+        let config = endpoints(Some("a"), &["b", "a", "c", "b"]);
+        assert_eq!(config.all_rpc_endpoints(), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn grpc_web_endpoint_fallback() {
+        let mut config = endpoints(Some("a"), &["b"]);
+        assert_eq!(config.all_grpc_web_endpoints(), vec!["a", "b"]);
+
+        config.grpc_web_endpoints = vec!["web-b".to_string()];
+        assert_eq!(config.all_grpc_web_endpoints(), vec!["web-b"]);
+
+        config.grpc_web_endpoint = Some("web-a".to_string());
+        assert_eq!(config.all_grpc_web_endpoints(), vec!["web-a", "web-b"]);
+    }
+
+    #[test]
+    fn legacy_single_endpoint_config() {
+        // a config from before the endpoint lists existed
+        let config: ChainConfig = serde_json::from_str(
+            r#"{
+                "chain_id": "cosmoshub-4",
+                "rpc_endpoint": "https://rpc.example.com",
+                "grpc_endpoint": "https://grpc.example.com",
+                "grpc_web_endpoint": null,
+                "gas_price": 0.025,
+                "gas_denom": "uatom",
+                "address_kind": {"cosmos": {"prefix": "cosmos"}}
+            }"#,
+        )
+        .unwrap();
+
+        assert!(config.rpc_endpoints.is_empty());
+        assert!(config.grpc_endpoints.is_empty());
+        assert!(config.grpc_web_endpoints.is_empty());
+        assert_eq!(config.all_rpc_endpoints(), vec!["https://rpc.example.com"]);
+        assert_eq!(
+            config.all_grpc_endpoints(),
+            vec!["https://grpc.example.com"]
+        );
+        assert_eq!(
+            config.all_grpc_web_endpoints(),
+            vec!["https://grpc.example.com"]
+        );
+
+        // and serializes back the same way, without the empty lists
+        let value = serde_json::to_value(&config).unwrap();
+        let keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert!(!keys.contains(&"rpc_endpoints"));
+        assert!(!keys.contains(&"grpc_endpoints"));
+        assert!(!keys.contains(&"grpc_web_endpoints"));
+    }
+
+    #[test]
+    fn multi_endpoint_config() {
+        let config: ChainConfig = serde_json::from_str(
+            r#"{
+                "chain_id": "cosmoshub-4",
+                "rpc_endpoint": null,
+                "grpc_endpoint": "https://grpc-1.example.com",
+                "grpc_web_endpoint": null,
+                "rpc_endpoints": ["https://rpc-1.example.com", "https://rpc-2.example.com"],
+                "grpc_endpoints": ["https://grpc-2.example.com"],
+                "gas_price": 0.025,
+                "gas_denom": "uatom",
+                "address_kind": {"cosmos": {"prefix": "cosmos"}}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.all_rpc_endpoints(),
+            vec!["https://rpc-1.example.com", "https://rpc-2.example.com"]
+        );
+        assert_eq!(
+            config.all_grpc_endpoints(),
+            vec!["https://grpc-1.example.com", "https://grpc-2.example.com"]
+        );
+    }
+}
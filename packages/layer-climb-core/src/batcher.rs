@@ -0,0 +1,521 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex, Weak},
+};
+
+use futures::{
+    channel::oneshot,
+    future::{select, Either, Shared},
+    FutureExt,
+};
+
+use crate::{error::parse_msg_index, events::Event, prelude::*};
+
+/// Coalesces messages submitted from many tasks into fewer txs
+/// a batch is broadcast once it reaches `max_msgs`, or `window` after its first message was submitted
+/// (if more messages arrive before its driver gets to it, it's sent in chunks of `max_msgs`)
+/// the next batch collects messages while the previous one is broadcasting,
+/// but batches are broadcast one at a time, so the sequence stays consistent
+///
+/// There's no background task - the first caller of each batch drives it,
+/// and if that future is dropped, one of the other callers waiting on the batch takes over
+///
+/// If a message fails, only its caller gets the error and the rest of the batch is broadcast again without it
+/// (as long as the node reports which message failed)
+#[derive(Clone)]
+pub struct TxBatcher {
+    pub client: SigningClient,
+    pub config: TxBatcherConfig,
+    queue: BatchQueue,
+}
+
+#[derive(Clone, Debug)]
+pub struct TxBatcherConfig {
+    pub max_msgs: usize,
+    pub window: std::time::Duration,
+}
+
+impl Default for TxBatcherConfig {
+    fn default() -> Self {
+        Self {
+            max_msgs: 20,
+            window: std::time::Duration::from_millis(200),
+        }
+    }
+}
+
+/// The result of a single message within a batched tx
+#[derive(Clone, Debug)]
+pub struct BatchedMsgResponse {
+    /// index of the message within the tx
+    pub msg_index: usize,
+    /// this message's entry in the tx `msg_responses`, if the chain returned it
+    pub msg_response: Option<layer_climb_proto::Any>,
+    /// the events emitted by this message
+    pub events: Vec<cosmwasm_std::Event>,
+    /// the full response of the tx this message was included in
+    pub tx_response: Arc<layer_climb_proto::abci::TxResponse>,
+}
+
+impl TxBatcher {
+    pub fn new(client: SigningClient, config: TxBatcherConfig) -> Self {
+        Self {
+            client,
+            config,
+            queue: BatchQueue::default(),
+        }
+    }
+
+    /// Queues a message for the next tx, resolves once that tx has been broadcast
+    pub async fn submit(&self, msg: layer_climb_proto::Any) -> Result<BatchedMsgResponse> {
+        self.queue
+            .submit(msg, &self.config, |msgs| {
+                self.client.tx_builder().broadcast(msgs)
+            })
+            .await
+    }
+
+    /// Convenience for `submit(proto_into_any(msg)?)`
+    pub async fn submit_msg<T: layer_climb_proto::Name>(
+        &self,
+        msg: &T,
+    ) -> Result<BatchedMsgResponse> {
+        self.submit(proto_into_any(msg)?).await
+    }
+}
+
+// the batching itself, kept apart from the client so it doesn't need a chain to test
+#[derive(Clone, Default)]
+struct BatchQueue {
+    state: Arc<Mutex<BatchState>>,
+    broadcast_lock: Arc<futures::lock::Mutex<()>>,
+}
+
+#[derive(Default)]
+struct BatchState {
+    pending: Vec<PendingMsg>,
+    // the driver of the batch that's collecting messages, if any
+    driver: Option<DriverSlot>,
+    next_driver_id: u64,
+}
+
+struct DriverSlot {
+    id: u64,
+    // gone once the driver closes its window (or its future is dropped)
+    window: Weak<()>,
+    // signals the driver to broadcast before the window is up
+    flush: Option<oneshot::Sender<()>>,
+    // resolves once the driver is done or dropped, so the callers waiting on it can take over
+    done: Shared<oneshot::Receiver<()>>,
+}
+
+impl BatchState {
+    fn current_driver(&self) -> Option<&DriverSlot> {
+        self.driver
+            .as_ref()
+            .filter(|driver| driver.window.strong_count() > 0)
+    }
+
+    fn open_window(&mut self) -> Driver {
+        let (flush_tx, flush_rx) = oneshot::channel();
+        let (done_tx, done_rx) = oneshot::channel();
+        let window = Arc::new(());
+
+        self.next_driver_id += 1;
+        self.driver = Some(DriverSlot {
+            id: self.next_driver_id,
+            window: Arc::downgrade(&window),
+            flush: Some(flush_tx),
+            done: done_rx.shared(),
+        });
+
+        Driver {
+            id: self.next_driver_id,
+            flush_rx,
+            window,
+            done: done_tx,
+        }
+    }
+
+    // takes the batch that's been collecting, if it's still this driver's
+    fn close_window(&mut self, id: u64) -> Vec<PendingMsg> {
+        if self.driver.as_ref().is_some_and(|driver| driver.id == id) {
+            self.driver = None;
+        }
+
+        std::mem::take(&mut self.pending)
+    }
+}
+
+struct PendingMsg {
+    msg: layer_climb_proto::Any,
+    resp: oneshot::Sender<Result<BatchedMsgResponse>>,
+}
+
+// held by the driver, dropping `done` wakes up the callers waiting on the batch
+struct Driver {
+    id: u64,
+    flush_rx: oneshot::Receiver<()>,
+    window: Arc<()>,
+    done: oneshot::Sender<()>,
+}
+
+impl BatchQueue {
+    async fn submit<F, Fut>(
+        &self,
+        msg: layer_climb_proto::Any,
+        config: &TxBatcherConfig,
+        broadcast: F,
+    ) -> Result<BatchedMsgResponse>
+    where
+        F: Fn(Vec<layer_climb_proto::Any>) -> Fut,
+        Fut: Future<Output = Result<layer_climb_proto::abci::TxResponse>>,
+    {
+        let (resp_tx, mut resp_rx) = oneshot::channel();
+
+        self.state
+            .lock()
+            .unwrap()
+            .pending
+            .push(PendingMsg { msg, resp: resp_tx });
+
+        loop {
+            let role = {
+                let mut state = self.state.lock().unwrap();
+
+                // take over if there's no driver collecting this batch (e.g. its future was dropped)
+                let role = match state.current_driver() {
+                    Some(driver) => Err(driver.done.clone()),
+                    None => Ok(state.open_window()),
+                };
+
+                if state.pending.len() >= config.max_msgs {
+                    if let Some(flush) =
+                        state.driver.as_mut().and_then(|driver| driver.flush.take())
+                    {
+                        let _ = flush.send(());
+                    }
+                }
+
+                role
+            };
+
+            match role {
+                Ok(Driver {
+                    id,
+                    flush_rx,
+                    window,
+                    done: _done,
+                }) => {
+                    select(futures_timer::Delay::new(config.window), flush_rx).await;
+
+                    // this batch includes our own message, anything submitted from now on goes in the next one
+                    let batch = self.state.lock().unwrap().close_window(id);
+                    drop(window);
+
+                    self.flush(batch, config, &broadcast).await;
+                    return recv_response(resp_rx.await);
+                }
+                Err(driver_done) => {
+                    match select(resp_rx, driver_done).await {
+                        Either::Left((resp, _)) => return recv_response(resp),
+                        // the driver is gone, check again whether our message still needs one
+                        Either::Right((_, rx)) => resp_rx = rx,
+                    }
+                }
+            }
+        }
+    }
+
+    // broadcasts a closed batch, in chunks of at most `max_msgs`
+    async fn flush<F, Fut>(
+        &self,
+        mut batch: Vec<PendingMsg>,
+        config: &TxBatcherConfig,
+        broadcast: &F,
+    ) where
+        F: Fn(Vec<layer_climb_proto::Any>) -> Fut,
+        Fut: Future<Output = Result<layer_climb_proto::abci::TxResponse>>,
+    {
+        let _guard = self.broadcast_lock.lock().await;
+
+        while !batch.is_empty() {
+            let rest = batch.split_off(batch.len().min(config.max_msgs.max(1)));
+            broadcast_batch(std::mem::replace(&mut batch, rest), broadcast).await;
+        }
+    }
+}
+
+fn recv_response(
+    resp: std::result::Result<Result<BatchedMsgResponse>, oneshot::Canceled>,
+) -> Result<BatchedMsgResponse> {
+    match resp {
+        Ok(resp) => resp,
+        Err(_) => Err(anyhow!(
+            "the batch was dropped while broadcasting, the message may or may not be on chain"
+        )),
+    }
+}
+
+async fn broadcast_batch<F, Fut>(pending: Vec<PendingMsg>, broadcast: &F)
+where
+    F: Fn(Vec<layer_climb_proto::Any>) -> Fut,
+    Fut: Future<Output = Result<layer_climb_proto::abci::TxResponse>>,
+{
+    let (mut msgs, mut senders): (Vec<_>, Vec<_>) = pending
+        .into_iter()
+        .map(|pending| (pending.msg, pending.resp))
+        .unzip();
+
+    loop {
+        tracing::debug!("broadcasting batch of {} messages", msgs.len());
+
+        match broadcast(msgs.clone()).await {
+            Ok(tx_response) => {
+                let tx_response = Arc::new(tx_response);
+                let msg_responses = match msg_responses(&tx_response) {
+                    Ok(msg_responses) => msg_responses,
+                    Err(err) => {
+                        tracing::warn!("unable to decode msg_responses: {err:?}");
+                        Vec::new()
+                    }
+                };
+
+                for (msg_index, sender) in senders.into_iter().enumerate() {
+                    let _ = sender.send(Ok(BatchedMsgResponse {
+                        msg_index,
+                        msg_response: msg_responses.get(msg_index).cloned(),
+                        events: msg_events(&tx_response, msg_index),
+                        tx_response: tx_response.clone(),
+                    }));
+                }
+
+                return;
+            }
+            Err(err) => {
+                // fail just the message the node blamed, and try again with the others
+                match parse_msg_index(&format!("{err:#}")) {
+                    Some(msg_index) if msg_index < msgs.len() && msgs.len() > 1 => {
+                        tracing::debug!("message {msg_index} failed, rebroadcasting the rest");
+                        msgs.remove(msg_index);
+                        let _ = senders.remove(msg_index).send(Err(err));
+                    }
+                    _ => {
+                        // keep the typed error where there is one so callers can still downcast
+                        let climb_error = ClimbError::from_anyhow(&err);
+                        for sender in senders {
+                            let err = match climb_error.clone() {
+                                Some(climb_error) => anyhow::Error::from(climb_error),
+                                None => anyhow!("{err:?}"),
+                            };
+                            let _ = sender.send(Err(err));
+                        }
+
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+// the response data is the hex-encoded TxMsgData
+fn msg_responses(
+    tx_response: &layer_climb_proto::abci::TxResponse,
+) -> Result<Vec<layer_climb_proto::Any>> {
+    if tx_response.data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let bytes = const_hex::decode(&tx_response.data)?;
+    let tx_msg_data = layer_climb_proto::abci::TxMsgData::decode(bytes.as_slice())?;
+
+    Ok(tx_msg_data.msg_responses)
+}
+
+// newer chains tag each event with a msg_index attribute, older ones split the logs per message
+fn msg_events(
+    tx_response: &layer_climb_proto::abci::TxResponse,
+    msg_index: usize,
+) -> Vec<cosmwasm_std::Event> {
+    if !tx_response.logs.is_empty() {
+        return tx_response
+            .logs
+            .iter()
+            .find(|log| log.msg_index as usize == msg_index)
+            .map(|log| {
+                log.events
+                    .iter()
+                    .map(|event| Event::String(event).into())
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+
+    let msg_index = msg_index.to_string();
+
+    tx_response
+        .events
+        .iter()
+        .map(Event::Tendermint)
+        .filter(|event| {
+            event
+                .attributes()
+                .any(|attr| attr.key() == "msg_index" && attr.value() == msg_index)
+        })
+        .map(Into::into)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use futures::{executor::block_on, future, poll};
+
+    use super::*;
+
+    fn msg(type_url: &str) -> layer_climb_proto::Any {
+        layer_climb_proto::Any {
+            type_url: type_url.to_string(),
+            value: Vec::new(),
+        }
+    }
+
+    // records every batch, and fails with the index of the first "/bad" message like the sdk does
+    fn fake_broadcast(
+        batches: &Mutex<Vec<Vec<String>>>,
+    ) -> impl Fn(
+        Vec<layer_climb_proto::Any>,
+    ) -> future::Ready<Result<layer_climb_proto::abci::TxResponse>>
+           + '_ {
+        move |msgs| {
+            let type_urls: Vec<_> = msgs.into_iter().map(|msg| msg.type_url).collect();
+            let bad = type_urls.iter().position(|type_url| type_url == "/bad");
+            batches.lock().unwrap().push(type_urls);
+
+            future::ready(match bad {
+                Some(index) => Err(anyhow!(
+                    "failed to execute message; message index: {index}: bad message"
+                )),
+                None => Ok(Default::default()),
+            })
+        }
+    }
+
+    #[test]
+    fn waiter_takes_over_dropped_driver() {
+        let queue = BatchQueue::default();
+        let config = TxBatcherConfig {
+            max_msgs: 10,
+            window: Duration::from_millis(20),
+        };
+        let batches = Mutex::new(Vec::new());
+
+        block_on(async {
+            let mut driver = Box::pin(queue.submit(msg("/a"), &config, fake_broadcast(&batches)));
+            let mut waiter = Box::pin(queue.submit(msg("/b"), &config, fake_broadcast(&batches)));
+
+            assert!(poll!(driver.as_mut()).is_pending());
+            assert!(poll!(waiter.as_mut()).is_pending());
+            drop(driver);
+
+            let resp = waiter.await.unwrap();
+            assert_eq!(resp.msg_index, 1);
+        });
+
+        // the dropped caller's message was still sent, it had already been queued
+        assert_eq!(*batches.lock().unwrap(), vec![vec!["/a", "/b"]]);
+    }
+
+    #[test]
+    fn batch_collected_while_broadcasting_keeps_its_window() {
+        let queue = BatchQueue::default();
+        let short = TxBatcherConfig {
+            max_msgs: 10,
+            window: Duration::from_millis(10),
+        };
+        let long = TxBatcherConfig {
+            max_msgs: 10,
+            window: Duration::from_millis(200),
+        };
+        let batches = Mutex::new(Vec::new());
+
+        let broadcast = |msgs: Vec<layer_climb_proto::Any>| {
+            let type_urls: Vec<_> = msgs.into_iter().map(|msg| msg.type_url).collect();
+            batches.lock().unwrap().push(type_urls);
+
+            async {
+                futures_timer::Delay::new(Duration::from_millis(50)).await;
+                Ok(Default::default())
+            }
+        };
+
+        block_on(async {
+            let a = async {
+                let resp = queue.submit(msg("/a"), &short, broadcast).await;
+                // "/b" arrived while "/a" was broadcasting, it waits for its own window
+                assert_eq!(batches.lock().unwrap().len(), 1);
+                resp
+            };
+            let b = async {
+                futures_timer::Delay::new(Duration::from_millis(30)).await;
+                queue.submit(msg("/b"), &long, broadcast).await
+            };
+
+            let (a, b) = futures::future::join(a, b).await;
+            assert!(a.is_ok());
+            assert!(b.is_ok());
+        });
+
+        assert_eq!(*batches.lock().unwrap(), vec![vec!["/a"], vec!["/b"]]);
+    }
+
+    #[test]
+    fn failed_msg_only_fails_its_caller() {
+        let queue = BatchQueue::default();
+        let config = TxBatcherConfig {
+            max_msgs: 3,
+            window: Duration::from_secs(60),
+        };
+        let batches = Mutex::new(Vec::new());
+
+        let (a, bad, b) = block_on(futures::future::join3(
+            queue.submit(msg("/a"), &config, fake_broadcast(&batches)),
+            queue.submit(msg("/bad"), &config, fake_broadcast(&batches)),
+            queue.submit(msg("/b"), &config, fake_broadcast(&batches)),
+        ));
+
+        assert_eq!(a.unwrap().msg_index, 0);
+        assert_eq!(b.unwrap().msg_index, 1);
+        assert!(bad.unwrap_err().to_string().contains("bad message"));
+
+        assert_eq!(
+            *batches.lock().unwrap(),
+            vec![vec!["/a", "/bad", "/b"], vec!["/a", "/b"]]
+        );
+    }
+
+    #[test]
+    fn unattributed_error_fails_every_caller() {
+        let queue = BatchQueue::default();
+        let config = TxBatcherConfig {
+            max_msgs: 2,
+            window: Duration::from_secs(60),
+        };
+
+        let broadcast = |_| {
+            future::ready(Err::<layer_climb_proto::abci::TxResponse, _>(anyhow!(
+                "out of gas"
+            )))
+        };
+
+        let (a, b) = block_on(futures::future::join(
+            queue.submit(msg("/a"), &config, broadcast),
+            queue.submit(msg("/b"), &config, broadcast),
+        ));
+
+        assert!(a.is_err());
+        assert!(b.is_err());
+    }
+}
@@ -5,7 +5,10 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use crate::network::rpc::{RpcClient, RpcTransport};
+use crate::{
+    network::rpc::{RpcClient, RpcTransport},
+    querier::middleware::cache::QueryResponseCache,
+};
 
 /// This cache is on the QueryClient and can be used
 /// to either pre-populate the cache with resources created on the outside
@@ -24,6 +27,8 @@ pub struct ClimbCache {
     grpc: Arc<Mutex<HashMap<String, tonic::transport::Channel>>>,
     rpc: Arc<Mutex<HashMap<String, RpcClient>>>,
     rpc_transport: Arc<dyn RpcTransport>,
+    /// Storage for `QueryCacheMiddleware`, only used if that middleware is enabled
+    pub query_responses: QueryResponseCache,
 }
 
 impl ClimbCache {
@@ -32,28 +37,40 @@ impl ClimbCache {
             grpc: Arc::new(Mutex::new(HashMap::new())),
             rpc: Arc::new(Mutex::new(HashMap::new())),
             rpc_transport,
+            query_responses: QueryResponseCache::default(),
         }
     }
 }
 
+/// Clients are pooled per endpoint, so every `ChainConfig` (and every failover list) that names
+/// the same endpoint shares one client or channel
 impl ClimbCache {
+    /// The primary rpc client, i.e. the first of `ChainConfig::all_rpc_endpoints()`
     pub fn get_rpc_client(&self, config: &ChainConfig) -> Option<RpcClient> {
-        match config.rpc_endpoint.as_ref() {
-            None => None,
-            Some(endpoint) => {
-                let rpc = { self.rpc.lock().unwrap().get(endpoint).cloned() };
+        self.get_rpc_clients(config).into_iter().next()
+    }
 
-                Some(match rpc {
-                    Some(rpc) => rpc,
-                    None => {
-                        let rpc = RpcClient::new(endpoint.to_string(), self.rpc_transport.clone());
-                        self.rpc
-                            .lock()
-                            .unwrap()
-                            .insert(endpoint.to_string(), rpc.clone());
-                        rpc
-                    }
-                })
+    /// One client per rpc endpoint, in priority order
+    pub fn get_rpc_clients(&self, config: &ChainConfig) -> Vec<RpcClient> {
+        config
+            .all_rpc_endpoints()
+            .into_iter()
+            .map(|endpoint| self.get_rpc_client_for_endpoint(endpoint))
+            .collect()
+    }
+
+    pub fn get_rpc_client_for_endpoint(&self, endpoint: &str) -> RpcClient {
+        let rpc = { self.rpc.lock().unwrap().get(endpoint).cloned() };
+
+        match rpc {
+            Some(rpc) => rpc,
+            None => {
+                let rpc = RpcClient::new(endpoint.to_string(), self.rpc_transport.clone());
+                self.rpc
+                    .lock()
+                    .unwrap()
+                    .insert(endpoint.to_string(), rpc.clone());
+                rpc
             }
         }
     }
@@ -61,28 +78,38 @@ impl ClimbCache {
 cfg_if::cfg_if! {
     if #[cfg(all(target_arch = "wasm32", target_os = "unknown"))] {
         impl ClimbCache {
+            /// The primary grpc-web client, i.e. the first of `ChainConfig::all_grpc_web_endpoints()`
             pub async fn get_web_grpc(&self, chain_config: &ChainConfig) -> Result<Option<tonic_web_wasm_client::Client>> {
-                let endpoint = match chain_config.grpc_web_endpoint.as_ref() {
-                    Some(endpoint) => endpoint.to_string(),
-                    None => match chain_config.grpc_endpoint.as_ref() {
-                        Some(endpoint) => endpoint.to_string(),
-                        None => return Ok(None),
-                    }
-                };
+                match chain_config.all_grpc_web_endpoints().first() {
+                    Some(endpoint) => Ok(Some(self.get_web_grpc_for_endpoint(endpoint).await?)),
+                    None => Ok(None),
+                }
+            }
 
+            /// One client per grpc-web endpoint, in priority order, paired with the endpoint
+            pub async fn get_web_grpcs(&self, chain_config: &ChainConfig) -> Result<Vec<(String, tonic_web_wasm_client::Client)>> {
+                let mut clients = Vec::new();
 
+                for endpoint in chain_config.all_grpc_web_endpoints() {
+                    clients.push((endpoint.to_string(), self.get_web_grpc_for_endpoint(endpoint).await?));
+                }
+
+                Ok(clients)
+            }
+
+            pub async fn get_web_grpc_for_endpoint(&self, endpoint: &str) -> Result<tonic_web_wasm_client::Client> {
                 let grpc = {
-                    self.grpc.lock().unwrap().get(&endpoint).cloned()
+                    self.grpc.lock().unwrap().get(endpoint).cloned()
                 };
 
-                Ok(Some(match grpc {
+                Ok(match grpc {
                     Some(grpc) => grpc,
                     None => {
-                        let grpc = crate::network::grpc_web::make_grpc_client(endpoint.clone()).await?;
-                        self.grpc.lock().unwrap().insert(endpoint, grpc.clone());
+                        let grpc = crate::network::grpc_web::make_grpc_client(endpoint.to_string()).await?;
+                        self.grpc.lock().unwrap().insert(endpoint.to_string(), grpc.clone());
                         grpc
                     }
-                }))
+                })
             }
         }
     } else if #[cfg(target_arch = "wasm32")] {
@@ -93,26 +120,61 @@ cfg_if::cfg_if! {
         }
     } else {
         impl ClimbCache {
+            /// The primary grpc channel, i.e. the first of `ChainConfig::all_grpc_endpoints()`
             pub async fn get_grpc(&self, chain_config: &ChainConfig) -> Result<Option<tonic::transport::Channel>> {
-                match chain_config.grpc_endpoint.as_ref() {
+                match chain_config.all_grpc_endpoints().first() {
+                    Some(endpoint) => Ok(Some(self.get_grpc_for_endpoint(endpoint).await?)),
                     None => Ok(None),
-                    Some(endpoint) => {
-                        let grpc = {
-                            self.grpc.lock().unwrap().get(endpoint).cloned()
-                        };
-
-                        Ok(Some(match grpc {
-                            Some(grpc) => grpc,
-                            None => {
-                                tracing::debug!("Creating new grpc channel for {}", endpoint);
-                                let grpc = crate::network::grpc_native::make_grpc_channel(endpoint).await?;
-                                self.grpc.lock().unwrap().insert(endpoint.to_string(), grpc.clone());
-                                grpc
-                            }
-                        }))
-                    }
                 }
             }
+
+            /// One channel per grpc endpoint, in priority order, paired with the endpoint
+            ///
+            /// Endpoints that can't be reached right now get a lazy channel, so they can still be failed over to later
+            /// only errors if none of them can be reached
+            pub async fn get_grpcs(&self, chain_config: &ChainConfig) -> Result<Vec<(String, tonic::transport::Channel)>> {
+                let mut channels = Vec::new();
+                let mut first_error = None;
+                let mut any_connected = false;
+
+                for endpoint in chain_config.all_grpc_endpoints() {
+                    let channel = match self.get_grpc_for_endpoint(endpoint).await {
+                        Ok(channel) => {
+                            any_connected = true;
+                            channel
+                        }
+                        Err(err) => {
+                            tracing::warn!("Could not connect to grpc endpoint {}: {}", endpoint, err);
+                            let channel = crate::network::grpc_native::make_lazy_grpc_channel(endpoint)?;
+                            first_error.get_or_insert(err);
+                            channel
+                        }
+                    };
+
+                    channels.push((endpoint.to_string(), channel));
+                }
+
+                match first_error {
+                    Some(err) if !any_connected => Err(err),
+                    _ => Ok(channels),
+                }
+            }
+
+            pub async fn get_grpc_for_endpoint(&self, endpoint: &str) -> Result<tonic::transport::Channel> {
+                let grpc = {
+                    self.grpc.lock().unwrap().get(endpoint).cloned()
+                };
+
+                Ok(match grpc {
+                    Some(grpc) => grpc,
+                    None => {
+                        tracing::debug!("Creating new grpc channel for {}", endpoint);
+                        let grpc = crate::network::grpc_native::make_grpc_channel(endpoint).await?;
+                        self.grpc.lock().unwrap().insert(endpoint.to_string(), grpc.clone());
+                        grpc
+                    }
+                })
+            }
         }
     }
 }
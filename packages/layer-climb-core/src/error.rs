@@ -0,0 +1,484 @@
+use crate::querier::tx::AnyTxResponse;
+
+/// Typed errors from broadcasting and polling transactions (or a middleware refusing to)
+/// these are returned inside `anyhow::Error`, use `err.downcast_ref::<ClimbError>()` to match on them
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum ClimbError {
+    #[error(transparent)]
+    Tx(#[from] TxError),
+    #[error("timed out after {elapsed:?} waiting for tx {tx_hash}")]
+    PollTimeout {
+        tx_hash: String,
+        elapsed: std::time::Duration,
+    },
+    #[error(transparent)]
+    Rejected(#[from] TxRejection),
+}
+
+impl ClimbError {
+    /// Finds a `ClimbError` (or a bare `TxError` / `TxRejection`) anywhere in an `anyhow::Error` chain
+    pub fn from_anyhow(err: &anyhow::Error) -> Option<Self> {
+        err.chain().find_map(|err| {
+            err.downcast_ref::<ClimbError>()
+                .cloned()
+                .or_else(|| err.downcast_ref::<TxError>().cloned().map(ClimbError::Tx))
+                .or_else(|| {
+                    err.downcast_ref::<TxRejection>()
+                        .cloned()
+                        .map(ClimbError::Rejected)
+                })
+        })
+    }
+
+    pub fn tx_error(&self) -> Option<&TxError> {
+        match self {
+            Self::Tx(err) => Some(err),
+            Self::PollTimeout { .. } | Self::Rejected(_) => None,
+        }
+    }
+}
+
+/// A signing middleware refused to let the tx through, nothing was broadcast
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum TxRejection {
+    #[error("message type {type_url} is not allowed")]
+    MsgNotAllowed { type_url: String },
+    #[error("fee of {amount}{denom} is above the limit of {limit}{denom}")]
+    FeeTooHigh {
+        denom: String,
+        amount: u128,
+        limit: u128,
+    },
+    #[error("fee denom {denom} is not allowed")]
+    FeeDenomNotAllowed { denom: String },
+    #[error("rejected: {0}")]
+    Custom(String),
+}
+
+/// Where in the lifecycle of a tx it failed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TxStage {
+    /// rejected by the node when broadcasting, the tx never made it into a block
+    CheckTx,
+    /// included in a block but failed during execution, fees and sequence are still used up
+    DeliverTx,
+    /// failed in simulation, nothing was broadcast
+    Simulation,
+}
+
+/// A tx that came back with a non-zero code
+#[derive(thiserror::Error, Debug, Clone)]
+#[error("{} failed with code: {code}, codespace: {codespace}, raw_log: {raw_log}", tx_label(.stage, .tx_hash))]
+pub struct TxError {
+    pub stage: TxStage,
+    /// empty for a simulation
+    pub tx_hash: String,
+    pub codespace: String,
+    pub code: u32,
+    pub raw_log: String,
+    /// not available from an rpc CheckTx response
+    pub gas_wanted: Option<i64>,
+    pub gas_used: Option<i64>,
+}
+
+/// The well-known failure reasons, classified from the codespace and code
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TxErrorKind {
+    OutOfGas,
+    InsufficientFee,
+    InsufficientFunds,
+    Unauthorized,
+    /// `expected` is parsed from the raw log, if it's there
+    SequenceMismatch {
+        expected: Option<u64>,
+    },
+    TxInMempoolCache,
+    TxTimeout,
+    /// the contract itself returned an error, `message` is what it reported
+    ContractError {
+        message: String,
+    },
+    /// any other wasm module error
+    Wasm,
+    /// any error from an ibc-go module
+    Ibc,
+    Other,
+}
+
+// https://github.com/cosmos/cosmos-sdk/blob/main/types/errors/errors.go
+const SDK_CODESPACE: &str = "sdk";
+const SDK_UNAUTHORIZED: u32 = 4;
+const SDK_INSUFFICIENT_FUNDS: u32 = 5;
+const SDK_UNKNOWN_REQUEST: u32 = 6;
+const SDK_OUT_OF_GAS: u32 = 11;
+const SDK_INSUFFICIENT_FEE: u32 = 13;
+const SDK_TX_IN_MEMPOOL_CACHE: u32 = 19;
+const SDK_TX_TIMEOUT_HEIGHT: u32 = 30;
+const SDK_WRONG_SEQUENCE: u32 = 32;
+const SDK_TX_TIMEOUT: u32 = 42;
+
+// https://github.com/CosmWasm/wasmd/blob/main/x/wasm/types/errors.go
+const WASM_CODESPACE: &str = "wasm";
+const WASM_INSTANTIATE_FAILED: u32 = 4;
+const WASM_EXECUTE_FAILED: u32 = 5;
+const WASM_MIGRATION_FAILED: u32 = 11;
+
+// what the errors above are registered with, a simulation error only carries the message
+const ERROR_DESCRIPTIONS: &[(&str, u32, &str)] = &[
+    (SDK_CODESPACE, SDK_UNAUTHORIZED, "unauthorized"),
+    (SDK_CODESPACE, SDK_INSUFFICIENT_FUNDS, "insufficient funds"),
+    (SDK_CODESPACE, SDK_OUT_OF_GAS, "out of gas"),
+    (SDK_CODESPACE, SDK_INSUFFICIENT_FEE, "insufficient fee"),
+    (
+        SDK_CODESPACE,
+        SDK_WRONG_SEQUENCE,
+        "incorrect account sequence",
+    ),
+    (
+        WASM_CODESPACE,
+        WASM_INSTANTIATE_FAILED,
+        "instantiate wasm contract failed",
+    ),
+    (
+        WASM_CODESPACE,
+        WASM_EXECUTE_FAILED,
+        "execute wasm contract failed",
+    ),
+    (
+        WASM_CODESPACE,
+        WASM_MIGRATION_FAILED,
+        "migrate wasm contract failed",
+    ),
+];
+
+// codespaces registered by ibc-go core and apps
+const IBC_CODESPACES: &[&str] = &[
+    "client",
+    "connection",
+    "channel",
+    "port",
+    "commitment",
+    "host",
+    "ibc",
+    "transfer",
+    "interchainaccounts",
+    "feeibc",
+];
+
+impl TxError {
+    pub fn new(stage: TxStage, tx_response: &AnyTxResponse) -> Self {
+        let (gas_wanted, gas_used) = match tx_response {
+            AnyTxResponse::Abci(res) => (Some(res.gas_wanted), Some(res.gas_used)),
+            AnyTxResponse::Rpc(_) => (None, None),
+        };
+
+        Self {
+            stage,
+            tx_hash: tx_response.tx_hash(),
+            codespace: tx_response.codespace().to_string(),
+            code: tx_response.code(),
+            raw_log: tx_response.raw_log().to_string(),
+            gas_wanted,
+            gas_used,
+        }
+    }
+
+    /// From a failed simulation, which only reports the error message (over abci, always as `unknown request`)
+    /// so the codespace and code of the errors `kind()` knows about are recovered from their description
+    /// e.g. "account sequence mismatch, expected 5, got 4: incorrect account sequence With gas wanted: '0' and gas used: '0' "
+    pub fn simulation(message: &str, reported: Option<(&str, u32)>) -> Self {
+        let message = message
+            .trim_start_matches("rpc error: code = Unknown desc = ")
+            .trim();
+
+        let (raw_log, gas) = match message.split_once(" With gas wanted: ") {
+            Some((raw_log, gas)) => (raw_log.trim_end(), Some(gas)),
+            None => (message, None),
+        };
+
+        // wasmd adds where the error was created, e.g. "... [CosmWasm/wasmd@v0.53.0/x/wasm/keeper/keeper.go:411]"
+        let raw_log = match raw_log.rsplit_once(" [") {
+            Some((stripped, location)) if location.ends_with(']') => stripped,
+            _ => raw_log,
+        };
+
+        let (codespace, code) = ERROR_DESCRIPTIONS
+            .iter()
+            .filter_map(|(codespace, code, description)| {
+                let position = raw_log.rfind(&format!(": {description}"))?;
+                Some((position, *codespace, *code))
+            })
+            .max_by_key(|(position, ..)| *position)
+            .map(|(_, codespace, code)| (codespace, code))
+            .or(reported)
+            .unwrap_or((SDK_CODESPACE, SDK_UNKNOWN_REQUEST));
+
+        // e.g. "'0' and gas used: '54219' : unknown request"
+        let mut gas_values = gas
+            .unwrap_or_default()
+            .split('\'')
+            .skip(1)
+            .step_by(2)
+            .map(|value| value.parse::<i64>().ok());
+        let gas_wanted = gas_values.next().flatten();
+        let gas_used = gas_values.next().flatten();
+
+        Self {
+            stage: TxStage::Simulation,
+            tx_hash: String::new(),
+            codespace: codespace.to_string(),
+            code,
+            raw_log: raw_log.to_string(),
+            gas_wanted,
+            gas_used,
+        }
+    }
+
+    pub fn kind(&self) -> TxErrorKind {
+        match (self.codespace.as_str(), self.code) {
+            (SDK_CODESPACE, SDK_OUT_OF_GAS) => TxErrorKind::OutOfGas,
+            (SDK_CODESPACE, SDK_INSUFFICIENT_FEE) => TxErrorKind::InsufficientFee,
+            (SDK_CODESPACE, SDK_INSUFFICIENT_FUNDS) => TxErrorKind::InsufficientFunds,
+            (SDK_CODESPACE, SDK_UNAUTHORIZED) => TxErrorKind::Unauthorized,
+            (SDK_CODESPACE, SDK_WRONG_SEQUENCE) => TxErrorKind::SequenceMismatch {
+                expected: parse_sequence_mismatch(&self.raw_log),
+            },
+            (SDK_CODESPACE, SDK_TX_IN_MEMPOOL_CACHE) => TxErrorKind::TxInMempoolCache,
+            (SDK_CODESPACE, SDK_TX_TIMEOUT_HEIGHT | SDK_TX_TIMEOUT) => TxErrorKind::TxTimeout,
+            (
+                WASM_CODESPACE,
+                WASM_INSTANTIATE_FAILED | WASM_EXECUTE_FAILED | WASM_MIGRATION_FAILED,
+            ) => TxErrorKind::ContractError {
+                message: parse_contract_error(&self.raw_log),
+            },
+            (WASM_CODESPACE, _) => TxErrorKind::Wasm,
+            (codespace, _) if IBC_CODESPACES.contains(&codespace) => TxErrorKind::Ibc,
+            _ => TxErrorKind::Other,
+        }
+    }
+}
+
+fn tx_label(stage: &TxStage, tx_hash: &str) -> String {
+    match stage {
+        TxStage::Simulation => "simulated tx".to_string(),
+        TxStage::CheckTx | TxStage::DeliverTx => format!("tx {tx_hash}"),
+    }
+}
+
+// e.g. "account sequence mismatch, expected 5, got 4: incorrect account sequence" (sdk error code 32)
+pub(crate) fn parse_sequence_mismatch(error: &str) -> Option<u64> {
+    const PREFIX: &str = "account sequence mismatch, expected ";
+
+    let start = error.find(PREFIX)? + PREFIX.len();
+    let digits: String = error[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+
+    digits.parse().ok()
+}
+
+// which message of a multi-message tx failed, from a simulation or CheckTx/DeliverTx error
+// e.g. "failed to execute message; message index: 2: ..."
+pub(crate) fn parse_msg_index(error: &str) -> Option<usize> {
+    const PREFIX: &str = "message index: ";
+
+    let start = error.find(PREFIX)? + PREFIX.len();
+    let digits: String = error[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+
+    digits.parse().ok()
+}
+
+// e.g. "failed to execute message; message index: 0: Unauthorized: execute wasm contract failed"
+// the wrapping differs a bit between wasmd versions, so strip what we recognize and keep the rest
+fn parse_contract_error(raw_log: &str) -> String {
+    let mut message = raw_log;
+
+    if let Some(index) = message.find("message index: ") {
+        let rest = &message[index + "message index: ".len()..];
+        message = rest
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .trim_start_matches(':')
+            .trim_start();
+    }
+
+    for suffix in [
+        ": execute wasm contract failed",
+        ": instantiate wasm contract failed",
+        ": migrate wasm contract failed",
+    ] {
+        if let Some(stripped) = message.strip_suffix(suffix) {
+            message = stripped;
+            break;
+        }
+    }
+
+    message.to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sequence_mismatch() {
+        assert_eq!(
+            parse_sequence_mismatch(
+                "account sequence mismatch, expected 5, got 4: incorrect account sequence"
+            ),
+            Some(5)
+        );
+        assert_eq!(
+            parse_sequence_mismatch("rpc error: code = Unknown desc = account sequence mismatch, expected 1234, got 1235: incorrect account sequence [cosmos/cosmos-sdk@v0.50.9/x/auth/ante/sigverify.go:290] with gas used: '54219': unknown request"),
+            Some(1234)
+        );
+        assert_eq!(parse_sequence_mismatch("insufficient fees"), None);
+        assert_eq!(
+            parse_sequence_mismatch("account sequence mismatch, expected , got 4"),
+            None
+        );
+    }
+
+    #[test]
+    fn msg_index() {
+        assert_eq!(
+            parse_msg_index("failed to execute message; message index: 2: insufficient funds"),
+            Some(2)
+        );
+        assert_eq!(
+            parse_msg_index("rpc error: code = Unknown desc = failed to execute message; message index: 13: Unauthorized: execute wasm contract failed [CosmWasm/wasmd@v0.53.0/x/wasm/keeper/keeper.go:411] With gas wanted: '0' and gas used: '1' : unknown request"),
+            Some(13)
+        );
+        assert_eq!(parse_msg_index("out of gas in location: WriteFlat"), None);
+        assert_eq!(parse_msg_index("message index: "), None);
+    }
+
+    fn tx_error(codespace: &str, code: u32, raw_log: &str) -> TxError {
+        TxError {
+            stage: TxStage::DeliverTx,
+            tx_hash: "ABCD".to_string(),
+            codespace: codespace.to_string(),
+            code,
+            raw_log: raw_log.to_string(),
+            gas_wanted: None,
+            gas_used: None,
+        }
+    }
+
+    #[test]
+    fn contract_error() {
+        assert_eq!(
+            parse_contract_error(
+                "failed to execute message; message index: 0: Unauthorized: execute wasm contract failed"
+            ),
+            "Unauthorized"
+        );
+        assert_eq!(
+            parse_contract_error(
+                "failed to execute message; message index: 12: Generic error: not enough funds: instantiate wasm contract failed"
+            ),
+            "Generic error: not enough funds"
+        );
+        assert_eq!(
+            parse_contract_error("Invalid migration: migrate wasm contract failed"),
+            "Invalid migration"
+        );
+        assert_eq!(parse_contract_error("something else"), "something else");
+    }
+
+    #[test]
+    fn kind() {
+        assert_eq!(
+            tx_error("sdk", 11, "out of gas").kind(),
+            TxErrorKind::OutOfGas
+        );
+        assert_eq!(
+            tx_error(
+                "sdk",
+                32,
+                "account sequence mismatch, expected 7, got 6: incorrect account sequence"
+            )
+            .kind(),
+            TxErrorKind::SequenceMismatch { expected: Some(7) }
+        );
+        assert_eq!(
+            tx_error(
+                "wasm",
+                5,
+                "failed to execute message; message index: 0: Unauthorized: execute wasm contract failed"
+            )
+            .kind(),
+            TxErrorKind::ContractError {
+                message: "Unauthorized".to_string()
+            }
+        );
+        assert_eq!(tx_error("wasm", 2, "").kind(), TxErrorKind::Wasm);
+        assert_eq!(tx_error("channel", 7, "").kind(), TxErrorKind::Ibc);
+        assert_eq!(tx_error("bank", 5, "").kind(), TxErrorKind::Other);
+    }
+
+    #[test]
+    fn simulation() {
+        // over grpc
+        let err = TxError::simulation(
+            "account sequence mismatch, expected 5, got 4: incorrect account sequence With gas wanted: '18446744073709551615' and gas used: '54219' ",
+            None,
+        );
+        assert_eq!(
+            err.kind(),
+            TxErrorKind::SequenceMismatch { expected: Some(5) }
+        );
+        assert_eq!(err.stage, TxStage::Simulation);
+        // the default simulation gas limit doesn't fit
+        assert_eq!((err.gas_wanted, err.gas_used), (None, Some(54219)));
+        assert!(err.to_string().starts_with("simulated tx failed"));
+
+        // over abci, the contract's own "unauthorized" comes before the root cause
+        let err = TxError::simulation(
+            "failed to execute message; message index: 0: unauthorized: execute wasm contract failed [CosmWasm/wasmd@v0.53.0/x/wasm/keeper/keeper.go:411] With gas wanted: '0' and gas used: '1' : unknown request",
+            Some(("sdk", 6)),
+        );
+        assert_eq!((err.codespace.as_str(), err.code), ("wasm", 5));
+        assert_eq!(
+            err.kind(),
+            TxErrorKind::ContractError {
+                message: "unauthorized".to_string()
+            }
+        );
+        assert_eq!((err.gas_wanted, err.gas_used), (Some(0), Some(1)));
+
+        let err = TxError::simulation(
+            "rpc error: code = Unknown desc = out of gas in location: ReadFlat; gasWanted: 100, gasUsed: 1120: out of gas With gas wanted: '100' and gas used: '1120' ",
+            None,
+        );
+        assert_eq!(err.kind(), TxErrorKind::OutOfGas);
+
+        // an error it doesn't know keeps what the node reported
+        let err = TxError::simulation("invalid coins: invalid request", Some(("sdk", 6)));
+        assert_eq!((err.codespace.as_str(), err.code), ("sdk", 6));
+        assert_eq!(err.kind(), TxErrorKind::Other);
+    }
+
+    #[test]
+    fn from_anyhow() {
+        let err = anyhow::Error::from(ClimbError::from(tx_error("sdk", 13, "insufficient fee")))
+            .context("couldn't broadcast");
+
+        let climb_error = ClimbError::from_anyhow(&err).unwrap();
+        assert_eq!(
+            climb_error.tx_error().map(TxError::kind),
+            Some(TxErrorKind::InsufficientFee)
+        );
+
+        let err = anyhow::Error::from(TxRejection::Custom("no".to_string()));
+        assert!(matches!(
+            ClimbError::from_anyhow(&err),
+            Some(ClimbError::Rejected(TxRejection::Custom(_)))
+        ));
+
+        assert!(ClimbError::from_anyhow(&anyhow::anyhow!("timed out")).is_none());
+    }
+}
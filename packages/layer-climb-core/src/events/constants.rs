@@ -3,6 +3,7 @@
 // event types
 pub const EVENT_TYPE_CONTRACT_INSTANTIATE: &str = "instantiate";
 pub const EVENT_TYPE_CONTRACT_STORE_CODE: &str = "store_code";
+pub const EVENT_TYPE_GOV_SUBMIT_PROPOSAL: &str = "submit_proposal";
 pub const EVENT_TYPE_IBC_CREATE_CLIENT: &str = "create_client";
 pub const EVENT_TYPE_IBC_CONNECTION_OPEN_INIT: &str = "connection_open_init";
 pub const EVENT_TYPE_IBC_CONNECTION_OPEN_TRY: &str = "connection_open_try";
@@ -18,6 +19,7 @@ pub const EVENT_TYPE_IBC_WRITE_ACK: &str = "write_acknowledgement";
 pub const EVENT_ATTR_STORE_CODE_ID: &str = "code_id";
 pub const EVENT_ATTR_INSTANTIATE_CONTRACT_ADDRESS_V1: &str = "_contract_address";
 pub const EVENT_ATTR_INSTANTIATE_CONTRACT_ADDRESS_V2: &str = "contract_address";
+pub const EVENT_ATTR_GOV_PROPOSAL_ID: &str = "proposal_id";
 pub const EVENT_ATTR_IBC_CONNECTION_ID: &str = "connection_id";
 pub const EVENT_ATTR_IBC_CHANNEL_ID: &str = "channel_id";
 pub const EVENT_ATTR_IBC_COUNTERPARTY_CHANNEL_ID: &str = "counterparty_channel_id";
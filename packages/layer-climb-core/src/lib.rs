@@ -1,5 +1,7 @@
+pub mod batcher;
 pub mod cache;
 pub mod contract_helpers;
+pub mod error;
 pub mod events;
 pub mod ibc_types;
 pub mod network;
@@ -8,5 +10,8 @@ pub mod querier;
 pub mod signing;
 pub mod transaction;
 
+#[cfg(test)]
+mod testing;
+
 #[cfg(not(target_arch = "wasm32"))]
 pub mod pool;
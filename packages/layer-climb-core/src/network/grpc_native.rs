@@ -2,6 +2,18 @@ use crate::prelude::*;
 use tonic::transport::{Channel, ClientTlsConfig};
 
 pub async fn make_grpc_channel(endpoint: &str) -> Result<Channel> {
+    make_grpc_endpoint(endpoint)?
+        .connect()
+        .await
+        .map_err(|err| anyhow!("error connecting on {}: {}", endpoint, err))
+}
+
+/// Doesn't connect until the first request, e.g. for failover endpoints that are down right now
+pub fn make_lazy_grpc_channel(endpoint: &str) -> Result<Channel> {
+    Ok(make_grpc_endpoint(endpoint)?.connect_lazy())
+}
+
+fn make_grpc_endpoint(endpoint: &str) -> Result<tonic::transport::Endpoint> {
     let endpoint_uri = endpoint.parse::<tonic::transport::Uri>()?;
 
     let channel =
@@ -15,9 +27,5 @@ pub async fn make_grpc_channel(endpoint: &str) -> Result<Channel> {
     //     tls_config = tls_config.ca_certificate(ca);
     // }
 
-    channel
-        .tls_config(tls_config)?
-        .connect()
-        .await
-        .map_err(|err| anyhow!("error connecting on {}: {}", endpoint, err))
+    Ok(channel.tls_config(tls_config)?)
 }
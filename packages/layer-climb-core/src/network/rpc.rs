@@ -93,6 +93,10 @@ impl RpcClient {
         Self { url, http_client }
     }
 
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
     pub async fn commit(&self, height: u64) -> Result<tendermint_rpc::endpoint::commit::Response> {
         let height = tendermint::block::Height::try_from(height)?;
         self.send(tendermint_rpc::endpoint::commit::Request::new(height))
@@ -145,6 +149,19 @@ impl RpcClient {
         .await
     }
 
+    pub async fn tx_search(
+        &self,
+        query: impl ToString,
+        page: u32,
+        per_page: u8,
+        order_by: tendermint_rpc::Order,
+    ) -> Result<tendermint_rpc::endpoint::tx_search::Response> {
+        self.send(tendermint_rpc::endpoint::tx_search::Request::new(
+            query, false, page, per_page, order_by,
+        ))
+        .await
+    }
+
     pub async fn health(&self) -> Result<tendermint_rpc::endpoint::health::Response> {
         self.send(tendermint_rpc::endpoint::health::Request).await
     }
@@ -174,7 +191,12 @@ impl RpcClient {
             .response;
 
         if resp.code.is_err() {
-            bail!("abci query failed: {}", resp.log);
+            return Err(AbciQueryError {
+                codespace: resp.codespace,
+                code: resp.code.value(),
+                log: resp.log,
+            }
+            .into());
         }
 
         Ok(resp)
@@ -207,6 +229,15 @@ impl RpcClient {
     }
 }
 
+/// An abci query that came back with a non-zero code
+#[derive(thiserror::Error, Debug, Clone)]
+#[error("abci query failed: {log}")]
+pub struct AbciQueryError {
+    pub codespace: String,
+    pub code: u32,
+    pub log: String,
+}
+
 /// Response from any kind of transaction broadcast request.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct TxResponse {
@@ -9,12 +9,22 @@ pub(crate) use layer_climb_proto::{proto_into_any, proto_into_bytes, Message};
 
 // common types
 pub use crate::{
+    batcher::{BatchedMsgResponse, TxBatcher, TxBatcherConfig},
     cache::ClimbCache,
     contract_helpers::contract_str_to_msg,
+    error::{ClimbError, TxError, TxErrorKind, TxRejection, TxStage},
     events::CosmosTxEvents,
-    querier::{Connection, ConnectionMode, QueryClient, QueryRequest},
+    querier::{
+        gov::GovVersion,
+        pagination::{PageCursor, PaginationOptions},
+        tx_search::TxQuery,
+        Connection, ConnectionMode, QueryClient, QueryRequest,
+    },
     signing::SigningClient,
-    transaction::TxBuilder,
+    transaction::{
+        offline::{SignedTx, UnsignedTx},
+        SimulationResult, TxBuilder,
+    },
 };
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -1,11 +1,23 @@
 pub mod abci;
+pub mod bank;
 pub mod basic;
+pub mod block_txs;
 pub mod contract;
+#[cfg(feature = "cw-storage")]
+pub mod contract_storage;
+pub mod distribution;
+pub mod endpoints;
+pub mod feegrant;
 pub mod fetch;
+pub mod gas_price;
+pub mod gov;
 pub mod ibc;
 pub mod middleware;
+pub mod pagination;
+pub mod staking;
 pub mod stream;
 pub mod tx;
+pub mod tx_search;
 pub mod validator;
 
 use std::{
@@ -18,7 +30,11 @@ use std::{
 };
 
 use basic::BlockHeightReq;
-use middleware::{QueryMiddlewareMapReq, QueryMiddlewareMapResp, QueryMiddlewareRun};
+use endpoints::ActiveEndpoints;
+use gas_price::GasPriceOracle;
+use middleware::{
+    cache::QueryCachePolicy, QueryMiddlewareMapReq, QueryMiddlewareMapResp, QueryMiddlewareRun,
+};
 use tracing::instrument;
 
 use crate::{
@@ -33,68 +49,99 @@ cfg_if::cfg_if! {
         pub struct QueryClient {
             pub chain_config: ChainConfig,
             pub cache: ClimbCache,
-            pub middleware_map_req: Arc<Vec<QueryMiddlewareMapReq>>,
-            pub middleware_map_resp: Arc<Vec<QueryMiddlewareMapResp>>,
-            pub middleware_run: Arc<Vec<QueryMiddlewareRun>>,
+            pub middleware_map_req: Arc<Vec<Arc<dyn QueryMiddlewareMapReq>>>,
+            pub middleware_map_resp: Arc<Vec<Arc<dyn QueryMiddlewareMapResp>>>,
+            pub middleware_run: Arc<Vec<Arc<dyn QueryMiddlewareRun>>>,
             pub balances_pagination_limit: u64,
+            /// used by TxBuilder to get the gas price, default is `GasPriceSource::Static`
+            pub gas_price_oracle: GasPriceOracle,
             pub wait_blocks_poll_sleep_duration: Duration,
             pub connection: Connection,
-            _grpc_channel: Option<tonic_web_wasm_client::Client>,
-            _rpc_client: Option<RpcClient>,
+            _height: Option<u64>,
+            _grpc_channels: Arc<Vec<(String, tonic_web_wasm_client::Client)>>,
+            _rpc_clients: Arc<Vec<RpcClient>>,
+            _active_endpoints: ActiveEndpoints,
             _connection_mode: Arc<AtomicU8>,
+            _gov_version: Arc<AtomicU8>,
         }
 
         impl QueryClient {
+            /// The channel for the active grpc endpoint
             pub fn clone_grpc_channel(&self) -> Result<tonic_web_wasm_client::Client> {
-                match self._grpc_channel.clone() {
-                    Some(channel) => Ok(channel),
+                match self._grpc_channels.get(self._active_endpoints.grpc()) {
+                    Some((_, channel)) => Ok(channel.clone()),
                     None => Err(anyhow!("grpc_channel isn't set")),
                 }
             }
+
+            /// All the grpc endpoints, in priority order
+            pub fn grpc_endpoints(&self) -> Vec<&str> {
+                self._grpc_channels.iter().map(|(endpoint, _)| endpoint.as_str()).collect()
+            }
         }
     } else if #[cfg(target_arch = "wasm32")] {
         #[derive(Clone)]
         pub struct QueryClient {
             pub chain_config: ChainConfig,
             pub cache: ClimbCache,
-            pub middleware_map_req: Arc<Vec<QueryMiddlewareMapReq>>,
-            pub middleware_map_resp: Arc<Vec<QueryMiddlewareMapResp>>,
-            pub middleware_run: Arc<Vec<QueryMiddlewareRun>>,
+            pub middleware_map_req: Arc<Vec<Arc<dyn QueryMiddlewareMapReq>>>,
+            pub middleware_map_resp: Arc<Vec<Arc<dyn QueryMiddlewareMapResp>>>,
+            pub middleware_run: Arc<Vec<Arc<dyn QueryMiddlewareRun>>>,
             pub balances_pagination_limit: u64,
+            /// used by TxBuilder to get the gas price, default is `GasPriceSource::Static`
+            pub gas_price_oracle: GasPriceOracle,
             pub wait_blocks_poll_sleep_duration: Duration,
             pub connection: Connection,
-            _rpc_client: Option<RpcClient>,
+            _height: Option<u64>,
+            _rpc_clients: Arc<Vec<RpcClient>>,
+            _active_endpoints: ActiveEndpoints,
             _connection_mode: Arc<AtomicU8>,
+            _gov_version: Arc<AtomicU8>,
         }
 
         impl QueryClient {
             pub fn clone_grpc_channel(&self) -> Result<crate::network::grpc_wasi::Client> {
                 Err(anyhow!("todo!"))
             }
+
+            pub fn grpc_endpoints(&self) -> Vec<&str> {
+                Vec::new()
+            }
         }
     } else {
         #[derive(Clone)]
         pub struct QueryClient {
             pub chain_config: ChainConfig,
             pub cache: ClimbCache,
-            pub middleware_map_req: Arc<Vec<QueryMiddlewareMapReq>>,
-            pub middleware_map_resp: Arc<Vec<QueryMiddlewareMapResp>>,
-            pub middleware_run: Arc<Vec<QueryMiddlewareRun>>,
+            pub middleware_map_req: Arc<Vec<Arc<dyn QueryMiddlewareMapReq>>>,
+            pub middleware_map_resp: Arc<Vec<Arc<dyn QueryMiddlewareMapResp>>>,
+            pub middleware_run: Arc<Vec<Arc<dyn QueryMiddlewareRun>>>,
             pub balances_pagination_limit: u64,
+            /// used by TxBuilder to get the gas price, default is `GasPriceSource::Static`
+            pub gas_price_oracle: GasPriceOracle,
             pub wait_blocks_poll_sleep_duration: Duration,
             pub connection: Connection,
-            _grpc_channel: Option<tonic::transport::Channel>,
-            _rpc_client: Option<RpcClient>,
+            _height: Option<u64>,
+            _grpc_channels: Arc<Vec<(String, tonic::transport::Channel)>>,
+            _rpc_clients: Arc<Vec<RpcClient>>,
+            _active_endpoints: ActiveEndpoints,
             _connection_mode: Arc<AtomicU8>,
+            _gov_version: Arc<AtomicU8>,
         }
 
         impl QueryClient {
+            /// The channel for the active grpc endpoint
             pub fn clone_grpc_channel(&self) -> Result<tonic::transport::Channel> {
-                match self._grpc_channel.clone() {
-                    Some(channel) => Ok(channel),
+                match self._grpc_channels.get(self._active_endpoints.grpc()) {
+                    Some((_, channel)) => Ok(channel.clone()),
                     None => Err(anyhow!("grpc_channel isn't set")),
                 }
             }
+
+            /// All the grpc endpoints, in priority order
+            pub fn grpc_endpoints(&self) -> Vec<&str> {
+                self._grpc_channels.iter().map(|(endpoint, _)| endpoint.as_str()).collect()
+            }
         }
     }
 }
@@ -103,14 +150,36 @@ impl std::fmt::Debug for QueryClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("QueryClient")
             .field("chain_id", &self.chain_config.chain_id)
+            .field("height", &self._height)
             .finish()
     }
 }
 
-pub trait QueryRequest: Clone + std::fmt::Debug + Send {
-    type QueryResponse: std::fmt::Debug + Send;
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        pub trait QueryRequest: Clone + std::fmt::Debug + Send + Sync + 'static {
+            type QueryResponse: std::fmt::Debug + Clone + Send + 'static;
+
+            fn request(&self, client: QueryClient) -> impl Future<Output = Result<Self::QueryResponse>>;
+
+            /// Only used if `QueryCacheMiddleware` is enabled
+            fn cache_policy(&self) -> QueryCachePolicy {
+                QueryCachePolicy::UntilNextBlock
+            }
+        }
+    } else {
+        // the futures are Send so they can be boxed up for the middleware
+        pub trait QueryRequest: Clone + std::fmt::Debug + Send + Sync + 'static {
+            type QueryResponse: std::fmt::Debug + Clone + Send + 'static;
+
+            fn request(&self, client: QueryClient) -> impl Future<Output = Result<Self::QueryResponse>> + Send;
 
-    fn request(&self, client: QueryClient) -> impl Future<Output = Result<Self::QueryResponse>>;
+            /// Only used if `QueryCacheMiddleware` is enabled
+            fn cache_policy(&self) -> QueryCachePolicy {
+                QueryCachePolicy::UntilNextBlock
+            }
+        }
+    }
 }
 
 const DEFAULT_BALANCES_PAGINATION_LIMIT: u64 = 10;
@@ -153,18 +222,51 @@ impl QueryClient {
         self._connection_mode.load(Ordering::SeqCst).into()
     }
 
+    /// The client for the active rpc endpoint
     pub fn rpc_client(&self) -> Result<&RpcClient> {
-        match self._rpc_client.as_ref() {
+        match self._rpc_clients.get(self._active_endpoints.rpc()) {
             Some(client) => Ok(client),
             None => Err(anyhow!("rpc_client isn't set")),
         }
     }
 
+    /// A clone of this client where every state query reads from the given block height instead of the latest one
+    /// e.g. to read a consistent snapshot across many queries
+    ///
+    /// Queries about the chain itself (like `block_height()`) and txs are not affected
+    /// nodes only keep a limited history unless they're archive nodes, so old heights may fail
+    pub fn at_height(&self, height: u64) -> Self {
+        Self {
+            _height: Some(height),
+            ..self.clone()
+        }
+    }
+
+    /// A clone of this client that reads from the latest block again
+    pub fn at_latest(&self) -> Self {
+        Self {
+            _height: None,
+            ..self.clone()
+        }
+    }
+
+    /// The height set via `at_height()`, if any
+    pub fn query_height(&self) -> Option<u64> {
+        self._height
+    }
+
+    /// Wraps a grpc request, pinning it to `query_height()` if set
+    pub fn grpc_request<T>(&self, req: T) -> Result<tonic::Request<T>> {
+        let mut req = tonic::Request::new(req);
+        apply_grpc_height(&mut req, self._height)?;
+        Ok(req)
+    }
+
     cfg_if::cfg_if! {
         if #[cfg(all(target_arch = "wasm32", target_os = "unknown"))] {
             pub async fn new_with_cache(chain_config: ChainConfig, cache: ClimbCache, connection: Option<Connection>) -> Result<Self> {
-                let _grpc_channel = cache.get_web_grpc(&chain_config).await?;
-                let _rpc_client = cache.get_rpc_client(&chain_config);
+                let _grpc_channels = Arc::new(cache.get_web_grpcs(&chain_config).await?);
+                let _rpc_clients = Arc::new(cache.get_rpc_clients(&chain_config));
 
                 let connection = connection.unwrap_or_default();
 
@@ -173,14 +275,18 @@ impl QueryClient {
                     _connection_mode: Arc::new(AtomicU8::new(connection.preferred_mode.unwrap_or(ConnectionMode::Grpc) as u8)),
                     chain_config,
                     cache,
-                    middleware_map_req: Arc::new(QueryMiddlewareMapReq::default_list()),
-                    middleware_map_resp: Arc::new(QueryMiddlewareMapResp::default_list()),
-                    middleware_run: Arc::new(QueryMiddlewareRun::default_list()),
+                    middleware_map_req: Arc::new(middleware::default_middleware_map_req()),
+                    middleware_map_resp: Arc::new(middleware::default_middleware_map_resp()),
+                    middleware_run: Arc::new(middleware::default_middleware_run()),
                     balances_pagination_limit: DEFAULT_BALANCES_PAGINATION_LIMIT,
+                    gas_price_oracle: GasPriceOracle::default(),
                     wait_blocks_poll_sleep_duration: DEFAULT_WAIT_BLOCKS_POLL_SLEEP_DURATION,
-                    _grpc_channel,
-                    _rpc_client,
+                    _grpc_channels,
+                    _rpc_clients,
+                    _active_endpoints: ActiveEndpoints::default(),
                     connection,
+                    _height: None,
+                    _gov_version: Arc::new(AtomicU8::new(0)),
                 };
 
                 if _self.connection.preferred_mode.is_none() {
@@ -191,7 +297,7 @@ impl QueryClient {
             }
         } else if #[cfg(target_arch = "wasm32")] {
             pub async fn new_with_cache(chain_config: ChainConfig, cache: ClimbCache, connection: Option<Connection>) -> Result<Self> {
-                let _rpc_client = cache.get_rpc_client(&chain_config);
+                let _rpc_clients = Arc::new(cache.get_rpc_clients(&chain_config));
 
                 let connection = connection.unwrap_or_default();
 
@@ -200,13 +306,17 @@ impl QueryClient {
                     _connection_mode: Arc::new(AtomicU8::new(connection.preferred_mode.unwrap_or(ConnectionMode::Rpc) as u8)),
                     chain_config,
                     cache,
-                    middleware_map_req: Arc::new(QueryMiddlewareMapReq::default_list()),
-                    middleware_map_resp: Arc::new(QueryMiddlewareMapResp::default_list()),
-                    middleware_run: Arc::new(QueryMiddlewareRun::default_list()),
+                    middleware_map_req: Arc::new(middleware::default_middleware_map_req()),
+                    middleware_map_resp: Arc::new(middleware::default_middleware_map_resp()),
+                    middleware_run: Arc::new(middleware::default_middleware_run()),
                     balances_pagination_limit: DEFAULT_BALANCES_PAGINATION_LIMIT,
+                    gas_price_oracle: GasPriceOracle::default(),
                     wait_blocks_poll_sleep_duration: DEFAULT_WAIT_BLOCKS_POLL_SLEEP_DURATION,
-                    _rpc_client,
+                    _rpc_clients,
+                    _active_endpoints: ActiveEndpoints::default(),
                     connection,
+                    _height: None,
+                    _gov_version: Arc::new(AtomicU8::new(0)),
                 };
 
                 if _self.connection.preferred_mode.is_none() {
@@ -217,8 +327,8 @@ impl QueryClient {
             }
         } else {
             pub async fn new_with_cache(chain_config: ChainConfig, cache: ClimbCache, connection: Option<Connection>) -> Result<Self> {
-                let _grpc_channel = cache.get_grpc(&chain_config).await?;
-                let _rpc_client = cache.get_rpc_client(&chain_config);
+                let _grpc_channels = Arc::new(cache.get_grpcs(&chain_config).await?);
+                let _rpc_clients = Arc::new(cache.get_rpc_clients(&chain_config));
 
                 let connection = connection.unwrap_or_default();
 
@@ -227,14 +337,18 @@ impl QueryClient {
                     _connection_mode: Arc::new(AtomicU8::new(connection.preferred_mode.unwrap_or(ConnectionMode::Rpc) as u8)),
                     chain_config,
                     cache,
-                    middleware_map_req: Arc::new(QueryMiddlewareMapReq::default_list()),
-                    middleware_map_resp: Arc::new(QueryMiddlewareMapResp::default_list()),
-                    middleware_run: Arc::new(QueryMiddlewareRun::default_list()),
+                    middleware_map_req: Arc::new(middleware::default_middleware_map_req()),
+                    middleware_map_resp: Arc::new(middleware::default_middleware_map_resp()),
+                    middleware_run: Arc::new(middleware::default_middleware_run()),
                     balances_pagination_limit: DEFAULT_BALANCES_PAGINATION_LIMIT,
+                    gas_price_oracle: GasPriceOracle::default(),
                     wait_blocks_poll_sleep_duration: DEFAULT_WAIT_BLOCKS_POLL_SLEEP_DURATION,
-                    _grpc_channel,
-                    _rpc_client,
+                    _grpc_channels,
+                    _rpc_clients,
+                    _active_endpoints: ActiveEndpoints::default(),
                     connection,
+                    _height: None,
+                    _gov_version: Arc::new(AtomicU8::new(0)),
                 };
 
                 if _self.connection.preferred_mode.is_none() {
@@ -247,42 +361,6 @@ impl QueryClient {
         }
     }
 
-    pub async fn run_with_middleware<REQ: QueryRequest>(
-        &self,
-        mut req: REQ,
-    ) -> Result<REQ::QueryResponse> {
-        for middleware in self.middleware_map_req.iter() {
-            req = match middleware.map_req(req.clone()).await {
-                Ok(req) => req,
-                Err(e) => return Err(e),
-            }
-        }
-
-        let mut response = None;
-
-        for middleware in self.middleware_run.iter() {
-            response = match middleware.run(req.clone(), self.clone()).await {
-                Ok(resp) => Some(resp),
-                Err(e) => return Err(e),
-            }
-        }
-
-        if response.is_none() {
-            response = Some(req.request(self.clone()).await?);
-        }
-
-        let mut response = response.unwrap();
-
-        for middleware in self.middleware_map_resp.iter() {
-            response = match middleware.map_resp(response).await {
-                Ok(resp) => resp,
-                Err(e) => return Err(e),
-            }
-        }
-
-        Ok(response)
-    }
-
     // these do not call middleware, but their inner calls do
     pub async fn wait_until_block_height(
         &self,
@@ -5,7 +5,7 @@ use crate::{
     prelude::*,
 };
 
-use super::ConnectionMode;
+use super::{middleware::cache::QueryCachePolicy, ConnectionMode};
 
 impl QueryClient {
     // from looking at other implementations, it might seem like getting proof_height from the current remote block height is the way to go
@@ -129,7 +129,7 @@ impl AbciProofKind {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct AbciProofResponse {
     pub proof: Vec<u8>,
     pub value: Vec<u8>,
@@ -139,13 +139,20 @@ pub struct AbciProofResponse {
 impl QueryRequest for AbciProofReq {
     type QueryResponse = AbciProofResponse;
 
+    fn cache_policy(&self) -> QueryCachePolicy {
+        match self.height {
+            Some(_) => QueryCachePolicy::Forever,
+            None => QueryCachePolicy::UntilNextBlock,
+        }
+    }
+
     async fn request(&self, client: QueryClient) -> Result<AbciProofResponse> {
         match client.get_connection_mode() {
             ConnectionMode::Grpc => {
                 let req = tonic::Request::new(layer_climb_proto::tendermint::AbciQueryRequest {
                     path: self.kind.path().to_string(),
                     data: self.kind.data_bytes(),
-                    height: match self.height {
+                    height: match self.height.or(client.query_height()) {
                         Some(height) => height.try_into()?,
                         // according to the rpc docs, 0 is latest...
                         None => 0.into(),
@@ -185,7 +192,7 @@ impl QueryRequest for AbciProofReq {
                     .abci_query(
                         self.kind.path().to_string(),
                         self.kind.data_bytes(),
-                        self.height,
+                        self.height.or(client.query_height()),
                         true,
                     )
                     .await?;
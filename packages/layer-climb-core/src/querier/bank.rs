@@ -0,0 +1,446 @@
+use futures::{Stream, TryStreamExt};
+use tracing::instrument;
+
+use crate::prelude::*;
+
+use super::pagination::{paginate, Page, PaginatedResponse, PaginationOptions};
+
+// balances themselves are in basic.rs
+
+impl QueryClient {
+    /// The total supply of every denom
+    #[instrument]
+    pub async fn total_supply(
+        &self,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<layer_climb_proto::Coin>> {
+        self.run_with_middleware(TotalSupplyReq { limit_per_page })
+            .await
+    }
+
+    /// Like `total_supply()`, but as a stream that fetches pages as it goes
+    pub fn stream_total_supply(
+        &self,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<layer_climb_proto::Coin>> {
+        paginate(self.clone(), options, move |client, pagination| {
+            let req = TotalSupplyPageReq { pagination };
+            async move { client.run_with_middleware(req).await }
+        })
+    }
+
+    /// The total supply of one denom, if not set the chain's gas denom
+    #[instrument]
+    pub async fn supply_of(&self, denom: Option<String>) -> Result<u128> {
+        self.run_with_middleware(SupplyOfReq { denom }).await
+    }
+
+    /// The display units and their exponents (among other things) of a denom
+    /// errors if the chain has no metadata for it
+    #[instrument]
+    pub async fn denom_metadata(
+        &self,
+        denom: impl ToString + std::fmt::Debug,
+    ) -> Result<layer_climb_proto::bank::Metadata> {
+        self.run_with_middleware(DenomMetadataReq {
+            denom: denom.to_string(),
+        })
+        .await
+    }
+
+    /// The metadata of every denom the chain has metadata for
+    #[instrument]
+    pub async fn denoms_metadata(
+        &self,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<layer_climb_proto::bank::Metadata>> {
+        self.run_with_middleware(DenomsMetadataReq { limit_per_page })
+            .await
+    }
+
+    /// The balances that aren't locked, e.g. by vesting
+    #[instrument]
+    pub async fn spendable_balances(
+        &self,
+        addr: Address,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<layer_climb_proto::Coin>> {
+        self.run_with_middleware(SpendableBalancesReq {
+            addr,
+            limit_per_page,
+        })
+        .await
+    }
+
+    /// The send-enabled flags set for the given denoms, or all of them if `denoms` is empty
+    ///
+    /// Denoms without a flag of their own use `default_send_enabled` from `bank_params()`
+    /// requires cosmos-sdk 0.47 or later
+    #[instrument]
+    pub async fn send_enabled(
+        &self,
+        denoms: Vec<String>,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<layer_climb_proto::bank::SendEnabled>> {
+        self.run_with_middleware(SendEnabledReq {
+            denoms,
+            limit_per_page,
+        })
+        .await
+    }
+
+    #[instrument]
+    pub async fn bank_params(&self) -> Result<layer_climb_proto::bank::Params> {
+        self.run_with_middleware(BankParamsReq {}).await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TotalSupplyReq {
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for TotalSupplyReq {
+    type QueryResponse = Vec<layer_climb_proto::Coin>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = TotalSupplyPageReq { pagination };
+            async move { req.request(client).await }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TotalSupplyPageReq {
+    pub pagination: layer_climb_proto::query::PageRequest,
+}
+
+impl QueryRequest for TotalSupplyPageReq {
+    type QueryResponse = Page<layer_climb_proto::Coin>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::bank::QueryTotalSupplyRequest {
+            pagination: Some(self.pagination.clone()),
+        };
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client = layer_climb_proto::bank::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                );
+
+                query_client
+                    .total_supply(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner())
+                    .context("couldn't get total supply")?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::bank::QueryTotalSupplyResponse>(
+                    "/cosmos.bank.v1beta1.Query/TotalSupply",
+                    req,
+                    client.query_height(),
+                )
+                .await
+                .context("couldn't get total supply")?,
+        };
+
+        Ok(resp.into_page())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SupplyOfReq {
+    pub denom: Option<String>,
+}
+
+impl QueryRequest for SupplyOfReq {
+    type QueryResponse = u128;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let denom = self
+            .denom
+            .clone()
+            .unwrap_or(client.chain_config.gas_denom.clone());
+
+        let req = layer_climb_proto::bank::QuerySupplyOfRequest {
+            denom: denom.clone(),
+        };
+
+        let coin = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client = layer_climb_proto::bank::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                );
+
+                query_client
+                    .supply_of(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner().amount)
+                    .with_context(|| format!("couldn't get supply of {denom}"))?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::bank::QuerySupplyOfResponse>(
+                    "/cosmos.bank.v1beta1.Query/SupplyOf",
+                    req,
+                    client.query_height(),
+                )
+                .await
+                .map(|res| res.amount)
+                .with_context(|| format!("couldn't get supply of {denom}"))?,
+        };
+
+        match coin {
+            None => Ok(0),
+            Some(coin) => coin.amount.parse::<u128>().context("couldn't parse amount"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DenomMetadataReq {
+    pub denom: String,
+}
+
+impl QueryRequest for DenomMetadataReq {
+    type QueryResponse = layer_climb_proto::bank::Metadata;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::bank::QueryDenomMetadataRequest {
+            denom: self.denom.clone(),
+        };
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client = layer_climb_proto::bank::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                );
+
+                query_client
+                    .denom_metadata(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner())
+                    .with_context(|| format!("couldn't get metadata of {}", self.denom))?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::bank::QueryDenomMetadataResponse>(
+                    "/cosmos.bank.v1beta1.Query/DenomMetadata",
+                    req,
+                    client.query_height(),
+                )
+                .await
+                .with_context(|| format!("couldn't get metadata of {}", self.denom))?,
+        };
+
+        resp.metadata
+            .ok_or_else(|| anyhow!("no metadata for {}", self.denom))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DenomsMetadataReq {
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for DenomsMetadataReq {
+    type QueryResponse = Vec<layer_climb_proto::bank::Metadata>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = layer_climb_proto::bank::QueryDenomsMetadataRequest {
+                pagination: Some(pagination),
+            };
+
+            async move {
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            layer_climb_proto::bank::query_client::QueryClient::new(
+                                client.clone_grpc_channel()?,
+                            );
+
+                        query_client
+                            .denoms_metadata(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .context("couldn't get denoms metadata")?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, layer_climb_proto::bank::QueryDenomsMetadataResponse>(
+                            "/cosmos.bank.v1beta1.Query/DenomsMetadata",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .context("couldn't get denoms metadata")?,
+                };
+
+                Ok(resp.into_page())
+            }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SpendableBalancesReq {
+    pub addr: Address,
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for SpendableBalancesReq {
+    type QueryResponse = Vec<layer_climb_proto::Coin>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = layer_climb_proto::bank::QuerySpendableBalancesRequest {
+                address: self.addr.to_string(),
+                pagination: Some(pagination),
+            };
+
+            async move {
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            layer_climb_proto::bank::query_client::QueryClient::new(
+                                client.clone_grpc_channel()?,
+                            );
+
+                        query_client
+                            .spendable_balances(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .context("couldn't get spendable balances")?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, layer_climb_proto::bank::QuerySpendableBalancesResponse>(
+                            "/cosmos.bank.v1beta1.Query/SpendableBalances",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .context("couldn't get spendable balances")?,
+                };
+
+                Ok(resp.into_page())
+            }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SendEnabledReq {
+    /// if empty, all of them
+    pub denoms: Vec<String>,
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for SendEnabledReq {
+    type QueryResponse = Vec<layer_climb_proto::bank::SendEnabled>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = layer_climb_proto::bank::QuerySendEnabledRequest {
+                denoms: self.denoms.clone(),
+                pagination: Some(pagination),
+            };
+
+            async move {
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            layer_climb_proto::bank::query_client::QueryClient::new(
+                                client.clone_grpc_channel()?,
+                            );
+
+                        query_client
+                            .send_enabled(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .context("couldn't get send enabled")?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, layer_climb_proto::bank::QuerySendEnabledResponse>(
+                            "/cosmos.bank.v1beta1.Query/SendEnabled",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .context("couldn't get send enabled")?,
+                };
+
+                Ok(resp.into_page())
+            }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BankParamsReq {}
+
+impl QueryRequest for BankParamsReq {
+    type QueryResponse = layer_climb_proto::bank::Params;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::bank::QueryParamsRequest {};
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client = layer_climb_proto::bank::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                );
+
+                query_client
+                    .params(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner())
+                    .context("couldn't get bank params")?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::bank::QueryParamsResponse>(
+                    "/cosmos.bank.v1beta1.Query/Params",
+                    req,
+                    client.query_height(),
+                )
+                .await
+                .context("couldn't get bank params")?,
+        };
+
+        resp.params.context("no bank params")
+    }
+}
@@ -2,7 +2,13 @@ use tracing::instrument;
 
 use crate::prelude::*;
 
-use super::ConnectionMode;
+use futures::{Stream, TryStreamExt};
+
+use super::{
+    middleware::cache::QueryCachePolicy,
+    pagination::{paginate, Page, PaginatedResponse, PaginationOptions},
+    ConnectionMode,
+};
 
 impl QueryClient {
     #[instrument]
@@ -23,6 +29,21 @@ impl QueryClient {
         .await
     }
 
+    /// Like `all_balances()`, but as a stream that fetches pages as it goes
+    pub fn stream_all_balances(
+        &self,
+        addr: Address,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<layer_climb_proto::Coin>> {
+        paginate(self.clone(), options, move |client, pagination| {
+            let req = AllBalancesPageReq {
+                addr: addr.clone(),
+                pagination,
+            };
+            async move { client.run_with_middleware(req).await }
+        })
+    }
+
     #[instrument]
     pub async fn base_account(
         &self,
@@ -37,25 +58,61 @@ impl QueryClient {
         self.run_with_middleware(StakingParamsReq {}).await
     }
 
+    // blocks aren't state, so these ignore `at_height()` (which would otherwise also pin them in the cache)
+    // i.e. `None` is always the latest block
+
     #[instrument]
     pub async fn block(&self, height: Option<u64>) -> Result<BlockResp> {
-        self.run_with_middleware(BlockReq { height }).await
+        self.at_latest()
+            .run_with_middleware(BlockReq { height })
+            .await
     }
 
     #[instrument]
     pub async fn block_header(&self, height: Option<u64>) -> Result<BlockHeaderResp> {
-        self.run_with_middleware(BlockHeaderReq { height }).await
+        self.at_latest()
+            .run_with_middleware(BlockHeaderReq { height })
+            .await
     }
 
     #[instrument]
     pub async fn block_height(&self) -> Result<u64> {
-        self.run_with_middleware(BlockHeightReq {}).await
+        self.at_latest()
+            .run_with_middleware(BlockHeightReq {})
+            .await
     }
 
     #[instrument]
     pub async fn node_info(&self) -> Result<layer_climb_proto::tendermint::GetNodeInfoResponse> {
         self.run_with_middleware(NodeInfoReq {}).await
     }
+
+    /// Unordered txs were added in cosmos-sdk 0.53
+    ///
+    /// This only checks the sdk version the node reports, but a 0.53 app also has to opt in
+    /// via `WithUnorderedTransactions`, so `true` can still mean the chain rejects them
+    #[instrument]
+    pub async fn supports_unordered_txs(&self) -> Result<bool> {
+        let node_info = self.node_info().await?;
+        let version = node_info
+            .application_version
+            .context("node info has no application version")?
+            .cosmos_sdk_version;
+
+        sdk_version_supports_unordered(&version)
+    }
+}
+
+// e.g. "v0.53.0", possibly with a suffix like "-lsm"
+fn sdk_version_supports_unordered(version: &str) -> Result<bool> {
+    let mut parts = version.trim_start_matches('v').split(['.', '-']);
+    let major = parts.next().and_then(|part| part.parse::<u64>().ok());
+    let minor = parts.next().and_then(|part| part.parse::<u64>().ok());
+
+    match (major, minor) {
+        (Some(major), Some(minor)) => Ok(major > 0 || minor >= 53),
+        _ => bail!("couldn't parse cosmos-sdk version \"{version}\""),
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -85,7 +142,7 @@ impl QueryRequest for BalanceReq {
                 );
 
                 query_client
-                    .balance(req)
+                    .balance(client.grpc_request(req)?)
                     .await
                     .map(|res| res.into_inner().balance)?
             }
@@ -94,7 +151,7 @@ impl QueryRequest for BalanceReq {
                 .abci_protobuf_query::<_, layer_climb_proto::bank::QueryBalanceResponse>(
                     "/cosmos.bank.v1beta1.Query/Balance",
                     req,
-                    None,
+                    client.query_height(),
                 )
                 .await
                 .map(|res| res.balance)?,
@@ -123,74 +180,62 @@ impl QueryRequest for AllBalancesReq {
     type QueryResponse = Vec<layer_climb_proto::Coin>;
 
     async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
-        let mut coins = Vec::new();
+        let options = PaginationOptions {
+            page_size: Some(
+                self.limit_per_page
+                    .unwrap_or(client.balances_pagination_limit),
+            ),
+            ..Default::default()
+        };
 
-        let mut pagination = None;
+        // already inside the middleware, so the pages are requested directly
+        paginate(client, options, |client, pagination| {
+            let req = AllBalancesPageReq {
+                addr: self.addr.clone(),
+                pagination,
+            };
+            async move { req.request(client).await }
+        })
+        .try_collect()
+        .await
+    }
+}
 
-        let limit = self
-            .limit_per_page
-            .unwrap_or(client.balances_pagination_limit);
+#[derive(Clone, Debug)]
+pub struct AllBalancesPageReq {
+    pub addr: Address,
+    pub pagination: layer_climb_proto::query::PageRequest,
+}
 
-        let mut grpc_query_client = match client.get_connection_mode() {
-            ConnectionMode::Grpc => Some(layer_climb_proto::bank::query_client::QueryClient::new(
-                client.clone_grpc_channel()?,
-            )),
-            ConnectionMode::Rpc => None,
-        };
+impl QueryRequest for AllBalancesPageReq {
+    type QueryResponse = Page<layer_climb_proto::Coin>;
 
-        // for RPC, keep a consistent height
-        let height = match client.get_connection_mode() {
-            ConnectionMode::Grpc => None,
-            ConnectionMode::Rpc => Some(BlockHeightReq {}.request(client.clone()).await?),
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::bank::QueryAllBalancesRequest {
+            address: self.addr.to_string(),
+            pagination: Some(self.pagination.clone()),
+            resolve_denom: true,
         };
 
-        loop {
-            let req = layer_climb_proto::bank::QueryAllBalancesRequest {
-                address: self.addr.to_string(),
-                pagination,
-                resolve_denom: true,
-            };
-
-            let resp = match client.get_connection_mode() {
-                ConnectionMode::Grpc => grpc_query_client
-                    .as_mut()
-                    .unwrap()
-                    .all_balances(req)
-                    .await
-                    .map(|res| res.into_inner())?,
+        let resp =
+            match client.get_connection_mode() {
+                ConnectionMode::Grpc => layer_climb_proto::bank::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                )
+                .all_balances(client.grpc_request(req)?)
+                .await
+                .map(|res| res.into_inner())?,
                 ConnectionMode::Rpc => client
                     .rpc_client()?
                     .abci_protobuf_query::<_, layer_climb_proto::bank::QueryAllBalancesResponse>(
                         "/cosmos.bank.v1beta1.Query/AllBalances",
                         req,
-                        height,
+                        client.query_height(),
                     )
                     .await?,
             };
 
-            coins.extend(resp.balances);
-
-            match &resp.pagination {
-                None => break,
-                Some(pagination_response) => {
-                    if pagination_response.next_key.is_empty() {
-                        break;
-                    }
-                }
-            }
-
-            pagination = resp
-                .pagination
-                .map(|p| layer_climb_proto::query::PageRequest {
-                    key: p.next_key,
-                    offset: 0,
-                    limit,
-                    count_total: false,
-                    reverse: false,
-                });
-        }
-
-        Ok(coins)
+        Ok(resp.into_page())
     }
 }
 
@@ -202,6 +247,11 @@ pub struct BaseAccountReq {
 impl QueryRequest for BaseAccountReq {
     type QueryResponse = layer_climb_proto::auth::BaseAccount;
 
+    // the sequence changes within a block as txs are broadcast
+    fn cache_policy(&self) -> QueryCachePolicy {
+        QueryCachePolicy::Never
+    }
+
     async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
         let req = layer_climb_proto::auth::QueryAccountRequest {
             address: self.addr.to_string(),
@@ -214,7 +264,7 @@ impl QueryRequest for BaseAccountReq {
                 );
 
                 query_client
-                    .account(req)
+                    .account(client.grpc_request(req)?)
                     .await
                     .map(|res| res.into_inner().account)
                     .map_err(|err| {
@@ -227,7 +277,7 @@ impl QueryRequest for BaseAccountReq {
                 .abci_protobuf_query::<_, layer_climb_proto::auth::QueryAccountResponse>(
                     "/cosmos.auth.v1beta1.Query/Account",
                     req,
-                    None,
+                    client.query_height(),
                 )
                 .await?
                 .account
@@ -255,7 +305,7 @@ impl QueryRequest for StakingParamsReq {
                 );
 
                 query_client
-                    .params(layer_climb_proto::staking::QueryParamsRequest {})
+                    .params(client.grpc_request(layer_climb_proto::staking::QueryParamsRequest {})?)
                     .await
                     .map(|res| res.into_inner())
                     .context("couldn't get staking params")?
@@ -266,7 +316,7 @@ impl QueryRequest for StakingParamsReq {
                     .abci_protobuf_query::<_, layer_climb_proto::staking::QueryParamsResponse>(
                         "/cosmos.staking.v1beta1.Query/Params",
                         layer_climb_proto::staking::QueryParamsRequest {},
-                        None,
+                        client.query_height(),
                     )
                     .await?
             }
@@ -316,7 +366,7 @@ pub struct BlockReq {
     pub height: Option<u64>,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum BlockResp {
     Sdk(layer_climb_proto::block::SdkBlock),
     Old(layer_climb_proto::block::TendermintBlock),
@@ -325,6 +375,13 @@ pub enum BlockResp {
 impl QueryRequest for BlockReq {
     type QueryResponse = BlockResp;
 
+    fn cache_policy(&self) -> QueryCachePolicy {
+        match self.height {
+            Some(_) => QueryCachePolicy::Forever,
+            None => QueryCachePolicy::UntilNextBlock,
+        }
+    }
+
     async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
         let height = self.height;
 
@@ -388,7 +445,7 @@ pub struct BlockHeaderReq {
     pub height: Option<u64>,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum BlockHeaderResp {
     Sdk(layer_climb_proto::block::SdkHeader),
     Old(layer_climb_proto::block::TendermintHeader),
@@ -430,6 +487,13 @@ impl BlockHeaderResp {
 impl QueryRequest for BlockHeaderReq {
     type QueryResponse = BlockHeaderResp;
 
+    fn cache_policy(&self) -> QueryCachePolicy {
+        match self.height {
+            Some(_) => QueryCachePolicy::Forever,
+            None => QueryCachePolicy::UntilNextBlock,
+        }
+    }
+
     async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
         let block = BlockReq {
             height: self.height,
@@ -454,6 +518,11 @@ pub struct BlockHeightReq {}
 impl QueryRequest for BlockHeightReq {
     type QueryResponse = u64;
 
+    // this is how the cache knows a block has passed
+    fn cache_policy(&self) -> QueryCachePolicy {
+        QueryCachePolicy::Never
+    }
+
     async fn request(&self, client: QueryClient) -> Result<u64> {
         let header = BlockHeaderReq { height: None }.request(client).await?;
 
@@ -464,3 +533,25 @@ impl QueryRequest for BlockHeightReq {
         .try_into()?)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unordered_sdk_version() {
+        assert!(sdk_version_supports_unordered("v0.53.0").unwrap());
+        assert!(sdk_version_supports_unordered("0.53.2").unwrap());
+        assert!(sdk_version_supports_unordered("v0.53.0-rc.2").unwrap());
+        assert!(sdk_version_supports_unordered("v0.54.1").unwrap());
+        assert!(sdk_version_supports_unordered("v1.0.0").unwrap());
+
+        assert!(!sdk_version_supports_unordered("v0.50.10-lsm").unwrap());
+        assert!(!sdk_version_supports_unordered("v0.47.16").unwrap());
+        assert!(!sdk_version_supports_unordered("v0.5.3").unwrap());
+
+        assert!(sdk_version_supports_unordered("").is_err());
+        assert!(sdk_version_supports_unordered("v0").is_err());
+        assert!(sdk_version_supports_unordered("devel").is_err());
+    }
+}
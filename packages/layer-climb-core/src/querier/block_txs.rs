@@ -0,0 +1,439 @@
+use layer_climb_proto::Message;
+use tracing::instrument;
+
+use crate::{
+    prelude::*,
+    transaction::msg_registry::{MsgRegistry, TxMsg, DEFAULT_MSG_REGISTRY},
+};
+
+use super::middleware::cache::QueryCachePolicy;
+
+/// A tx in a block, see `QueryClient::block_txs()`
+#[derive(Clone, Debug)]
+pub enum BlockTx {
+    Decoded(Box<DecodedBlockTx>),
+    /// not a tx climb can decode (e.g. a chain-specific tx format), the rest of the block is still decoded
+    Undecodable {
+        hash: String,
+        bytes: Vec<u8>,
+        /// why it couldn't be decoded
+        error: String,
+        result: Box<tendermint::abci::types::ExecTxResult>,
+    },
+}
+
+impl BlockTx {
+    /// upper-case hex, as used for `QueryClient::poll_until_tx_ready()`
+    pub fn hash(&self) -> &str {
+        match self {
+            Self::Decoded(tx) => &tx.hash,
+            Self::Undecodable { hash, .. } => hash,
+        }
+    }
+
+    pub fn result(&self) -> &tendermint::abci::types::ExecTxResult {
+        match self {
+            Self::Decoded(tx) => &tx.result,
+            Self::Undecodable { result, .. } => result,
+        }
+    }
+
+    pub fn decoded(&self) -> Option<&DecodedBlockTx> {
+        match self {
+            Self::Decoded(tx) => Some(tx),
+            Self::Undecodable { .. } => None,
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.result().code == tendermint::abci::Code::Ok
+    }
+}
+
+/// A tx in a block that decoded as a cosmos-sdk tx
+#[derive(Clone, Debug)]
+pub struct DecodedBlockTx {
+    /// upper-case hex, as used for `QueryClient::poll_until_tx_ready()`
+    pub hash: String,
+    pub body: layer_climb_proto::tx::TxBodyV053,
+    pub auth_info: layer_climb_proto::tx::AuthInfo,
+    pub signatures: Vec<Vec<u8>>,
+    /// one per signer info, None if it has no public key (i.e. the key was already on chain)
+    /// or it's a kind of key an address can't be derived from
+    pub signers: Vec<Option<Address>>,
+    /// the decoded `body.messages`, any that couldn't be decoded are `TxMsg::Unknown`
+    pub msgs: Vec<TxMsg>,
+    /// failed txs are included too, check `result.code`
+    pub result: tendermint::abci::types::ExecTxResult,
+}
+
+impl QueryClient {
+    /// Every tx in the block, in order, with its messages decoded via the default `MsgRegistry`
+    /// a tx that can't be decoded doesn't fail the whole block, see `BlockTx::Undecodable`
+    #[instrument]
+    pub async fn block_txs(&self, height: u64) -> Result<Vec<BlockTx>> {
+        self.run_with_middleware(BlockTxsReq { height }).await
+    }
+
+    /// Same as `block_txs()`, with a custom `MsgRegistry`, e.g. for chain-specific messages
+    #[instrument(skip(registry))]
+    pub async fn block_txs_with_registry(
+        &self,
+        height: u64,
+        registry: &MsgRegistry,
+    ) -> Result<Vec<BlockTx>> {
+        let (txs, results) = self.run_with_middleware(BlockTxsRawReq { height }).await?;
+
+        decode_block_txs(&self.chain_config, registry, txs, results)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BlockTxsReq {
+    pub height: u64,
+}
+
+impl QueryRequest for BlockTxsReq {
+    type QueryResponse = Vec<BlockTx>;
+
+    fn cache_policy(&self) -> QueryCachePolicy {
+        QueryCachePolicy::Forever
+    }
+
+    async fn request(&self, client: QueryClient) -> Result<Vec<BlockTx>> {
+        let (txs, results) = BlockTxsRawReq {
+            height: self.height,
+        }
+        .request(client.clone())
+        .await?;
+
+        decode_block_txs(&client.chain_config, &DEFAULT_MSG_REGISTRY, txs, results)
+    }
+}
+
+/// The raw txs of a block and their results
+#[derive(Clone, Debug)]
+pub struct BlockTxsRawReq {
+    pub height: u64,
+}
+
+impl QueryRequest for BlockTxsRawReq {
+    type QueryResponse = (Vec<Vec<u8>>, Vec<tendermint::abci::types::ExecTxResult>);
+
+    fn cache_policy(&self) -> QueryCachePolicy {
+        QueryCachePolicy::Forever
+    }
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        // block results only exist on rpc
+        let rpc_client = client.rpc_client()?;
+
+        let block = rpc_client
+            .block(Some(self.height))
+            .await
+            .with_context(|| format!("couldn't get block {}", self.height))?;
+        let results = rpc_client
+            .block_results(self.height)
+            .await
+            .with_context(|| format!("couldn't get block results {}", self.height))?;
+
+        let txs = block.block.data;
+        let results = results.txs_results.unwrap_or_default();
+
+        if txs.len() != results.len() {
+            bail!(
+                "block {} has {} txs but {} tx results",
+                self.height,
+                txs.len(),
+                results.len()
+            );
+        }
+
+        Ok((txs, results))
+    }
+}
+
+fn decode_block_txs(
+    chain_config: &ChainConfig,
+    registry: &MsgRegistry,
+    txs: Vec<Vec<u8>>,
+    results: Vec<tendermint::abci::types::ExecTxResult>,
+) -> Result<Vec<BlockTx>> {
+    Ok(txs
+        .into_iter()
+        .zip(results)
+        .map(|(tx, result)| decode_block_tx(chain_config, registry, tx, result))
+        .collect())
+}
+
+fn decode_block_tx(
+    chain_config: &ChainConfig,
+    registry: &MsgRegistry,
+    tx: Vec<u8>,
+    result: tendermint::abci::types::ExecTxResult,
+) -> BlockTx {
+    use tendermint::crypto::Sha256;
+
+    let hash = const_hex::encode_upper(tendermint::crypto::default::Sha256::digest(&tx));
+
+    match decode_tx(&tx) {
+        Ok((tx_raw, body, auth_info)) => {
+            let signers = auth_info
+                .signer_infos
+                .iter()
+                .map(|signer_info| match &signer_info.public_key {
+                    Some(public_key) => signer_address(chain_config, public_key),
+                    None => None,
+                })
+                .collect();
+
+            let msgs = registry.decode_all(&body.messages);
+
+            BlockTx::Decoded(Box::new(DecodedBlockTx {
+                hash,
+                body,
+                auth_info,
+                signatures: tx_raw.signatures,
+                signers,
+                msgs,
+                result,
+            }))
+        }
+        Err(err) => BlockTx::Undecodable {
+            hash,
+            bytes: tx,
+            error: format!("{err:?}"),
+            result: Box::new(result),
+        },
+    }
+}
+
+fn decode_tx(
+    tx: &[u8],
+) -> Result<(
+    layer_climb_proto::tx::TxRaw,
+    layer_climb_proto::tx::TxBodyV053,
+    layer_climb_proto::tx::AuthInfo,
+)> {
+    let tx_raw = layer_climb_proto::tx::TxRaw::decode(tx).context("couldn't decode tx")?;
+    let body = layer_climb_proto::tx::TxBodyV053::decode(tx_raw.body_bytes.as_slice())
+        .context("couldn't decode tx body")?;
+    let auth_info = layer_climb_proto::tx::AuthInfo::decode(tx_raw.auth_info_bytes.as_slice())
+        .context("couldn't decode tx auth info")?;
+
+    Ok((tx_raw, body, auth_info))
+}
+
+fn signer_address(
+    chain_config: &ChainConfig,
+    public_key: &layer_climb_proto::Any,
+) -> Option<Address> {
+    match public_key.type_url.as_str() {
+        "/cosmos.crypto.multisig.LegacyAminoPubKey" => {
+            let key = layer_climb_proto::crypto::multisig::LegacyAminoPubKey::decode(
+                public_key.value.as_slice(),
+            )
+            .ok()?;
+            let public_keys = key
+                .public_keys
+                .iter()
+                .map(tendermint_public_key)
+                .collect::<Option<Vec<_>>>()?;
+
+            chain_config
+                .address_from_multisig_pub_key(key.threshold, &public_keys)
+                .ok()
+        }
+        _ => chain_config
+            .address_from_pub_key(&tendermint_public_key(public_key)?)
+            .ok(),
+    }
+}
+
+fn tendermint_public_key(public_key: &layer_climb_proto::Any) -> Option<tendermint::PublicKey> {
+    match public_key.type_url.as_str() {
+        "/cosmos.crypto.secp256k1.PubKey" => {
+            let key =
+                layer_climb_proto::crypto::secp256k1::PubKey::decode(public_key.value.as_slice())
+                    .ok()?;
+            tendermint::PublicKey::from_raw_secp256k1(&key.key)
+        }
+        "/cosmos.crypto.ed25519.PubKey" => {
+            let key =
+                layer_climb_proto::crypto::ed25519::PubKey::decode(public_key.value.as_slice())
+                    .ok()?;
+            tendermint::PublicKey::from_raw_ed25519(&key.key)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use layer_climb_proto::{
+        bank::MsgSend,
+        tx::{AuthInfo, SignerInfo, TxBodyV053, TxRaw},
+        Any,
+    };
+    use tendermint::abci::{types::ExecTxResult, Code};
+
+    use super::*;
+
+    // the secp256k1 generator point, a valid compressed public key
+    const PUB_KEY: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const SIGNER: &str = "cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c";
+
+    fn chain_config() -> ChainConfig {
+        ChainConfig {
+            chain_id: ChainId::new("cosmoshub-4"),
+            ..Default::default()
+        }
+    }
+
+    fn tx_bytes(msgs: Vec<Any>, memo: &str, public_key: Option<Any>) -> Vec<u8> {
+        TxRaw {
+            body_bytes: TxBodyV053 {
+                messages: msgs,
+                memo: memo.to_string(),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+            auth_info_bytes: AuthInfo {
+                signer_infos: vec![SignerInfo {
+                    public_key,
+                    mode_info: None,
+                    sequence: 3,
+                }],
+                ..Default::default()
+            }
+            .encode_to_vec(),
+            signatures: vec![vec![1; 64]],
+        }
+        .encode_to_vec()
+    }
+
+    fn msg_send() -> MsgSend {
+        MsgSend {
+            from_address: SIGNER.to_string(),
+            to_address: "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu".to_string(),
+            amount: vec![new_coin(100, "uatom")],
+        }
+    }
+
+    fn result(code: u32, log: &str) -> ExecTxResult {
+        ExecTxResult {
+            code: Code::from(code),
+            log: log.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pairs_txs_with_results() {
+        let public_key = proto_into_any(&layer_climb_proto::crypto::secp256k1::PubKey {
+            key: const_hex::decode(PUB_KEY).unwrap(),
+        })
+        .unwrap();
+
+        let txs = vec![
+            tx_bytes(
+                vec![proto_into_any(&msg_send()).unwrap()],
+                "first",
+                Some(public_key),
+            ),
+            // e.g. a chain-specific tx format in the middle of the block
+            vec![0xff, 0xff, 0xff, 0x01],
+            tx_bytes(
+                vec![Any {
+                    type_url: "/chain.custom.v1.MsgUnknown".to_string(),
+                    value: vec![1, 2, 3],
+                }],
+                "third",
+                None,
+            ),
+        ];
+        let results = vec![
+            result(0, "ok"),
+            result(2, "tx parse error"),
+            result(11, "out of gas"),
+        ];
+
+        let block_txs =
+            decode_block_txs(&chain_config(), &DEFAULT_MSG_REGISTRY, txs.clone(), results).unwrap();
+        assert_eq!(block_txs.len(), 3);
+
+        for (block_tx, tx) in block_txs.iter().zip(&txs) {
+            use tendermint::crypto::Sha256;
+            let hash = tendermint::crypto::default::Sha256::digest(tx);
+            assert_eq!(block_tx.hash(), const_hex::encode_upper(hash));
+        }
+
+        let first = block_txs[0].decoded().unwrap();
+        assert!(block_txs[0].is_ok());
+        assert_eq!(first.result.log, "ok");
+        assert_eq!(first.body.memo, "first");
+        assert_eq!(first.auth_info.signer_infos[0].sequence, 3);
+        assert_eq!(first.signatures, vec![vec![1; 64]]);
+        assert_eq!(
+            first.signers,
+            vec![Some(chain_config().parse_address(SIGNER).unwrap())]
+        );
+        match first.msgs.as_slice() {
+            [TxMsg::BankSend(msg)] => assert_eq!(*msg, msg_send()),
+            msgs => panic!("unexpected msgs {msgs:?}"),
+        }
+
+        match &block_txs[1] {
+            BlockTx::Undecodable {
+                bytes,
+                error,
+                result,
+                ..
+            } => {
+                assert_eq!(*bytes, txs[1]);
+                assert!(error.contains("couldn't decode tx"), "{error}");
+                assert_eq!(result.log, "tx parse error");
+            }
+            BlockTx::Decoded(_) => panic!("expected an undecodable tx"),
+        }
+        assert!(!block_txs[1].is_ok());
+
+        // the txs after the undecodable one keep their own results
+        let third = block_txs[2].decoded().unwrap();
+        assert!(!block_txs[2].is_ok());
+        assert_eq!(third.result.code, Code::from(11));
+        assert_eq!(third.result.log, "out of gas");
+        assert_eq!(third.body.memo, "third");
+        // no public key in the signer info, e.g. it's already on chain
+        assert_eq!(third.signers, vec![None]);
+        match third.msgs.as_slice() {
+            [TxMsg::Unknown(any)] => assert_eq!(any.type_url, "/chain.custom.v1.MsgUnknown"),
+            msgs => panic!("unexpected msgs {msgs:?}"),
+        }
+    }
+
+    #[test]
+    fn bad_body_is_undecodable() {
+        let tx = TxRaw {
+            body_bytes: vec![0xff, 0xff],
+            auth_info_bytes: Vec::new(),
+            signatures: Vec::new(),
+        }
+        .encode_to_vec();
+
+        let block_txs = decode_block_txs(
+            &chain_config(),
+            &DEFAULT_MSG_REGISTRY,
+            vec![tx],
+            vec![result(0, "")],
+        )
+        .unwrap();
+
+        match &block_txs[0] {
+            BlockTx::Undecodable { error, .. } => {
+                assert!(error.contains("couldn't decode tx body"), "{error}")
+            }
+            BlockTx::Decoded(_) => panic!("expected an undecodable tx"),
+        }
+    }
+}
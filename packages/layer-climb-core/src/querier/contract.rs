@@ -1,12 +1,18 @@
 use crate::{contract_helpers::contract_msg_to_vec, prelude::*};
+use futures::{Stream, TryStreamExt};
 use layer_climb_address::{AddrKind, CosmosAddr};
 use serde::{de::DeserializeOwned, Serialize};
 use tracing::instrument;
 
+use super::{
+    middleware::cache::QueryCachePolicy,
+    pagination::{paginate, Page, PaginatedResponse, PaginationOptions},
+};
+
 impl QueryClient {
     #[instrument]
     pub async fn contract_smart<
-        D: DeserializeOwned + Send + std::fmt::Debug + Sync,
+        D: DeserializeOwned + Clone + Send + std::fmt::Debug + Sync + 'static,
         S: Serialize + std::fmt::Debug,
     >(
         &self,
@@ -53,6 +59,176 @@ impl QueryClient {
         .await
     }
 
+    /// The wasm bytes of a stored code, checked against its checksum
+    /// these can be a few megabytes, so they're never cached
+    #[instrument]
+    pub async fn contract_code_bytes(&self, code_id: u64) -> Result<Vec<u8>> {
+        self.run_with_middleware(ContractCodeBytesReq { code_id })
+            .await
+    }
+
+    /// Every stored code
+    #[instrument]
+    pub async fn contract_codes(
+        &self,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<layer_climb_proto::wasm::CodeInfoResponse>> {
+        self.run_with_middleware(ContractCodesReq { limit_per_page })
+            .await
+    }
+
+    /// Like `contract_codes()`, but as a stream that fetches pages as it goes
+    pub fn stream_contract_codes(
+        &self,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<layer_climb_proto::wasm::CodeInfoResponse>> {
+        paginate(self.clone(), options, move |client, pagination| {
+            let req = ContractCodesPageReq { pagination };
+            async move { client.run_with_middleware(req).await }
+        })
+    }
+
+    /// All the contracts instantiated from `code_id`
+    #[instrument]
+    pub async fn contracts_by_code(
+        &self,
+        code_id: u64,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<Address>> {
+        self.run_with_middleware(ContractsByCodeReq {
+            code_id,
+            limit_per_page,
+        })
+        .await
+    }
+
+    /// All the contracts instantiated from `code_id`, fetching pages as the stream is polled
+    pub fn stream_contracts_by_code(
+        &self,
+        code_id: u64,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<Address>> {
+        let chain_config = self.chain_config.clone();
+
+        paginate(self.clone(), options, move |client, pagination| {
+            let req = ContractsByCodePageReq {
+                code_id,
+                pagination,
+            };
+            async move { client.run_with_middleware(req).await }
+        })
+        .and_then(move |addr| futures::future::ready(chain_config.parse_address(&addr)))
+    }
+
+    /// All the contracts instantiated by `creator`, requires wasmd 0.40 or later
+    #[instrument]
+    pub async fn contracts_by_creator(
+        &self,
+        creator: &Address,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<Address>> {
+        self.run_with_middleware(ContractsByCreatorReq {
+            creator: creator.clone(),
+            limit_per_page,
+        })
+        .await
+    }
+
+    /// Like `contracts_by_creator()`, but as a stream that fetches pages as it goes
+    pub fn stream_contracts_by_creator(
+        &self,
+        creator: &Address,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<Address>> {
+        let chain_config = self.chain_config.clone();
+        let creator = creator.clone();
+
+        paginate(self.clone(), options, move |client, pagination| {
+            let req = ContractsByCreatorPageReq {
+                creator: creator.clone(),
+                pagination,
+            };
+            async move { client.run_with_middleware(req).await }
+        })
+        .and_then(move |addr| futures::future::ready(chain_config.parse_address(&addr)))
+    }
+
+    /// The contract's instantiation and every migration since, oldest first
+    #[instrument]
+    pub async fn contract_history(
+        &self,
+        address: &Address,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<layer_climb_proto::wasm::ContractCodeHistoryEntry>> {
+        self.run_with_middleware(ContractHistoryReq {
+            address: address.clone(),
+            limit_per_page,
+        })
+        .await
+    }
+
+    /// Like `contract_history()`, but as a stream that fetches pages as it goes
+    pub fn stream_contract_history(
+        &self,
+        address: &Address,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<layer_climb_proto::wasm::ContractCodeHistoryEntry>> {
+        let address = address.clone();
+
+        paginate(self.clone(), options, move |client, pagination| {
+            let req = ContractHistoryPageReq {
+                address: address.clone(),
+                pagination,
+            };
+            async move { client.run_with_middleware(req).await }
+        })
+    }
+
+    /// The raw value stored under `key` in the contract's storage, None if it isn't set
+    #[instrument]
+    pub async fn contract_raw_state(
+        &self,
+        address: &Address,
+        key: &[u8],
+    ) -> Result<Option<Vec<u8>>> {
+        self.run_with_middleware(ContractRawStateReq {
+            address: address.clone(),
+            key: key.to_vec(),
+        })
+        .await
+    }
+
+    /// All of a contract's raw storage, as key/value pairs
+    #[instrument]
+    pub async fn all_contract_state(
+        &self,
+        address: &Address,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<layer_climb_proto::wasm::Model>> {
+        self.run_with_middleware(AllContractStateReq {
+            address: address.clone(),
+            limit_per_page,
+        })
+        .await
+    }
+
+    /// All of a contract's raw storage, as key/value pairs, fetching pages as the stream is polled
+    pub fn stream_all_contract_state(
+        &self,
+        address: &Address,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<layer_climb_proto::wasm::Model>> {
+        let address = address.clone();
+
+        paginate(self.clone(), options, move |client, pagination| {
+            let req = AllContractStatePageReq {
+                address: address.clone(),
+                pagination,
+            };
+            async move { client.run_with_middleware(req).await }
+        })
+    }
+
     #[instrument]
     pub async fn contract_predict_address(
         &self,
@@ -94,6 +270,21 @@ impl QueryClient {
     }
 }
 
+/// The raw storage prefix of everything in a cw-storage-plus `Map` named `namespace`,
+/// under the first `parts` of its key (e.g. the first element of a tuple key), for use as a `PageCursor::Key`
+///
+/// cw-storage-plus length-prefixes the namespace and each of these parts, but not the rest of the key
+pub fn storage_prefix(namespace: &[u8], parts: &[&[u8]]) -> Vec<u8> {
+    let len = namespace.len() + parts.iter().map(|part| part.len() + 2).sum::<usize>() + 2;
+
+    let mut bytes = Vec::with_capacity(len);
+    for part in std::iter::once(namespace).chain(parts.iter().copied()) {
+        bytes.extend_from_slice(&(part.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(part);
+    }
+    bytes
+}
+
 #[derive(Debug)]
 struct ContractSmartReq<D> {
     pub address: Address,
@@ -111,7 +302,9 @@ impl<D> Clone for ContractSmartReq<D> {
     }
 }
 
-impl<D: DeserializeOwned + Send + std::fmt::Debug + Sync> QueryRequest for ContractSmartReq<D> {
+impl<D: DeserializeOwned + Clone + Send + std::fmt::Debug + Sync + 'static> QueryRequest
+    for ContractSmartReq<D>
+{
     type QueryResponse = D;
 
     async fn request(&self, client: QueryClient) -> Result<D> {
@@ -151,14 +344,18 @@ impl QueryRequest for ContractSmartRawReq {
                 );
 
                 query_client
-                    .smart_contract_state(req)
+                    .smart_contract_state(client.grpc_request(req)?)
                     .await
                     .map(|res| res.into_inner())?
             }
             ConnectionMode::Rpc => {
                 client
                     .rpc_client()?
-                    .abci_protobuf_query("/cosmwasm.wasm.v1.Query/SmartContractState", req, None)
+                    .abci_protobuf_query(
+                        "/cosmwasm.wasm.v1.Query/SmartContractState",
+                        req,
+                        client.query_height(),
+                    )
                     .await?
             }
         };
@@ -175,6 +372,11 @@ struct ContractCodeInfoReq {
 impl QueryRequest for ContractCodeInfoReq {
     type QueryResponse = layer_climb_proto::wasm::CodeInfoResponse;
 
+    // code is immutable once stored
+    fn cache_policy(&self) -> QueryCachePolicy {
+        QueryCachePolicy::Forever
+    }
+
     async fn request(
         &self,
         client: QueryClient,
@@ -226,7 +428,7 @@ impl QueryRequest for ContractInfoReq {
                 );
 
                 let resp = query_client
-                    .contract_info(req)
+                    .contract_info(client.grpc_request(req)?)
                     .await
                     .map(|res| res.into_inner())?;
 
@@ -238,7 +440,7 @@ impl QueryRequest for ContractInfoReq {
                     .abci_protobuf_query::<_, layer_climb_proto::wasm::QueryContractInfoResponse>(
                         "/cosmwasm.wasm.v1.Query/ContractInfo",
                         req,
-                        None,
+                        client.query_height(),
                     )
                     .await?;
 
@@ -247,3 +449,473 @@ impl QueryRequest for ContractInfoReq {
         }
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct ContractsByCodePageReq {
+    pub code_id: u64,
+    pub pagination: layer_climb_proto::query::PageRequest,
+}
+
+impl QueryRequest for ContractsByCodePageReq {
+    type QueryResponse = Page<String>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::wasm::QueryContractsByCodeRequest {
+            code_id: self.code_id,
+            pagination: Some(self.pagination.clone()),
+        };
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client = layer_climb_proto::wasm::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                );
+
+                query_client
+                    .contracts_by_code(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner())?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::wasm::QueryContractsByCodeResponse>(
+                    "/cosmwasm.wasm.v1.Query/ContractsByCode",
+                    req,
+                    client.query_height(),
+                )
+                .await?,
+        };
+
+        Ok(resp.into_page())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AllContractStatePageReq {
+    pub address: Address,
+    pub pagination: layer_climb_proto::query::PageRequest,
+}
+
+impl QueryRequest for AllContractStatePageReq {
+    type QueryResponse = Page<layer_climb_proto::wasm::Model>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::wasm::QueryAllContractStateRequest {
+            address: self.address.to_string(),
+            pagination: Some(self.pagination.clone()),
+        };
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client = layer_climb_proto::wasm::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                );
+
+                query_client
+                    .all_contract_state(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner())?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::wasm::QueryAllContractStateResponse>(
+                    "/cosmwasm.wasm.v1.Query/AllContractState",
+                    req,
+                    client.query_height(),
+                )
+                .await?,
+        };
+
+        Ok(resp.into_page())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ContractCodeBytesReq {
+    pub code_id: u64,
+}
+
+impl QueryRequest for ContractCodeBytesReq {
+    type QueryResponse = Vec<u8>;
+
+    fn cache_policy(&self) -> QueryCachePolicy {
+        QueryCachePolicy::Never
+    }
+
+    async fn request(&self, client: QueryClient) -> Result<Vec<u8>> {
+        use tendermint::crypto::Sha256;
+
+        let req = layer_climb_proto::wasm::QueryCodeRequest {
+            code_id: self.code_id,
+        };
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                // the wasm can be bigger than tonic's default 4MB limit
+                let mut query_client = layer_climb_proto::wasm::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                )
+                .max_decoding_message_size(usize::MAX);
+
+                query_client
+                    .code(req)
+                    .await
+                    .map(|res| res.into_inner())
+                    .with_context(|| format!("couldn't get code {}", self.code_id))?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::wasm::QueryCodeResponse>(
+                    "/cosmwasm.wasm.v1.Query/Code",
+                    req,
+                    None,
+                )
+                .await
+                .with_context(|| format!("couldn't get code {}", self.code_id))?,
+        };
+
+        let code_info = resp.code_info.context("no code info found")?;
+        let checksum = tendermint::crypto::default::Sha256::digest(&resp.data);
+
+        if checksum.as_slice() != code_info.data_hash.as_slice() {
+            bail!(
+                "checksum mismatch for code {}, expected {} but got {}",
+                self.code_id,
+                const_hex::encode(&code_info.data_hash),
+                const_hex::encode(checksum)
+            );
+        }
+
+        Ok(resp.data)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ContractCodesReq {
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for ContractCodesReq {
+    type QueryResponse = Vec<layer_climb_proto::wasm::CodeInfoResponse>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = ContractCodesPageReq { pagination };
+            async move { req.request(client).await }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ContractCodesPageReq {
+    pub pagination: layer_climb_proto::query::PageRequest,
+}
+
+impl QueryRequest for ContractCodesPageReq {
+    type QueryResponse = Page<layer_climb_proto::wasm::CodeInfoResponse>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::wasm::QueryCodesRequest {
+            pagination: Some(self.pagination.clone()),
+        };
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client = layer_climb_proto::wasm::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                );
+
+                query_client
+                    .codes(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner())
+                    .context("couldn't get codes")?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::wasm::QueryCodesResponse>(
+                    "/cosmwasm.wasm.v1.Query/Codes",
+                    req,
+                    client.query_height(),
+                )
+                .await
+                .context("couldn't get codes")?,
+        };
+
+        Ok(resp.into_page())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ContractsByCodeReq {
+    pub code_id: u64,
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for ContractsByCodeReq {
+    type QueryResponse = Vec<Address>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+        let chain_config = client.chain_config.clone();
+        let code_id = self.code_id;
+
+        paginate(client, options, |client, pagination| {
+            let req = ContractsByCodePageReq {
+                code_id,
+                pagination,
+            };
+            async move { req.request(client).await }
+        })
+        .and_then(|addr| futures::future::ready(chain_config.parse_address(&addr)))
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ContractsByCreatorReq {
+    pub creator: Address,
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for ContractsByCreatorReq {
+    type QueryResponse = Vec<Address>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+        let chain_config = client.chain_config.clone();
+
+        paginate(client, options, |client, pagination| {
+            let req = ContractsByCreatorPageReq {
+                creator: self.creator.clone(),
+                pagination,
+            };
+            async move { req.request(client).await }
+        })
+        .and_then(|addr| futures::future::ready(chain_config.parse_address(&addr)))
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ContractsByCreatorPageReq {
+    pub creator: Address,
+    pub pagination: layer_climb_proto::query::PageRequest,
+}
+
+impl QueryRequest for ContractsByCreatorPageReq {
+    type QueryResponse = Page<String>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::wasm::QueryContractsByCreatorRequest {
+            creator_address: self.creator.to_string(),
+            pagination: Some(self.pagination.clone()),
+        };
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client = layer_climb_proto::wasm::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                );
+
+                query_client
+                    .contracts_by_creator(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner())
+                    .with_context(|| format!("couldn't get contracts by {}", self.creator))?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::wasm::QueryContractsByCreatorResponse>(
+                    "/cosmwasm.wasm.v1.Query/ContractsByCreator",
+                    req,
+                    client.query_height(),
+                )
+                .await
+                .with_context(|| format!("couldn't get contracts by {}", self.creator))?,
+        };
+
+        Ok(resp.into_page())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ContractHistoryReq {
+    pub address: Address,
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for ContractHistoryReq {
+    type QueryResponse = Vec<layer_climb_proto::wasm::ContractCodeHistoryEntry>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = ContractHistoryPageReq {
+                address: self.address.clone(),
+                pagination,
+            };
+            async move { req.request(client).await }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ContractHistoryPageReq {
+    pub address: Address,
+    pub pagination: layer_climb_proto::query::PageRequest,
+}
+
+impl QueryRequest for ContractHistoryPageReq {
+    type QueryResponse = Page<layer_climb_proto::wasm::ContractCodeHistoryEntry>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::wasm::QueryContractHistoryRequest {
+            address: self.address.to_string(),
+            pagination: Some(self.pagination.clone()),
+        };
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client = layer_climb_proto::wasm::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                );
+
+                query_client
+                    .contract_history(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner())
+                    .with_context(|| format!("couldn't get history of {}", self.address))?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::wasm::QueryContractHistoryResponse>(
+                    "/cosmwasm.wasm.v1.Query/ContractHistory",
+                    req,
+                    client.query_height(),
+                )
+                .await
+                .with_context(|| format!("couldn't get history of {}", self.address))?,
+        };
+
+        Ok(resp.into_page())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ContractRawStateReq {
+    pub address: Address,
+    pub key: Vec<u8>,
+}
+
+impl QueryRequest for ContractRawStateReq {
+    type QueryResponse = Option<Vec<u8>>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::wasm::QueryRawContractStateRequest {
+            address: self.address.to_string(),
+            query_data: self.key.clone(),
+        };
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client = layer_climb_proto::wasm::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                );
+
+                query_client
+                    .raw_contract_state(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner())
+                    .with_context(|| format!("couldn't get raw state of {}", self.address))?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::wasm::QueryRawContractStateResponse>(
+                    "/cosmwasm.wasm.v1.Query/RawContractState",
+                    req,
+                    client.query_height(),
+                )
+                .await
+                .with_context(|| format!("couldn't get raw state of {}", self.address))?,
+        };
+
+        // missing keys come back empty
+        match resp.data.is_empty() {
+            true => Ok(None),
+            false => Ok(Some(resp.data)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AllContractStateReq {
+    pub address: Address,
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for AllContractStateReq {
+    type QueryResponse = Vec<layer_climb_proto::wasm::Model>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = AllContractStatePageReq {
+                address: self.address.clone(),
+                pagination,
+            };
+            async move { req.request(client).await }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn storage_prefix_encoding() {
+        assert_eq!(storage_prefix(b"balances", &[]), b"\x00\x08balances");
+        assert_eq!(
+            storage_prefix(b"allowances", &[b"alice"]),
+            b"\x00\x0aallowances\x00\x05alice"
+        );
+        // e.g. a u64 key part is its big-endian bytes
+        assert_eq!(
+            storage_prefix(b"by_id", &[&7u64.to_be_bytes()]),
+            b"\x00\x05by_id\x00\x08\x00\x00\x00\x00\x00\x00\x00\x07"
+        );
+        assert_eq!(
+            storage_prefix(b"nested", &[b"a", b"bc"]),
+            b"\x00\x06nested\x00\x01a\x00\x02bc"
+        );
+        assert_eq!(storage_prefix(b"", &[]), b"\x00\x00");
+    }
+}
@@ -0,0 +1,162 @@
+// Typed reads of a contract's raw storage, using the contract's own cw-storage-plus definitions
+// e.g. for internals the contract doesn't expose through smart queries
+
+use cw_storage_plus::{Item, KeyDeserialize, Map, Prefixer, PrimaryKey};
+use futures::{Stream, StreamExt, TryStreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::prelude::*;
+
+use super::{
+    contract::storage_prefix,
+    pagination::{PageCursor, PaginationOptions},
+};
+
+impl QueryClient {
+    /// Reads an `Item`, None if it isn't set
+    ///
+    /// e.g. `querier.contract_item(&addr, &Item::<Config>::new("config"))`
+    pub async fn contract_item<T>(&self, address: &Address, item: &Item<T>) -> Result<Option<T>>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        self.contract_raw_state(address, item.as_slice())
+            .await?
+            .map(|value| from_storage_json(&value))
+            .transpose()
+    }
+
+    /// Reads the value at `key` in a `Map`, None if it isn't set
+    pub async fn contract_map_value<'a, K, T>(
+        &self,
+        address: &Address,
+        map: &Map<K, T>,
+        key: K,
+    ) -> Result<Option<T>>
+    where
+        K: PrimaryKey<'a>,
+        T: Serialize + DeserializeOwned,
+    {
+        self.contract_raw_state(address, &map.key(key))
+            .await?
+            .map(|value| from_storage_json(&value))
+            .transpose()
+    }
+
+    /// Every entry of a `Map`, in key order, fetching pages of the contract's raw state as the stream is polled
+    ///
+    /// Starts at the map's first key, so `options.cursor` and `options.reverse` are ignored
+    pub fn stream_contract_map<'a, K, T>(
+        &self,
+        address: &Address,
+        map: &Map<K, T>,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<(K::Output, T)>>
+    where
+        K: PrimaryKey<'a> + KeyDeserialize,
+        T: Serialize + DeserializeOwned,
+    {
+        self.stream_contract_prefix::<K, T>(
+            address,
+            map_prefix(map.namespace_bytes(), &()),
+            options,
+        )
+    }
+
+    /// The entries of a `Map` under `prefix`, e.g. the first part of a tuple key
+    /// the keys are the rest of the key after the prefix
+    ///
+    /// Starts at the prefix's first key, so `options.cursor` and `options.reverse` are ignored
+    pub fn stream_contract_map_prefix<'a, K, T>(
+        &self,
+        address: &Address,
+        map: &Map<K, T>,
+        prefix: K::Prefix,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<(<K::Suffix as KeyDeserialize>::Output, T)>>
+    where
+        K: PrimaryKey<'a>,
+        T: Serialize + DeserializeOwned,
+    {
+        self.stream_contract_prefix::<K::Suffix, T>(
+            address,
+            map_prefix(map.namespace_bytes(), &prefix),
+            options,
+        )
+    }
+
+    fn stream_contract_prefix<K, T>(
+        &self,
+        address: &Address,
+        prefix: Vec<u8>,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<(K::Output, T)>>
+    where
+        K: KeyDeserialize,
+        T: DeserializeOwned,
+    {
+        // the raw state is ordered by key, so everything under the prefix is in one run starting at the prefix itself
+        let options = PaginationOptions {
+            cursor: Some(PageCursor::Key(prefix.clone())),
+            reverse: false,
+            ..options
+        };
+
+        self.stream_all_contract_state(address, options)
+            .try_take_while({
+                let prefix = prefix.clone();
+                move |model| futures::future::ready(Ok(model.key.starts_with(&prefix)))
+            })
+            .map(move |model| {
+                let model = model?;
+                let key = K::from_slice(&model.key[prefix.len()..])
+                    .map_err(|e| anyhow!("couldn't decode storage key: {e:?}"))?;
+
+                Ok((key, from_storage_json(&model.value)?))
+            })
+    }
+}
+
+// see `storage_prefix()`, which this is for a typed prefix
+fn map_prefix<'a>(namespace: &[u8], prefix: &impl Prefixer<'a>) -> Vec<u8> {
+    let parts = prefix.prefix();
+    storage_prefix(
+        namespace,
+        &parts.iter().map(|part| part.as_ref()).collect::<Vec<_>>(),
+    )
+}
+
+fn from_storage_json<T: DeserializeOwned>(value: &[u8]) -> Result<T> {
+    cosmwasm_std::from_json(value).map_err(|e| anyhow!("couldn't deserialize storage value: {e:?}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // the encoding itself is tested with `storage_prefix()`, this checks it lines up with cw-storage-plus
+    #[test]
+    fn map_prefix_matches_keys() {
+        let balances: Map<&str, u64> = Map::new("balances");
+        let prefix = map_prefix(balances.namespace_bytes(), &());
+        // the last part of a key isn't length-prefixed, so it follows the prefix as-is
+        assert_eq!(
+            balances.key("alice").to_vec(),
+            [prefix, b"alice".to_vec()].concat()
+        );
+
+        let allowances: Map<(&str, &str), u64> = Map::new("allowances");
+        let prefix = map_prefix(allowances.namespace_bytes(), &"alice");
+        assert_eq!(
+            allowances.key(("alice", "bob")).to_vec(),
+            [prefix, b"bob".to_vec()].concat()
+        );
+
+        let by_id: Map<(u64, &str), u64> = Map::new("by_id");
+        let prefix = map_prefix(by_id.namespace_bytes(), &7u64);
+        assert_eq!(
+            by_id.key((7, "x")).to_vec(),
+            [prefix, b"x".to_vec()].concat()
+        );
+    }
+}
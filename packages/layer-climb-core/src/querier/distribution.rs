@@ -0,0 +1,245 @@
+use tracing::instrument;
+
+use crate::prelude::*;
+
+impl QueryClient {
+    /// The pending staking rewards of `delegator`, per validator and in total
+    #[instrument]
+    pub async fn delegator_rewards(
+        &self,
+        delegator: Address,
+    ) -> Result<layer_climb_proto::distribution::QueryDelegationTotalRewardsResponse> {
+        self.run_with_middleware(DistributionDelegatorRewardsReq { delegator })
+            .await
+    }
+
+    /// The commission a validator has accumulated and not withdrawn yet
+    #[instrument]
+    pub async fn validator_commission(
+        &self,
+        validator_address: impl ToString + std::fmt::Debug,
+    ) -> Result<Vec<layer_climb_proto::DecCoin>> {
+        self.run_with_middleware(DistributionValidatorCommissionReq {
+            validator_address: validator_address.to_string(),
+        })
+        .await
+    }
+
+    #[instrument]
+    pub async fn community_pool(&self) -> Result<Vec<layer_climb_proto::DecCoin>> {
+        self.run_with_middleware(DistributionCommunityPoolReq {})
+            .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DistributionDelegatorRewardsReq {
+    pub delegator: Address,
+}
+
+impl QueryRequest for DistributionDelegatorRewardsReq {
+    type QueryResponse = layer_climb_proto::distribution::QueryDelegationTotalRewardsResponse;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::distribution::QueryDelegationTotalRewardsRequest {
+            delegator_address: self.delegator.to_string(),
+        };
+
+        match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client =
+                    layer_climb_proto::distribution::query_client::QueryClient::new(
+                        client.clone_grpc_channel()?,
+                    );
+
+                query_client
+                    .delegation_total_rewards(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner())
+                    .context("couldn't get delegator rewards")
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::distribution::QueryDelegationTotalRewardsResponse>(
+                    "/cosmos.distribution.v1beta1.Query/DelegationTotalRewards",
+                    req,
+                    client.query_height(),
+                )
+                .await
+                .context("couldn't get delegator rewards"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DistributionValidatorCommissionReq {
+    pub validator_address: String,
+}
+
+impl QueryRequest for DistributionValidatorCommissionReq {
+    type QueryResponse = Vec<layer_climb_proto::DecCoin>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::distribution::QueryValidatorCommissionRequest {
+            validator_address: self.validator_address.clone(),
+        };
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client =
+                    layer_climb_proto::distribution::query_client::QueryClient::new(
+                        client.clone_grpc_channel()?,
+                    );
+
+                query_client
+                    .validator_commission(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner())
+                    .context("couldn't get validator commission")?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::distribution::QueryValidatorCommissionResponse>(
+                    "/cosmos.distribution.v1beta1.Query/ValidatorCommission",
+                    req,
+                    client.query_height(),
+                )
+                .await
+                .context("couldn't get validator commission")?,
+        };
+
+        Ok(resp
+            .commission
+            .map(|commission| commission.commission)
+            .unwrap_or_default())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DistributionCommunityPoolReq {}
+
+impl QueryRequest for DistributionCommunityPoolReq {
+    type QueryResponse = Vec<layer_climb_proto::DecCoin>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::distribution::QueryCommunityPoolRequest {};
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client =
+                    layer_climb_proto::distribution::query_client::QueryClient::new(
+                        client.clone_grpc_channel()?,
+                    );
+
+                query_client
+                    .community_pool(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner())
+                    .context("couldn't get community pool")?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::distribution::QueryCommunityPoolResponse>(
+                    "/cosmos.distribution.v1beta1.Query/CommunityPool",
+                    req,
+                    client.query_height(),
+                )
+                .await
+                .context("couldn't get community pool")?,
+        };
+
+        Ok(resp.pool)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use layer_climb_proto::distribution::{
+        DelegationDelegatorReward, QueryCommunityPoolResponse, QueryDelegationTotalRewardsRequest,
+        QueryDelegationTotalRewardsResponse, QueryValidatorCommissionRequest,
+        QueryValidatorCommissionResponse, ValidatorAccumulatedCommission,
+    };
+
+    use super::*;
+    use crate::testing::{address, query_client};
+
+    fn dec_coin(amount: &str) -> layer_climb_proto::DecCoin {
+        layer_climb_proto::DecCoin {
+            denom: "uatom".to_string(),
+            amount: amount.to_string(),
+        }
+    }
+
+    #[test]
+    fn delegator_rewards() {
+        let client = query_client(|path, data, _| {
+            assert_eq!(
+                path,
+                "/cosmos.distribution.v1beta1.Query/DelegationTotalRewards"
+            );
+            let req = QueryDelegationTotalRewardsRequest::decode(data).unwrap();
+            assert_eq!(req.delegator_address, address(0).to_string());
+
+            Ok(QueryDelegationTotalRewardsResponse {
+                rewards: vec![DelegationDelegatorReward {
+                    validator_address: "cosmosvaloper1".to_string(),
+                    reward: vec![dec_coin("1500000000000000000000")],
+                }],
+                total: vec![dec_coin("1500000000000000000000")],
+            }
+            .encode_to_vec())
+        });
+
+        let rewards = block_on(client.delegator_rewards(address(0))).unwrap();
+        assert_eq!(rewards.rewards.len(), 1);
+        assert_eq!(rewards.rewards[0].validator_address, "cosmosvaloper1");
+        assert_eq!(rewards.total, vec![dec_coin("1500000000000000000000")]);
+    }
+
+    #[test]
+    fn validator_commission() {
+        let client = query_client(|path, data, _| {
+            assert_eq!(
+                path,
+                "/cosmos.distribution.v1beta1.Query/ValidatorCommission"
+            );
+            let req = QueryValidatorCommissionRequest::decode(data).unwrap();
+
+            Ok(QueryValidatorCommissionResponse {
+                commission: (req.validator_address == "cosmosvaloper1").then(|| {
+                    ValidatorAccumulatedCommission {
+                        commission: vec![dec_coin("250000000000000000000")],
+                    }
+                }),
+            }
+            .encode_to_vec())
+        });
+
+        assert_eq!(
+            block_on(client.validator_commission("cosmosvaloper1")).unwrap(),
+            vec![dec_coin("250000000000000000000")]
+        );
+        // no commission yet is empty rather than an error
+        assert!(block_on(client.validator_commission("cosmosvaloper2"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn community_pool() {
+        let client = query_client(|path, _, _| {
+            assert_eq!(path, "/cosmos.distribution.v1beta1.Query/CommunityPool");
+
+            Ok(QueryCommunityPoolResponse {
+                pool: vec![dec_coin("42000000000000000000")],
+            }
+            .encode_to_vec())
+        });
+
+        assert_eq!(
+            block_on(client.community_pool()).unwrap(),
+            vec![dec_coin("42000000000000000000")]
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use super::{basic::BlockHeightReq, ConnectionMode};
+use crate::prelude::*;
+
+/// Which endpoint is in use for each connection mode, as an index into the priority-ordered list
+/// shared between clones of a `QueryClient`, so a failover is seen by all of them
+#[derive(Clone, Default)]
+pub(crate) struct ActiveEndpoints {
+    grpc: Arc<AtomicUsize>,
+    rpc: Arc<AtomicUsize>,
+}
+
+impl ActiveEndpoints {
+    pub fn grpc(&self) -> usize {
+        self.grpc.load(Ordering::SeqCst)
+    }
+
+    pub fn rpc(&self) -> usize {
+        self.rpc.load(Ordering::SeqCst)
+    }
+
+    // not shared with anyone else, so it can't be moved by a failover
+    fn pinned(mode: ConnectionMode, index: usize) -> Self {
+        let _self = Self::default();
+        _self.for_mode(mode).store(index, Ordering::SeqCst);
+        _self
+    }
+
+    fn for_mode(&self, mode: ConnectionMode) -> &AtomicUsize {
+        match mode {
+            ConnectionMode::Grpc => &self.grpc,
+            ConnectionMode::Rpc => &self.rpc,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EndpointHealth {
+    pub endpoint: String,
+    /// None if the endpoint couldn't be reached
+    pub block_height: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl QueryClient {
+    /// All the endpoints for the current connection mode, in priority order
+    pub fn endpoints(&self) -> Vec<&str> {
+        match self.get_connection_mode() {
+            ConnectionMode::Grpc => self.grpc_endpoints(),
+            ConnectionMode::Rpc => self
+                ._rpc_clients
+                .iter()
+                .map(|client| client.url())
+                .collect(),
+        }
+    }
+
+    /// The endpoint queries are currently sent to, for the current connection mode
+    pub fn active_endpoint(&self) -> Option<&str> {
+        let index = self.active_endpoint_index();
+        self.endpoints().get(index).copied()
+    }
+
+    pub fn active_endpoint_index(&self) -> usize {
+        self._active_endpoints
+            .for_mode(self.get_connection_mode())
+            .load(Ordering::SeqCst)
+    }
+
+    /// Switches to another endpoint for the current connection mode (for this client and all its clones)
+    pub fn set_active_endpoint(&self, index: usize) -> Result<()> {
+        let len = self.endpoints().len();
+        if index >= len {
+            bail!("endpoint index {index} is out of range, there are {len} endpoints");
+        }
+
+        self._active_endpoints
+            .for_mode(self.get_connection_mode())
+            .store(index, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    // moves on to the next endpoint, unless someone else already moved away from `index`
+    // returns the endpoint that's now active
+    pub(crate) fn failover_from(&self, index: usize) -> Option<&str> {
+        let endpoints = self.endpoints();
+        if endpoints.len() > 1 {
+            let _ = self
+                ._active_endpoints
+                .for_mode(self.get_connection_mode())
+                .compare_exchange(
+                    index,
+                    (index + 1) % endpoints.len(),
+                    Ordering::SeqCst,
+                    Ordering::SeqCst,
+                );
+        }
+
+        endpoints.get(self.active_endpoint_index()).copied()
+    }
+
+    /// Queries the block height from every endpoint of the current connection mode
+    /// and switches to the first one (in priority order) that's reachable and at most `max_block_lag` blocks
+    /// behind the highest, if that isn't the active one already
+    pub async fn check_endpoints(&self, max_block_lag: u64) -> Vec<EndpointHealth> {
+        let mode = self.get_connection_mode();
+        let endpoints = self.endpoints();
+
+        let heights = futures::future::join_all((0..endpoints.len()).map(|index| {
+            let mut client = self.clone();
+            client._active_endpoints = ActiveEndpoints::pinned(mode, index);
+            async move { BlockHeightReq {}.request(client).await }
+        }))
+        .await;
+
+        let health: Vec<EndpointHealth> = endpoints
+            .iter()
+            .zip(heights)
+            .map(|(endpoint, height)| match height {
+                Ok(height) => EndpointHealth {
+                    endpoint: endpoint.to_string(),
+                    block_height: Some(height),
+                    error: None,
+                },
+                Err(err) => EndpointHealth {
+                    endpoint: endpoint.to_string(),
+                    block_height: None,
+                    error: Some(err.to_string()),
+                },
+            })
+            .collect();
+
+        let heights: Vec<Option<u64>> = health.iter().map(|h| h.block_height).collect();
+
+        let Some(healthy) = healthy_endpoint(&heights, max_block_lag) else {
+            tracing::warn!(
+                "none of the {mode} endpoints for {} are reachable",
+                self.chain_config.chain_id
+            );
+            return health;
+        };
+
+        let active = self.active_endpoint_index();
+        if healthy != active {
+            tracing::warn!(
+                from = endpoints.get(active).copied(),
+                to = endpoints[healthy],
+                "switching {mode} endpoint"
+            );
+            self._active_endpoints
+                .for_mode(mode)
+                .store(healthy, Ordering::SeqCst);
+        }
+
+        health
+    }
+}
+
+// the first endpoint (in priority order) that's reachable and at most `max_block_lag` blocks behind the highest
+// None if none of them are reachable
+fn healthy_endpoint(heights: &[Option<u64>], max_block_lag: u64) -> Option<usize> {
+    let highest = heights.iter().flatten().max()?;
+
+    heights.iter().position(|height| {
+        height.is_some_and(|height| height.saturating_add(max_block_lag) >= *highest)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn healthy_endpoint_prefers_priority_order() {
+        assert_eq!(healthy_endpoint(&[Some(100), Some(100)], 5), Some(0));
+        assert_eq!(
+            healthy_endpoint(&[Some(98), Some(100), Some(100)], 5),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn healthy_endpoint_skips_lagging() {
+        // 6 behind with a max lag of 5
+        assert_eq!(healthy_endpoint(&[Some(94), Some(100)], 5), Some(1));
+        // exactly at the max lag is fine
+        assert_eq!(healthy_endpoint(&[Some(95), Some(100)], 5), Some(0));
+        assert_eq!(
+            healthy_endpoint(&[Some(10), Some(99), Some(100)], 0),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn healthy_endpoint_skips_unreachable() {
+        assert_eq!(healthy_endpoint(&[None, Some(50), Some(100)], 5), Some(2));
+        assert_eq!(healthy_endpoint(&[None, Some(100)], 5), Some(1));
+        assert_eq!(healthy_endpoint(&[None, None], 5), None);
+        assert_eq!(healthy_endpoint(&[], 5), None);
+    }
+
+    #[test]
+    fn healthy_endpoint_large_lag() {
+        assert_eq!(healthy_endpoint(&[Some(1), Some(100)], u64::MAX), Some(0));
+    }
+}
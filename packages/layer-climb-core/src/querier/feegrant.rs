@@ -0,0 +1,328 @@
+use tracing::instrument;
+
+use crate::prelude::*;
+
+use futures::TryStreamExt;
+
+use super::{
+    pagination::{paginate, Page, PaginatedResponse, PaginationOptions},
+    ConnectionMode,
+};
+
+impl QueryClient {
+    /// The allowance that `granter` gave to `grantee`, if any
+    #[instrument]
+    pub async fn feegrant_allowance(
+        &self,
+        granter: Address,
+        grantee: Address,
+    ) -> Result<Option<layer_climb_proto::feegrant::Grant>> {
+        self.run_with_middleware(FeegrantAllowanceReq { granter, grantee })
+            .await
+    }
+
+    /// All the allowances given to `grantee`
+    #[instrument]
+    pub async fn feegrant_allowances(
+        &self,
+        grantee: Address,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<layer_climb_proto::feegrant::Grant>> {
+        self.run_with_middleware(FeegrantAllowancesReq {
+            grantee,
+            limit_per_page,
+        })
+        .await
+    }
+
+    /// All the allowances given by `granter`
+    #[instrument]
+    pub async fn feegrant_allowances_by_granter(
+        &self,
+        granter: Address,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<layer_climb_proto::feegrant::Grant>> {
+        self.run_with_middleware(FeegrantAllowancesByGranterReq {
+            granter,
+            limit_per_page,
+        })
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FeegrantAllowanceReq {
+    pub granter: Address,
+    pub grantee: Address,
+}
+
+impl QueryRequest for FeegrantAllowanceReq {
+    type QueryResponse = Option<layer_climb_proto::feegrant::Grant>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::feegrant::QueryAllowanceRequest {
+            granter: self.granter.to_string(),
+            grantee: self.grantee.to_string(),
+        };
+
+        let res = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client = layer_climb_proto::feegrant::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                );
+
+                query_client
+                    .allowance(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner().allowance)
+                    .map_err(|err| anyhow!(err))
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::feegrant::QueryAllowanceResponse>(
+                    "/cosmos.feegrant.v1beta1.Query/Allowance",
+                    req,
+                    client.query_height(),
+                )
+                .await
+                .map(|res| res.allowance),
+        };
+
+        // a missing allowance is an error on chain ("fee-grant not found"), but a None here
+        match res {
+            Ok(allowance) => Ok(allowance),
+            Err(err) if format!("{err:?}").contains("not found") => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FeegrantAllowancesReq {
+    pub grantee: Address,
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for FeegrantAllowancesReq {
+    type QueryResponse = Vec<layer_climb_proto::feegrant::Grant>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = FeegrantAllowancesPageReq {
+                grantee: self.grantee.clone(),
+                pagination,
+            };
+            async move { req.request(client).await }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FeegrantAllowancesPageReq {
+    pub grantee: Address,
+    pub pagination: layer_climb_proto::query::PageRequest,
+}
+
+impl QueryRequest for FeegrantAllowancesPageReq {
+    type QueryResponse = Page<layer_climb_proto::feegrant::Grant>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::feegrant::QueryAllowancesRequest {
+            grantee: self.grantee.to_string(),
+            pagination: Some(self.pagination.clone()),
+        };
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => layer_climb_proto::feegrant::query_client::QueryClient::new(
+                client.clone_grpc_channel()?,
+            )
+            .allowances(client.grpc_request(req)?)
+            .await
+            .map(|res| res.into_inner())?,
+            ConnectionMode::Rpc => {
+                client
+                    .rpc_client()?
+                    .abci_protobuf_query::<_, layer_climb_proto::feegrant::QueryAllowancesResponse>(
+                        "/cosmos.feegrant.v1beta1.Query/Allowances",
+                        req,
+                        client.query_height(),
+                    )
+                    .await?
+            }
+        };
+
+        Ok(resp.into_page())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FeegrantAllowancesByGranterReq {
+    pub granter: Address,
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for FeegrantAllowancesByGranterReq {
+    type QueryResponse = Vec<layer_climb_proto::feegrant::Grant>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = FeegrantAllowancesByGranterPageReq {
+                granter: self.granter.clone(),
+                pagination,
+            };
+            async move { req.request(client).await }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FeegrantAllowancesByGranterPageReq {
+    pub granter: Address,
+    pub pagination: layer_climb_proto::query::PageRequest,
+}
+
+impl QueryRequest for FeegrantAllowancesByGranterPageReq {
+    type QueryResponse = Page<layer_climb_proto::feegrant::Grant>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::feegrant::QueryAllowancesByGranterRequest {
+            granter: self.granter.to_string(),
+            pagination: Some(self.pagination.clone()),
+        };
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                layer_climb_proto::feegrant::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                )
+                .allowances_by_granter(client.grpc_request(req)?)
+                .await
+                .map(|res| res.into_inner())?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::feegrant::QueryAllowancesByGranterResponse>(
+                    "/cosmos.feegrant.v1beta1.Query/AllowancesByGranter",
+                    req,
+                    client.query_height(),
+                )
+                .await?,
+        };
+
+        Ok(resp.into_page())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use layer_climb_proto::feegrant::{
+        Grant, QueryAllowanceRequest, QueryAllowanceResponse, QueryAllowancesRequest,
+        QueryAllowancesResponse,
+    };
+
+    use super::*;
+    use crate::{
+        network::rpc::AbciQueryError,
+        testing::{address, not_found, query_client},
+    };
+
+    fn grant(granter: u32, grantee: u32) -> Grant {
+        Grant {
+            granter: address(granter).to_string(),
+            grantee: address(grantee).to_string(),
+            allowance: Some(
+                proto_into_any(&layer_climb_proto::feegrant::BasicAllowance::default()).unwrap(),
+            ),
+        }
+    }
+
+    #[test]
+    fn allowance() {
+        let client = query_client(|path, data, _| {
+            assert_eq!(path, "/cosmos.feegrant.v1beta1.Query/Allowance");
+            let req = QueryAllowanceRequest::decode(data).unwrap();
+            assert_eq!(req.grantee, address(2).to_string());
+
+            match req.granter == address(1).to_string() {
+                true => Ok(QueryAllowanceResponse {
+                    allowance: Some(grant(1, 2)),
+                }
+                .encode_to_vec()),
+                false => Err(not_found(&format!(
+                    "fee-grant not found: granter: {}, grantee: {}: not found",
+                    req.granter, req.grantee
+                ))),
+            }
+        });
+
+        let allowance = block_on(client.feegrant_allowance(address(1), address(2))).unwrap();
+        assert_eq!(allowance, Some(grant(1, 2)));
+
+        // a missing grant is None rather than an error
+        let allowance = block_on(client.feegrant_allowance(address(3), address(2))).unwrap();
+        assert_eq!(allowance, None);
+    }
+
+    #[test]
+    fn allowance_error() {
+        let client = query_client(|_, _, _| {
+            Err(AbciQueryError {
+                codespace: "sdk".to_string(),
+                code: 7,
+                log: "invalid address".to_string(),
+            })
+        });
+
+        assert!(block_on(client.feegrant_allowance(address(1), address(2))).is_err());
+    }
+
+    #[test]
+    fn allowances_all_pages() {
+        let client = query_client(|path, data, height| {
+            assert_eq!(path, "/cosmos.feegrant.v1beta1.Query/Allowances");
+            assert_eq!(height, Some(crate::testing::HEIGHT));
+
+            let req = QueryAllowancesRequest::decode(data).unwrap();
+            assert_eq!(req.grantee, address(0).to_string());
+            let pagination = req.pagination.unwrap();
+            assert_eq!(pagination.limit, 2);
+
+            // granters 1..=3, two per page
+            let (granters, next_key) = match pagination.key.as_slice() {
+                [] => (vec![1, 2], vec![3]),
+                [3] => (vec![3], Vec::new()),
+                key => panic!("unexpected key {key:?}"),
+            };
+
+            Ok(QueryAllowancesResponse {
+                allowances: granters.into_iter().map(|n| grant(n, 0)).collect(),
+                pagination: Some(layer_climb_proto::query::PageResponse { next_key, total: 0 }),
+            }
+            .encode_to_vec())
+        });
+
+        let allowances = block_on(
+            client
+                .at_height(crate::testing::HEIGHT)
+                .feegrant_allowances(address(0), Some(2)),
+        )
+        .unwrap();
+
+        assert_eq!(allowances, vec![grant(1, 0), grant(2, 0), grant(3, 0)]);
+    }
+}
@@ -0,0 +1,213 @@
+use std::sync::{Arc, Mutex};
+
+use tracing::instrument;
+
+use crate::prelude::*;
+
+use super::ConnectionMode;
+
+/// Where the gas price comes from when a TxBuilder calculates the fee
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum GasPriceSource {
+    /// `chain_config.gas_price`, no network calls
+    #[default]
+    Static,
+    /// Skip's x/feemarket (e.g. Neutron)
+    Feemarket,
+    /// Osmosis' EIP-1559 base fee from x/txfees
+    OsmosisEip1559,
+}
+
+/// Lives on the QueryClient, the price from a dynamic source is cached for the block it was queried at
+/// (keyed by height like the query cache, the height is looked up on every call)
+/// if the dynamic source fails, it falls back to `chain_config.gas_price`
+#[derive(Clone, Debug, Default)]
+pub struct GasPriceOracle {
+    pub source: GasPriceSource,
+    // (block height, gas price)
+    cache: Arc<Mutex<Option<(u64, f32)>>>,
+}
+
+impl GasPriceOracle {
+    pub fn new(source: GasPriceSource) -> Self {
+        Self {
+            source,
+            cache: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn cached(&self, height: u64) -> Option<f32> {
+        match *self.cache.lock().unwrap() {
+            Some((cached_height, gas_price)) if cached_height == height => Some(gas_price),
+            _ => None,
+        }
+    }
+}
+
+impl QueryClient {
+    /// The current gas price in `chain_config.gas_denom`, according to `gas_price_oracle`
+    #[instrument]
+    pub async fn gas_price(&self) -> Result<f32> {
+        if self.gas_price_oracle.source == GasPriceSource::Static {
+            return Ok(self.chain_config.gas_price);
+        }
+
+        match self.dynamic_gas_price().await {
+            Ok(gas_price) => Ok(gas_price),
+            Err(err) => {
+                tracing::warn!(
+                    "unable to get gas price from {:?}, falling back to static gas price: {err:?}",
+                    self.gas_price_oracle.source
+                );
+                Ok(self.chain_config.gas_price)
+            }
+        }
+    }
+
+    async fn dynamic_gas_price(&self) -> Result<f32> {
+        let height = self.block_height().await?;
+
+        if let Some(gas_price) = self.gas_price_oracle.cached(height) {
+            return Ok(gas_price);
+        }
+
+        let gas_price = match self.gas_price_oracle.source {
+            GasPriceSource::Static => self.chain_config.gas_price,
+            GasPriceSource::Feemarket => {
+                self.feemarket_gas_price(self.chain_config.gas_denom.clone())
+                    .await?
+            }
+            GasPriceSource::OsmosisEip1559 => self.osmosis_eip_base_fee().await?,
+        };
+
+        *self.gas_price_oracle.cache.lock().unwrap() = Some((height, gas_price));
+
+        Ok(gas_price)
+    }
+
+    #[instrument]
+    pub async fn feemarket_gas_price(&self, denom: String) -> Result<f32> {
+        self.run_with_middleware(FeemarketGasPriceReq { denom })
+            .await
+    }
+
+    #[instrument]
+    pub async fn osmosis_eip_base_fee(&self) -> Result<f32> {
+        self.run_with_middleware(OsmosisEipBaseFeeReq {}).await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FeemarketGasPriceReq {
+    pub denom: String,
+}
+
+impl QueryRequest for FeemarketGasPriceReq {
+    type QueryResponse = f32;
+
+    async fn request(&self, client: QueryClient) -> Result<f32> {
+        let req = layer_climb_proto::feemarket::GasPriceRequest {
+            denom: self.denom.clone(),
+        };
+
+        let price = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client = layer_climb_proto::feemarket::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                );
+
+                query_client
+                    .gas_price(req)
+                    .await
+                    .map(|res| res.into_inner().price)?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::feemarket::GasPriceResponse>(
+                    "/feemarket.feemarket.v1.Query/GasPrice",
+                    req,
+                    None,
+                )
+                .await
+                .map(|res| res.price)?,
+        };
+
+        let price = price.context("no gas price in feemarket response")?;
+
+        if price.denom != self.denom {
+            bail!(
+                "feemarket gas price is in {}, expected {}",
+                price.denom,
+                self.denom
+            );
+        }
+
+        parse_dec(&price.amount)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OsmosisEipBaseFeeReq {}
+
+impl QueryRequest for OsmosisEipBaseFeeReq {
+    type QueryResponse = f32;
+
+    async fn request(&self, client: QueryClient) -> Result<f32> {
+        let req = layer_climb_proto::osmosis_txfees::QueryEipBaseFeeRequest {};
+
+        let base_fee = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client =
+                    layer_climb_proto::osmosis_txfees::query_client::QueryClient::new(
+                        client.clone_grpc_channel()?,
+                    );
+
+                query_client
+                    .get_eip_base_fee(req)
+                    .await
+                    .map(|res| res.into_inner().base_fee)?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::osmosis_txfees::QueryEipBaseFeeResponse>(
+                    "/osmosis.txfees.v1beta1.Query/GetEipBaseFee",
+                    req,
+                    None,
+                )
+                .await
+                .map(|res| res.base_fee)?,
+        };
+
+        parse_dec(&base_fee)
+    }
+}
+
+// sdk Decs are sent over protobuf as integers with 18 decimal places, but be lenient in case it's already formatted
+fn parse_dec(value: &str) -> Result<f32> {
+    if value.contains('.') {
+        return value
+            .parse::<f32>()
+            .with_context(|| format!("invalid decimal: {value}"));
+    }
+
+    let value = value
+        .parse::<f64>()
+        .with_context(|| format!("invalid decimal: {value}"))?;
+
+    Ok((value / 1e18) as f32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cache_until_next_block() {
+        let oracle = GasPriceOracle::new(GasPriceSource::Feemarket);
+        assert_eq!(oracle.cached(10), None);
+
+        *oracle.cache.lock().unwrap() = Some((10, 0.025));
+        assert_eq!(oracle.cached(10), Some(0.025));
+        assert_eq!(oracle.cached(11), None);
+    }
+}
@@ -0,0 +1,900 @@
+use std::{str::FromStr, sync::atomic::Ordering};
+
+use cosmwasm_std::{Decimal, Uint128};
+use futures::{Stream, TryStreamExt};
+use layer_climb_proto::{
+    gov::{v1, v1beta1},
+    Message,
+};
+use tracing::instrument;
+
+use crate::prelude::*;
+
+use super::{
+    middleware::cache::QueryCachePolicy,
+    pagination::{paginate, Page, PaginatedResponse, PaginationOptions},
+};
+
+/// The version of the gov module a node serves, see `QueryClient::gov_version()`
+///
+/// Queries always return the v1 types, v1beta1 responses are converted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GovVersion {
+    /// cosmos-sdk 0.46 and later
+    V1,
+    V1Beta1,
+}
+
+// 0 means not detected yet
+impl From<GovVersion> for u8 {
+    fn from(version: GovVersion) -> u8 {
+        match version {
+            GovVersion::V1 => 1,
+            GovVersion::V1Beta1 => 2,
+        }
+    }
+}
+
+impl QueryClient {
+    /// The gov version of the node, detected on first use and shared by all clones of this client
+    pub async fn gov_version(&self) -> Result<GovVersion> {
+        loop {
+            match self._gov_version.load(Ordering::SeqCst) {
+                1 => return Ok(GovVersion::V1),
+                2 => return Ok(GovVersion::V1Beta1),
+                _ => self.set_gov_version(None).await?,
+            }
+        }
+    }
+
+    // if None, will detect it by trying the v1 params query and falling back to v1beta1 if the node doesn't serve it
+    // any other error is returned as-is, and nothing is stored
+    #[instrument]
+    pub async fn set_gov_version(&self, version: Option<GovVersion>) -> Result<()> {
+        let version = match version {
+            Some(version) => version,
+            None => {
+                self.at_latest()
+                    .run_with_middleware(GovVersionReq {})
+                    .await?
+            }
+        };
+
+        self._gov_version.store(version.into(), Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    /// All the proposals, optionally only those with the given status
+    #[instrument]
+    pub async fn gov_proposals(
+        &self,
+        status: Option<v1::ProposalStatus>,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<v1::Proposal>> {
+        self.run_with_middleware(GovProposalsReq {
+            status,
+            limit_per_page,
+        })
+        .await
+    }
+
+    /// Like `gov_proposals()`, but as a stream that fetches pages as it goes
+    /// e.g. with `reverse: true` to get the most recent proposals first
+    pub fn stream_gov_proposals(
+        &self,
+        status: Option<v1::ProposalStatus>,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<v1::Proposal>> {
+        paginate(self.clone(), options, move |client, pagination| {
+            let req = GovProposalsPageReq { status, pagination };
+            async move { client.run_with_middleware(req).await }
+        })
+    }
+
+    #[instrument]
+    pub async fn gov_proposal(&self, proposal_id: u64) -> Result<v1::Proposal> {
+        self.run_with_middleware(GovProposalReq { proposal_id })
+            .await
+    }
+
+    #[instrument]
+    pub async fn gov_votes(
+        &self,
+        proposal_id: u64,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<v1::Vote>> {
+        self.run_with_middleware(GovVotesReq {
+            proposal_id,
+            limit_per_page,
+        })
+        .await
+    }
+
+    #[instrument]
+    pub async fn gov_deposits(
+        &self,
+        proposal_id: u64,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<v1::Deposit>> {
+        self.run_with_middleware(GovDepositsReq {
+            proposal_id,
+            limit_per_page,
+        })
+        .await
+    }
+
+    /// The current tally while voting is open, the final one after
+    #[instrument]
+    pub async fn gov_tally(&self, proposal_id: u64) -> Result<v1::TallyResult> {
+        self.run_with_middleware(GovTallyReq { proposal_id }).await
+    }
+
+    /// For v1beta1 nodes (and early v1 ones) only the deposit, voting and tally params are filled in
+    #[instrument]
+    pub async fn gov_params(&self) -> Result<v1::Params> {
+        self.run_with_middleware(GovParamsReq {}).await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GovProposalsReq {
+    /// if None, proposals of any status
+    pub status: Option<v1::ProposalStatus>,
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for GovProposalsReq {
+    type QueryResponse = Vec<v1::Proposal>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = GovProposalsPageReq {
+                status: self.status,
+                pagination,
+            };
+            async move { req.request(client).await }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GovProposalsPageReq {
+    /// if None, proposals of any status
+    pub status: Option<v1::ProposalStatus>,
+    pub pagination: layer_climb_proto::query::PageRequest,
+}
+
+impl QueryRequest for GovProposalsPageReq {
+    type QueryResponse = Page<v1::Proposal>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        // the status values are the same in both versions
+        let proposal_status = self.status.map(|status| status as i32).unwrap_or_default();
+
+        match client.gov_version().await? {
+            GovVersion::V1 => {
+                let req = v1::QueryProposalsRequest {
+                    proposal_status,
+                    voter: String::new(),
+                    depositor: String::new(),
+                    pagination: Some(self.pagination.clone()),
+                };
+
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            v1::query_client::QueryClient::new(client.clone_grpc_channel()?);
+
+                        query_client
+                            .proposals(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .context("couldn't get proposals")?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, v1::QueryProposalsResponse>(
+                            "/cosmos.gov.v1.Query/Proposals",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .context("couldn't get proposals")?,
+                };
+
+                Ok(resp.into_page())
+            }
+            GovVersion::V1Beta1 => {
+                let req = v1beta1::QueryProposalsRequest {
+                    proposal_status,
+                    voter: String::new(),
+                    depositor: String::new(),
+                    pagination: Some(self.pagination.clone()),
+                };
+
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            v1beta1::query_client::QueryClient::new(client.clone_grpc_channel()?);
+
+                        query_client
+                            .proposals(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .context("couldn't get proposals")?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, v1beta1::QueryProposalsResponse>(
+                            "/cosmos.gov.v1beta1.Query/Proposals",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .context("couldn't get proposals")?,
+                };
+
+                Ok(Page::new(
+                    resp.proposals
+                        .into_iter()
+                        .map(proposal_from_v1beta1)
+                        .collect(),
+                    resp.pagination,
+                ))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GovProposalReq {
+    pub proposal_id: u64,
+}
+
+impl QueryRequest for GovProposalReq {
+    type QueryResponse = v1::Proposal;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let proposal = match client.gov_version().await? {
+            GovVersion::V1 => {
+                let req = v1::QueryProposalRequest {
+                    proposal_id: self.proposal_id,
+                };
+
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            v1::query_client::QueryClient::new(client.clone_grpc_channel()?);
+
+                        query_client
+                            .proposal(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .with_context(|| {
+                                format!("couldn't get proposal {}", self.proposal_id)
+                            })?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, v1::QueryProposalResponse>(
+                            "/cosmos.gov.v1.Query/Proposal",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .with_context(|| format!("couldn't get proposal {}", self.proposal_id))?,
+                };
+
+                resp.proposal
+            }
+            GovVersion::V1Beta1 => {
+                let req = v1beta1::QueryProposalRequest {
+                    proposal_id: self.proposal_id,
+                };
+
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            v1beta1::query_client::QueryClient::new(client.clone_grpc_channel()?);
+
+                        query_client
+                            .proposal(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .with_context(|| {
+                                format!("couldn't get proposal {}", self.proposal_id)
+                            })?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, v1beta1::QueryProposalResponse>(
+                            "/cosmos.gov.v1beta1.Query/Proposal",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .with_context(|| format!("couldn't get proposal {}", self.proposal_id))?,
+                };
+
+                resp.proposal.map(proposal_from_v1beta1)
+            }
+        };
+
+        proposal.ok_or_else(|| anyhow!("proposal {} not found", self.proposal_id))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GovVotesReq {
+    pub proposal_id: u64,
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for GovVotesReq {
+    type QueryResponse = Vec<v1::Vote>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = GovVotesPageReq {
+                proposal_id: self.proposal_id,
+                pagination,
+            };
+            async move { req.request(client).await }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GovVotesPageReq {
+    pub proposal_id: u64,
+    pub pagination: layer_climb_proto::query::PageRequest,
+}
+
+impl QueryRequest for GovVotesPageReq {
+    type QueryResponse = Page<v1::Vote>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        match client.gov_version().await? {
+            GovVersion::V1 => {
+                let req = v1::QueryVotesRequest {
+                    proposal_id: self.proposal_id,
+                    pagination: Some(self.pagination.clone()),
+                };
+
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            v1::query_client::QueryClient::new(client.clone_grpc_channel()?);
+
+                        query_client
+                            .votes(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .context("couldn't get votes")?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, v1::QueryVotesResponse>(
+                            "/cosmos.gov.v1.Query/Votes",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .context("couldn't get votes")?,
+                };
+
+                Ok(resp.into_page())
+            }
+            GovVersion::V1Beta1 => {
+                let req = v1beta1::QueryVotesRequest {
+                    proposal_id: self.proposal_id,
+                    pagination: Some(self.pagination.clone()),
+                };
+
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            v1beta1::query_client::QueryClient::new(client.clone_grpc_channel()?);
+
+                        query_client
+                            .votes(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .context("couldn't get votes")?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, v1beta1::QueryVotesResponse>(
+                            "/cosmos.gov.v1beta1.Query/Votes",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .context("couldn't get votes")?,
+                };
+
+                Ok(Page::new(
+                    resp.votes
+                        .into_iter()
+                        .map(vote_from_v1beta1)
+                        .collect::<Result<_>>()?,
+                    resp.pagination,
+                ))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GovDepositsReq {
+    pub proposal_id: u64,
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for GovDepositsReq {
+    type QueryResponse = Vec<v1::Deposit>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = GovDepositsPageReq {
+                proposal_id: self.proposal_id,
+                pagination,
+            };
+            async move { req.request(client).await }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GovDepositsPageReq {
+    pub proposal_id: u64,
+    pub pagination: layer_climb_proto::query::PageRequest,
+}
+
+impl QueryRequest for GovDepositsPageReq {
+    type QueryResponse = Page<v1::Deposit>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        match client.gov_version().await? {
+            GovVersion::V1 => {
+                let req = v1::QueryDepositsRequest {
+                    proposal_id: self.proposal_id,
+                    pagination: Some(self.pagination.clone()),
+                };
+
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            v1::query_client::QueryClient::new(client.clone_grpc_channel()?);
+
+                        query_client
+                            .deposits(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .context("couldn't get deposits")?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, v1::QueryDepositsResponse>(
+                            "/cosmos.gov.v1.Query/Deposits",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .context("couldn't get deposits")?,
+                };
+
+                Ok(resp.into_page())
+            }
+            GovVersion::V1Beta1 => {
+                let req = v1beta1::QueryDepositsRequest {
+                    proposal_id: self.proposal_id,
+                    pagination: Some(self.pagination.clone()),
+                };
+
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            v1beta1::query_client::QueryClient::new(client.clone_grpc_channel()?);
+
+                        query_client
+                            .deposits(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .context("couldn't get deposits")?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, v1beta1::QueryDepositsResponse>(
+                            "/cosmos.gov.v1beta1.Query/Deposits",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .context("couldn't get deposits")?,
+                };
+
+                Ok(Page::new(
+                    resp.deposits
+                        .into_iter()
+                        .map(|deposit| v1::Deposit {
+                            proposal_id: deposit.proposal_id,
+                            depositor: deposit.depositor,
+                            amount: deposit.amount,
+                        })
+                        .collect(),
+                    resp.pagination,
+                ))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GovTallyReq {
+    pub proposal_id: u64,
+}
+
+impl QueryRequest for GovTallyReq {
+    type QueryResponse = v1::TallyResult;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let tally = match client.gov_version().await? {
+            GovVersion::V1 => {
+                let req = v1::QueryTallyResultRequest {
+                    proposal_id: self.proposal_id,
+                };
+
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            v1::query_client::QueryClient::new(client.clone_grpc_channel()?);
+
+                        query_client
+                            .tally_result(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .context("couldn't get tally")?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, v1::QueryTallyResultResponse>(
+                            "/cosmos.gov.v1.Query/TallyResult",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .context("couldn't get tally")?,
+                };
+
+                resp.tally
+            }
+            GovVersion::V1Beta1 => {
+                let req = v1beta1::QueryTallyResultRequest {
+                    proposal_id: self.proposal_id,
+                };
+
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            v1beta1::query_client::QueryClient::new(client.clone_grpc_channel()?);
+
+                        query_client
+                            .tally_result(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .context("couldn't get tally")?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, v1beta1::QueryTallyResultResponse>(
+                            "/cosmos.gov.v1beta1.Query/TallyResult",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .context("couldn't get tally")?,
+                };
+
+                resp.tally.map(tally_from_v1beta1)
+            }
+        };
+
+        tally.ok_or_else(|| anyhow!("no tally for proposal {}", self.proposal_id))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GovVersionReq {}
+
+impl QueryRequest for GovVersionReq {
+    type QueryResponse = GovVersion;
+
+    // the result is kept by the client itself, see `QueryClient::gov_version()`
+    fn cache_policy(&self) -> QueryCachePolicy {
+        QueryCachePolicy::Never
+    }
+
+    async fn request(&self, client: QueryClient) -> Result<GovVersion> {
+        match gov_v1_params(&client, "deposit").await {
+            Ok(_) => Ok(GovVersion::V1),
+            Err(err) if is_unimplemented(&err) => {
+                gov_v1beta1_params(&client, "deposit")
+                    .await
+                    .with_context(|| {
+                        format!("couldn't detect the gov version, v1 failed with: {err:?}")
+                    })?;
+                Ok(GovVersion::V1Beta1)
+            }
+            Err(err) => Err(err.context("couldn't detect the gov version")),
+        }
+    }
+}
+
+// the node doesn't serve the query at all, as opposed to the query failing
+// grpc says so with a status code, rpc abci queries only in the message
+fn is_unimplemented(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<tonic::Status>()
+            .is_some_and(|status| status.code() == tonic::Code::Unimplemented)
+    }) || format!("{err:?}").contains("unknown query path")
+}
+
+#[derive(Clone, Debug)]
+pub struct GovParamsReq {}
+
+impl QueryRequest for GovParamsReq {
+    type QueryResponse = v1::Params;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        match client.gov_version().await? {
+            GovVersion::V1 => {
+                let deposit = gov_v1_params(&client, "deposit").await?;
+
+                // later versions fill in all the params regardless of the type asked for
+                if let Some(params) = deposit.params {
+                    return Ok(params);
+                }
+
+                // 0.46 only has the deprecated split params
+                #[allow(deprecated)]
+                let (deposit, voting, tally) = (
+                    deposit.deposit_params.unwrap_or_default(),
+                    gov_v1_params(&client, "voting")
+                        .await?
+                        .voting_params
+                        .unwrap_or_default(),
+                    gov_v1_params(&client, "tallying")
+                        .await?
+                        .tally_params
+                        .unwrap_or_default(),
+                );
+
+                Ok(v1::Params {
+                    min_deposit: deposit.min_deposit,
+                    max_deposit_period: deposit.max_deposit_period,
+                    voting_period: voting.voting_period,
+                    quorum: tally.quorum,
+                    threshold: tally.threshold,
+                    veto_threshold: tally.veto_threshold,
+                    ..Default::default()
+                })
+            }
+            GovVersion::V1Beta1 => {
+                let deposit = gov_v1beta1_params(&client, "deposit")
+                    .await?
+                    .deposit_params
+                    .unwrap_or_default();
+                let voting = gov_v1beta1_params(&client, "voting")
+                    .await?
+                    .voting_params
+                    .unwrap_or_default();
+                let tally = gov_v1beta1_params(&client, "tallying")
+                    .await?
+                    .tally_params
+                    .unwrap_or_default();
+
+                Ok(v1::Params {
+                    min_deposit: deposit.min_deposit,
+                    max_deposit_period: deposit.max_deposit_period,
+                    voting_period: voting.voting_period,
+                    quorum: legacy_dec_bytes_to_string(tally.quorum)?,
+                    threshold: legacy_dec_bytes_to_string(tally.threshold)?,
+                    veto_threshold: legacy_dec_bytes_to_string(tally.veto_threshold)?,
+                    ..Default::default()
+                })
+            }
+        }
+    }
+}
+
+// params_type is one of "deposit", "voting" or "tallying"
+async fn gov_v1_params(client: &QueryClient, params_type: &str) -> Result<v1::QueryParamsResponse> {
+    let req = v1::QueryParamsRequest {
+        params_type: params_type.to_string(),
+    };
+
+    match client.get_connection_mode() {
+        ConnectionMode::Grpc => {
+            let mut query_client = v1::query_client::QueryClient::new(client.clone_grpc_channel()?);
+
+            query_client
+                .params(client.grpc_request(req)?)
+                .await
+                .map(|res| res.into_inner())
+                .context("couldn't get gov params")
+        }
+        ConnectionMode::Rpc => client
+            .rpc_client()?
+            .abci_protobuf_query::<_, v1::QueryParamsResponse>(
+                "/cosmos.gov.v1.Query/Params",
+                req,
+                client.query_height(),
+            )
+            .await
+            .context("couldn't get gov params"),
+    }
+}
+
+async fn gov_v1beta1_params(
+    client: &QueryClient,
+    params_type: &str,
+) -> Result<v1beta1::QueryParamsResponse> {
+    let req = v1beta1::QueryParamsRequest {
+        params_type: params_type.to_string(),
+    };
+
+    match client.get_connection_mode() {
+        ConnectionMode::Grpc => {
+            let mut query_client =
+                v1beta1::query_client::QueryClient::new(client.clone_grpc_channel()?);
+
+            query_client
+                .params(client.grpc_request(req)?)
+                .await
+                .map(|res| res.into_inner())
+                .context("couldn't get gov params")
+        }
+        ConnectionMode::Rpc => client
+            .rpc_client()?
+            .abci_protobuf_query::<_, v1beta1::QueryParamsResponse>(
+                "/cosmos.gov.v1beta1.Query/Params",
+                req,
+                client.query_height(),
+            )
+            .await
+            .context("couldn't get gov params"),
+    }
+}
+
+// the legacy content is kept as the only message
+// every legacy content type starts with the same title and description fields as `TextProposal`, so those are filled in too
+fn proposal_from_v1beta1(proposal: v1beta1::Proposal) -> v1::Proposal {
+    let text = proposal
+        .content
+        .as_ref()
+        .and_then(|content| v1beta1::TextProposal::decode(content.value.as_slice()).ok())
+        .unwrap_or_default();
+
+    v1::Proposal {
+        id: proposal.proposal_id,
+        messages: proposal.content.into_iter().collect(),
+        status: proposal.status,
+        final_tally_result: proposal.final_tally_result.map(tally_from_v1beta1),
+        submit_time: proposal.submit_time,
+        deposit_end_time: proposal.deposit_end_time,
+        total_deposit: proposal.total_deposit,
+        voting_start_time: proposal.voting_start_time,
+        voting_end_time: proposal.voting_end_time,
+        title: text.title,
+        summary: text.description,
+        ..Default::default()
+    }
+}
+
+fn tally_from_v1beta1(tally: v1beta1::TallyResult) -> v1::TallyResult {
+    v1::TallyResult {
+        yes_count: tally.yes,
+        abstain_count: tally.abstain,
+        no_count: tally.no,
+        no_with_veto_count: tally.no_with_veto,
+    }
+}
+
+fn vote_from_v1beta1(vote: v1beta1::Vote) -> Result<v1::Vote> {
+    #[allow(deprecated)]
+    let options = match vote.options.is_empty() {
+        // votes from before weighted voting only have the single option
+        true => vec![v1::WeightedVoteOption {
+            option: vote.option,
+            weight: Decimal::one().to_string(),
+        }],
+        false => vote
+            .options
+            .into_iter()
+            .map(|option| {
+                Ok(v1::WeightedVoteOption {
+                    option: option.option,
+                    weight: legacy_dec_to_string(&option.weight)?,
+                })
+            })
+            .collect::<Result<_>>()?,
+    };
+
+    Ok(v1::Vote {
+        proposal_id: vote.proposal_id,
+        voter: vote.voter,
+        options,
+        metadata: String::new(),
+    })
+}
+
+// decimals in v1beta1 protos are encoded as their atomics, i.e. the value * 10^18
+// so 0.5 is "500000000000000000", while v1 uses plain decimal strings
+pub(crate) fn legacy_dec_to_string(atomics: &str) -> Result<String> {
+    Decimal::from_atomics(
+        Uint128::from_str(atomics).map_err(|e| anyhow!("{e:?}"))?,
+        18,
+    )
+    .map(|value| value.to_string())
+    .map_err(|e| anyhow!("{e:?}"))
+}
+
+pub(crate) fn legacy_dec_from_str(value: &str) -> Result<String> {
+    Decimal::from_str(value)
+        .map(|value| value.atomics().to_string())
+        .map_err(|e| anyhow!("{e:?}"))
+}
+
+fn legacy_dec_bytes_to_string(atomics: Vec<u8>) -> Result<String> {
+    match atomics.is_empty() {
+        true => Ok(String::new()),
+        false => legacy_dec_to_string(&String::from_utf8(atomics)?),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unimplemented() {
+        let err = anyhow::Error::from(tonic::Status::unimplemented(
+            "unknown service cosmos.gov.v1.Query",
+        ))
+        .context("couldn't get gov params");
+        assert!(is_unimplemented(&err));
+
+        let err = anyhow!(
+            "abci query failed: codespace: sdk, code: 6, log: unknown query path: unknown request"
+        );
+        assert!(is_unimplemented(&err));
+
+        let err = anyhow::Error::from(tonic::Status::unavailable("connection refused"));
+        assert!(!is_unimplemented(&err));
+        assert!(!is_unimplemented(&anyhow!("timed out")));
+    }
+}
@@ -274,7 +274,7 @@ impl QueryRequest for IbcClientStateReq {
         let resp = match client.get_connection_mode() {
             ConnectionMode::Grpc => {
                 let mut req = tonic::Request::new(req);
-                apply_grpc_height(&mut req, *height)?;
+                apply_grpc_height(&mut req, height.or(client.query_height()))?;
 
                 let mut query_client =
                     layer_climb_proto::ibc::client::query_client::QueryClient::new(
@@ -294,7 +294,7 @@ impl QueryRequest for IbcClientStateReq {
                 .abci_protobuf_query::<_, layer_climb_proto::ibc::client::QueryClientStateResponse>(
                     "/ibc.core.client.v1.Query/ClientState",
                     req,
-                    *height,
+                    height.or(client.query_height()),
                 )
                 .await
                 .context("couldn't get client state")?,
@@ -346,7 +346,7 @@ impl QueryRequest for IbcConnectionReq {
             ConnectionMode::Grpc => {
                 let mut req = tonic::Request::new(req);
 
-                apply_grpc_height(&mut req, *height)?;
+                apply_grpc_height(&mut req, height.or(client.query_height()))?;
 
                 let mut query_client = layer_climb_proto::ibc::connection::query_client::QueryClient::new(
                     client.clone_grpc_channel()?,
@@ -361,7 +361,7 @@ impl QueryRequest for IbcConnectionReq {
 
             ConnectionMode::Rpc => {
                 client.rpc_client()?
-                    .abci_protobuf_query::<_, layer_climb_proto::ibc::connection::QueryConnectionResponse>("/ibc.core.connection.v1.Query/Connection", req, *height)
+                    .abci_protobuf_query::<_, layer_climb_proto::ibc::connection::QueryConnectionResponse>("/ibc.core.connection.v1.Query/Connection", req, height.or(client.query_height()))
                     .await
                     .context("couldn't get connection")?
             }
@@ -414,7 +414,7 @@ impl QueryRequest for IbcConnectionConsensusStateReq {
 
                 let mut req = tonic::Request::new(req);
 
-                apply_grpc_height(&mut req, *height)?;
+                apply_grpc_height(&mut req, height.or(client.query_height()))?;
 
                 query_client
                     .connection_consensus_state(req)
@@ -424,7 +424,7 @@ impl QueryRequest for IbcConnectionConsensusStateReq {
             },
             ConnectionMode::Rpc => {
                 client.rpc_client()?
-                    .abci_protobuf_query::<_, layer_climb_proto::ibc::connection::QueryConnectionConsensusStateResponse>("/ibc.core.connection.v1.Query/ConnectionConsensusState", req, *height)
+                    .abci_protobuf_query::<_, layer_climb_proto::ibc::connection::QueryConnectionConsensusStateResponse>("/ibc.core.connection.v1.Query/ConnectionConsensusState", req, height.or(client.query_height()))
                     .await
                     .context("couldn't get consensus state")?
             }
@@ -463,7 +463,7 @@ impl QueryRequest for IbcChannelReq {
             ConnectionMode::Grpc => {
                 let mut req = tonic::Request::new(req);
 
-                apply_grpc_height(&mut req, *height)?;
+                apply_grpc_height(&mut req, height.or(client.query_height()))?;
 
                 let mut query_client =
                     layer_climb_proto::ibc::channel::query_client::QueryClient::new(
@@ -481,7 +481,7 @@ impl QueryRequest for IbcChannelReq {
                 .abci_protobuf_query::<_, layer_climb_proto::ibc::channel::QueryChannelResponse>(
                     "/ibc.core.channel.v1.Query/Channel",
                     req,
-                    *height,
+                    height.or(client.query_height()),
                 )
                 .await
                 .context("couldn't get channel")?,
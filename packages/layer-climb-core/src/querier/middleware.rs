@@ -1,59 +1,224 @@
+pub mod cache;
+pub mod failover;
 pub mod logger;
 pub mod retry;
 
+use std::{any::Any, sync::Arc};
+
+use async_trait::async_trait;
+
 use crate::prelude::*;
-use logger::{QueryLoggerMiddlewareMapReq, QueryLoggerMiddlewareMapResp};
+use failover::QueryFailoverMiddleware;
 use retry::QueryRetryMiddleware;
 
-pub enum QueryMiddlewareMapReq {
-    Logger(QueryLoggerMiddlewareMapReq),
-}
+// Middleware is stored as trait objects, so requests and responses pass through type-erased
+// the concrete types can be recovered via `as_any()` / `as_any_mut()` and downcasting
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        pub type QueryFuture<'a, T> = futures::future::LocalBoxFuture<'a, Result<T>>;
+
+        /// Runs before the request, may modify it
+        #[async_trait(?Send)]
+        pub trait QueryMiddlewareMapReq: Send + Sync {
+            async fn map_req(&self, req: &mut dyn AnyQueryRequest) -> Result<()>;
+        }
+
+        /// Runs after the response, may modify it
+        #[async_trait(?Send)]
+        pub trait QueryMiddlewareMapResp: Send + Sync {
+            async fn map_resp(&self, resp: &mut dyn AnyQueryResponse) -> Result<()>;
+        }
+
+        /// Wraps the request itself, i.e. it decides when (and whether) to call `next.run()`
+        #[async_trait(?Send)]
+        pub trait QueryMiddlewareRun: Send + Sync {
+            async fn run(&self, next: QueryNext<'_>) -> Result<Box<dyn AnyQueryResponse>>;
+        }
+    } else {
+        pub type QueryFuture<'a, T> = futures::future::BoxFuture<'a, Result<T>>;
+
+        /// Runs before the request, may modify it
+        #[async_trait]
+        pub trait QueryMiddlewareMapReq: Send + Sync {
+            async fn map_req(&self, req: &mut dyn AnyQueryRequest) -> Result<()>;
+        }
+
+        /// Runs after the response, may modify it
+        #[async_trait]
+        pub trait QueryMiddlewareMapResp: Send + Sync {
+            async fn map_resp(&self, resp: &mut dyn AnyQueryResponse) -> Result<()>;
+        }
 
-impl QueryMiddlewareMapReq {
-    pub async fn map_req<REQ: QueryRequest>(&self, req: REQ) -> Result<REQ> {
-        match self {
-            Self::Logger(m) => m.map_req(req).await,
+        /// Wraps the request itself, i.e. it decides when (and whether) to call `next.run()`
+        #[async_trait]
+        pub trait QueryMiddlewareRun: Send + Sync {
+            async fn run(&self, next: QueryNext<'_>) -> Result<Box<dyn AnyQueryResponse>>;
         }
     }
-    pub fn default_list() -> Vec<Self> {
-        vec![
-            //Self::Logger(QueryLoggerMiddlewareMapReq::default()),
-        ]
+}
+
+pub fn default_middleware_map_req() -> Vec<Arc<dyn QueryMiddlewareMapReq>> {
+    vec![
+        //Arc::new(logger::QueryLoggerMiddlewareMapReq::default()),
+    ]
+}
+
+pub fn default_middleware_map_resp() -> Vec<Arc<dyn QueryMiddlewareMapResp>> {
+    vec![
+        //Arc::new(logger::QueryLoggerMiddlewareMapResp::default()),
+    ]
+}
+
+pub fn default_middleware_run() -> Vec<Arc<dyn QueryMiddlewareRun>> {
+    vec![
+        Arc::new(QueryFailoverMiddleware::default()),
+        Arc::new(QueryRetryMiddleware::default()),
+    ]
+}
+
+/// Any `QueryRequest`, type-erased
+pub trait AnyQueryRequest: std::fmt::Debug + Send + Sync {
+    /// e.g. `layer_climb_core::querier::basic::BlockHeightReq`
+    fn type_name(&self) -> &'static str;
+    fn cache_policy(&self) -> cache::QueryCachePolicy;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<REQ: QueryRequest> AnyQueryRequest for REQ {
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<REQ>()
+    }
+
+    fn cache_policy(&self) -> cache::QueryCachePolicy {
+        QueryRequest::cache_policy(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
     }
 }
 
-pub enum QueryMiddlewareMapResp {
-    Logger(QueryLoggerMiddlewareMapResp),
+/// Any `QueryRequest::QueryResponse`, type-erased
+pub trait AnyQueryResponse: std::fmt::Debug + Send {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+    fn clone_box(&self) -> Box<dyn AnyQueryResponse>;
 }
 
-impl QueryMiddlewareMapResp {
-    pub async fn map_resp<RESP: std::fmt::Debug + Send>(&self, resp: RESP) -> Result<RESP> {
-        match self {
-            Self::Logger(m) => m.map_resp(resp).await,
-        }
+impl<RESP: std::fmt::Debug + Clone + Send + 'static> AnyQueryResponse for RESP {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
     }
-    pub fn default_list() -> Vec<Self> {
-        vec![
-            //Self::Logger(QueryLoggerMiddlewareMapResp::default()),
-        ]
+
+    fn clone_box(&self) -> Box<dyn AnyQueryResponse> {
+        Box::new(self.clone())
     }
 }
 
-pub enum QueryMiddlewareRun {
-    Retry(QueryRetryMiddleware),
+/// The rest of the chain for a `QueryMiddlewareRun`
+/// i.e. the remaining run middleware, and finally the request itself
+#[derive(Clone, Copy)]
+pub struct QueryNext<'a> {
+    query: &'a dyn ErasedQuery,
+    client: &'a QueryClient,
+    middleware: &'a [Arc<dyn QueryMiddlewareRun>],
+}
+
+impl<'a> QueryNext<'a> {
+    pub fn req(&self) -> &'a dyn AnyQueryRequest {
+        self.query.req()
+    }
+
+    pub fn client(&self) -> &'a QueryClient {
+        self.client
+    }
+
+    /// Can be called multiple times, e.g. to retry
+    pub fn run(&self) -> QueryFuture<'a, Box<dyn AnyQueryResponse>> {
+        match self.middleware.split_first() {
+            Some((middleware, rest)) => {
+                let next = QueryNext {
+                    middleware: rest,
+                    ..*self
+                };
+                Box::pin(async move { middleware.run(next).await })
+            }
+            None => self.query.request(self.client.clone()),
+        }
+    }
 }
 
-impl QueryMiddlewareRun {
-    pub async fn run<REQ: QueryRequest>(
+impl QueryClient {
+    pub async fn run_with_middleware<REQ: QueryRequest>(
         &self,
-        req: REQ,
-        client: QueryClient,
+        mut req: REQ,
     ) -> Result<REQ::QueryResponse> {
-        match self {
-            Self::Retry(m) => m.run(req, client).await,
+        for middleware in self.middleware_map_req.iter() {
+            middleware.map_req(&mut req).await?;
         }
+
+        let mut response = if self.middleware_run.is_empty() {
+            req.request(self.clone()).await?
+        } else {
+            let resp = QueryNext {
+                query: &Query(req),
+                client: self,
+                middleware: &self.middleware_run,
+            }
+            .run()
+            .await?;
+
+            *resp
+                .into_any()
+                .downcast::<REQ::QueryResponse>()
+                .map_err(|_| {
+                    anyhow!(
+                        "query middleware returned the wrong response type, expected {}",
+                        std::any::type_name::<REQ::QueryResponse>()
+                    )
+                })?
+        };
+
+        for middleware in self.middleware_map_resp.iter() {
+            middleware.map_resp(&mut response).await?;
+        }
+
+        Ok(response)
     }
-    pub fn default_list() -> Vec<Self> {
-        vec![Self::Retry(QueryRetryMiddleware::default())]
+}
+
+// lets QueryNext call the concrete request without knowing its type
+trait ErasedQuery: Send + Sync {
+    fn req(&self) -> &dyn AnyQueryRequest;
+    fn request(&self, client: QueryClient) -> QueryFuture<'_, Box<dyn AnyQueryResponse>>;
+}
+
+struct Query<REQ>(REQ);
+
+impl<REQ: QueryRequest> ErasedQuery for Query<REQ> {
+    fn req(&self) -> &dyn AnyQueryRequest {
+        &self.0
+    }
+
+    fn request(&self, client: QueryClient) -> QueryFuture<'_, Box<dyn AnyQueryResponse>> {
+        Box::pin(async move {
+            let resp = self.0.request(client).await?;
+            Ok(Box::new(resp) as Box<dyn AnyQueryResponse>)
+        })
     }
 }
@@ -0,0 +1,319 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use async_trait::async_trait;
+
+use super::{AnyQueryResponse, QueryMiddlewareRun, QueryNext};
+use crate::prelude::*;
+
+/// How long a response can be kept by `QueryCacheMiddleware`
+/// set per request type via `QueryRequest::cache_policy()`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueryCachePolicy {
+    /// always hits the network, e.g. the block height itself, or account sequences
+    Never,
+    /// "latest" queries, the response is reused until the chain moves to the next block
+    UntilNextBlock,
+    /// immutable lookups, e.g. queries pinned to a past height
+    Forever,
+}
+
+/// Caches query responses, keyed by the chain, the request, and (for "latest" queries) the block height
+/// the storage lives in `ClimbCache::query_responses`, so clients built from the same `ClimbCache` share it
+///
+/// "latest" lookups still query the block height, since that's the only way to know if the chain moved on
+///
+/// Not enabled by default, add it to `QueryClient::middleware_run` (typically before the retry middleware)
+#[derive(Clone)]
+pub struct QueryCacheMiddleware {
+    pub cache: QueryResponseCache,
+}
+
+impl QueryCacheMiddleware {
+    pub fn new(cache: QueryResponseCache) -> Self {
+        Self { cache }
+    }
+
+    async fn cached(&self, next: QueryNext<'_>) -> Result<Box<dyn AnyQueryResponse>> {
+        let req = next.req();
+
+        let pinned_height = next.client().query_height();
+
+        let height = match (req.cache_policy(), pinned_height) {
+            (QueryCachePolicy::Never, _) => return next.run().await,
+            // a client pinned via `at_height()` reads state that won't change anymore
+            (QueryCachePolicy::UntilNextBlock, None) => Some(next.client().block_height().await?),
+            (QueryCachePolicy::UntilNextBlock | QueryCachePolicy::Forever, _) => None,
+        };
+
+        let key = CacheKey {
+            chain_id: next.client().chain_config.chain_id.to_string(),
+            type_name: req.type_name(),
+            req: format!("{req:?}"),
+            pinned_height,
+            height,
+        };
+
+        if let Some(resp) = self.cache.get(&key) {
+            return Ok(resp);
+        }
+
+        let resp = next.run().await?;
+        self.cache.insert(key, resp.clone_box());
+
+        Ok(resp)
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        #[async_trait(?Send)]
+        impl QueryMiddlewareRun for QueryCacheMiddleware {
+            async fn run(&self, next: QueryNext<'_>) -> Result<Box<dyn AnyQueryResponse>> {
+                self.cached(next).await
+            }
+        }
+    } else {
+        #[async_trait]
+        impl QueryMiddlewareRun for QueryCacheMiddleware {
+            async fn run(&self, next: QueryNext<'_>) -> Result<Box<dyn AnyQueryResponse>> {
+                self.cached(next).await
+            }
+        }
+    }
+}
+
+/// Bounded storage for `QueryCacheMiddleware`, cheap to clone (clones share the same storage)
+/// when full, responses for past blocks are dropped first, then the least recently used
+#[derive(Clone)]
+pub struct QueryResponseCache {
+    inner: Arc<Mutex<CacheInner>>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+    evictions: Arc<AtomicU64>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct QueryCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub entries: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct CacheKey {
+    chain_id: String,
+    type_name: &'static str,
+    // the Debug output is the only view of the request we have after type-erasure
+    req: String,
+    // the height set via `QueryClient::at_height()`, if any
+    pinned_height: Option<u64>,
+    // None for responses that are cached forever
+    height: Option<u64>,
+}
+
+struct CacheEntry {
+    resp: Box<dyn AnyQueryResponse>,
+    last_used: u64,
+}
+
+struct CacheInner {
+    max_entries: usize,
+    entries: HashMap<CacheKey, CacheEntry>,
+    // highest block height seen per chain, anything below it is stale
+    latest_heights: HashMap<String, u64>,
+    tick: u64,
+}
+
+impl QueryResponseCache {
+    pub const DEFAULT_MAX_ENTRIES: usize = 1000;
+
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(CacheInner {
+                max_entries,
+                entries: HashMap::new(),
+                latest_heights: HashMap::new(),
+                tick: 0,
+            })),
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
+            evictions: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn stats(&self) -> QueryCacheStats {
+        QueryCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            entries: self.inner.lock().unwrap().entries.len(),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.inner.lock().unwrap().entries.clear();
+    }
+
+    fn get(&self, key: &CacheKey) -> Option<Box<dyn AnyQueryResponse>> {
+        let mut inner = self.inner.lock().unwrap();
+
+        if let Some(height) = key.height {
+            let latest = inner
+                .latest_heights
+                .entry(key.chain_id.clone())
+                .or_default();
+            *latest = (*latest).max(height);
+        }
+
+        inner.tick += 1;
+        let tick = inner.tick;
+
+        match inner.entries.get_mut(key) {
+            Some(entry) => {
+                entry.last_used = tick;
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(entry.resp.clone_box())
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    fn insert(&self, key: CacheKey, resp: Box<dyn AnyQueryResponse>) {
+        let mut inner = self.inner.lock().unwrap();
+
+        if inner.max_entries == 0 {
+            return;
+        }
+
+        if inner.entries.len() >= inner.max_entries && !inner.entries.contains_key(&key) {
+            let evicted = inner.evict();
+            self.evictions.fetch_add(evicted, Ordering::Relaxed);
+        }
+
+        inner.tick += 1;
+        let last_used = inner.tick;
+        inner.entries.insert(key, CacheEntry { resp, last_used });
+    }
+}
+
+impl Default for QueryResponseCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_ENTRIES)
+    }
+}
+
+impl CacheInner {
+    // returns how many entries were removed
+    fn evict(&mut self) -> u64 {
+        let before = self.entries.len();
+
+        let latest_heights = &self.latest_heights;
+        self.entries.retain(|key, _| match key.height {
+            Some(height) => latest_heights
+                .get(&key.chain_id)
+                .is_none_or(|latest| height >= *latest),
+            None => true,
+        });
+
+        if self.entries.len() >= self.max_entries {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        (before - self.entries.len()) as u64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn key(req: &str, height: Option<u64>) -> CacheKey {
+        CacheKey {
+            chain_id: "test-1".to_string(),
+            type_name: "Req",
+            req: req.to_string(),
+            pinned_height: None,
+            height,
+        }
+    }
+
+    fn cached_value(cache: &QueryResponseCache, key: &CacheKey) -> Option<u64> {
+        cache
+            .get(key)
+            .map(|resp| *resp.as_any().downcast_ref::<u64>().unwrap())
+    }
+
+    #[test]
+    fn hit_and_miss() {
+        let cache = QueryResponseCache::new(10);
+
+        assert_eq!(cached_value(&cache, &key("a", Some(1))), None);
+        cache.insert(key("a", Some(1)), Box::new(1u64));
+        assert_eq!(cached_value(&cache, &key("a", Some(1))), Some(1));
+        // same request at the next block
+        assert_eq!(cached_value(&cache, &key("a", Some(2))), None);
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 1));
+    }
+
+    #[test]
+    fn evicts_stale_heights_first() {
+        let cache = QueryResponseCache::new(3);
+
+        cache.insert(key("forever", None), Box::new(0u64));
+        cache.insert(key("a", Some(1)), Box::new(1u64));
+        cache.insert(key("b", Some(1)), Box::new(2u64));
+
+        // seeing height 2 makes everything at height 1 stale
+        assert_eq!(cached_value(&cache, &key("a", Some(2))), None);
+        cache.insert(key("a", Some(2)), Box::new(3u64));
+
+        assert_eq!(cache.stats().evictions, 2);
+        assert_eq!(cache.stats().entries, 2);
+        assert_eq!(cached_value(&cache, &key("forever", None)), Some(0));
+        assert_eq!(cached_value(&cache, &key("a", Some(2))), Some(3));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = QueryResponseCache::new(2);
+
+        cache.insert(key("a", None), Box::new(1u64));
+        cache.insert(key("b", None), Box::new(2u64));
+        // touch "a" so "b" is the oldest
+        assert_eq!(cached_value(&cache, &key("a", None)), Some(1));
+
+        cache.insert(key("c", None), Box::new(3u64));
+
+        assert_eq!(cache.stats().evictions, 1);
+        assert_eq!(cached_value(&cache, &key("a", None)), Some(1));
+        assert_eq!(cached_value(&cache, &key("b", None)), None);
+        assert_eq!(cached_value(&cache, &key("c", None)), Some(3));
+    }
+
+    #[test]
+    fn zero_capacity_stores_nothing() {
+        let cache = QueryResponseCache::new(0);
+        cache.insert(key("a", None), Box::new(1u64));
+        assert_eq!(cached_value(&cache, &key("a", None)), None);
+    }
+}
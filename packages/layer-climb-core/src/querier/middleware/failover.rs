@@ -0,0 +1,236 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+
+use super::{
+    retry::{classify_query_error, QueryErrorKind},
+    AnyQueryResponse, QueryMiddlewareRun, QueryNext,
+};
+use crate::prelude::*;
+
+/// Moves on to the next endpoint (see `ChainConfig::all_rpc_endpoints()` etc.) when a query fails with a transient error
+/// and every `check_every` queries, health-checks all the endpoints to move off one that fell behind
+///
+/// A no-op if there's only one endpoint for the current connection mode
+/// should be placed before the retry middleware, so that each endpoint gets the full retry policy
+#[derive(Clone, Debug)]
+pub struct QueryFailoverMiddleware {
+    /// how many blocks an endpoint can be behind the highest one before it's considered unhealthy
+    pub max_block_lag: u64,
+    /// if None, endpoints are only switched on errors
+    pub check_every: Option<u64>,
+    queries: Arc<AtomicU64>,
+}
+
+impl Default for QueryFailoverMiddleware {
+    fn default() -> Self {
+        Self::new(5, Some(100))
+    }
+}
+
+impl QueryFailoverMiddleware {
+    pub fn new(max_block_lag: u64, check_every: Option<u64>) -> Self {
+        Self {
+            max_block_lag,
+            check_every,
+            queries: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    async fn failover(&self, next: QueryNext<'_>) -> Result<Box<dyn AnyQueryResponse>> {
+        let client = next.client();
+        let n_endpoints = client.endpoints().len();
+
+        if n_endpoints <= 1 {
+            return next.run().await;
+        }
+
+        if let Some(check_every) = self.check_every.filter(|n| *n > 0) {
+            if self
+                .queries
+                .fetch_add(1, Ordering::Relaxed)
+                .is_multiple_of(check_every)
+            {
+                client.check_endpoints(self.max_block_lag).await;
+            }
+        }
+
+        with_failover(
+            n_endpoints,
+            || client.active_endpoint_index(),
+            || next.run(),
+            |index, err| {
+                let endpoint = client.failover_from(index);
+
+                tracing::warn!(
+                    request = next.req().type_name(),
+                    to = endpoint,
+                    error = %err,
+                    "query failed, failing over to the next endpoint"
+                );
+            },
+        )
+        .await
+    }
+}
+
+// tries each endpoint at most once, calling `failover` with the index that failed before moving on
+// stops at the first success or permanent error
+async fn with_failover<T, Fut>(
+    n_endpoints: usize,
+    active_index: impl Fn() -> usize,
+    mut run: impl FnMut() -> Fut,
+    mut failover: impl FnMut(usize, &anyhow::Error),
+) -> Result<T>
+where
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut attempts = 0;
+
+    loop {
+        let index = active_index();
+        attempts += 1;
+
+        match run().await {
+            Ok(resp) => return Ok(resp),
+            Err(err) => {
+                if attempts >= n_endpoints
+                    || classify_query_error(&err) == QueryErrorKind::Permanent
+                {
+                    return Err(err);
+                }
+
+                failover(index, &err);
+            }
+        }
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        #[async_trait(?Send)]
+        impl QueryMiddlewareRun for QueryFailoverMiddleware {
+            async fn run(&self, next: QueryNext<'_>) -> Result<Box<dyn AnyQueryResponse>> {
+                self.failover(next).await
+            }
+        }
+    } else {
+        #[async_trait]
+        impl QueryMiddlewareRun for QueryFailoverMiddleware {
+            async fn run(&self, next: QueryNext<'_>) -> Result<Box<dyn AnyQueryResponse>> {
+                self.failover(next).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{atomic::AtomicUsize, Mutex};
+
+    use futures::executor::block_on;
+
+    use super::*;
+
+    // fakes `n` endpoints, where each attempt pops the next scripted result
+    // and a failover moves the active index on, like `QueryClient::failover_from`
+    struct FakeEndpoints {
+        n: usize,
+        active: AtomicUsize,
+        results: Mutex<Vec<Result<u64>>>,
+        attempted: Mutex<Vec<usize>>,
+        failed_over_from: Mutex<Vec<usize>>,
+    }
+
+    impl FakeEndpoints {
+        fn new(n: usize, mut results: Vec<Result<u64>>) -> Self {
+            results.reverse();
+            Self {
+                n,
+                active: AtomicUsize::new(0),
+                results: Mutex::new(results),
+                attempted: Mutex::new(Vec::new()),
+                failed_over_from: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn run(&self) -> Result<u64> {
+            block_on(with_failover(
+                self.n,
+                || self.active.load(Ordering::SeqCst),
+                || async {
+                    self.attempted
+                        .lock()
+                        .unwrap()
+                        .push(self.active.load(Ordering::SeqCst));
+                    self.results.lock().unwrap().pop().unwrap()
+                },
+                |index, _| {
+                    self.failed_over_from.lock().unwrap().push(index);
+                    self.active.store((index + 1) % self.n, Ordering::SeqCst);
+                },
+            ))
+        }
+    }
+
+    fn transient() -> anyhow::Error {
+        tonic::Status::unavailable("connection refused").into()
+    }
+
+    fn permanent() -> anyhow::Error {
+        tonic::Status::not_found("no such contract").into()
+    }
+
+    #[test]
+    fn fails_over_on_transient_errors() {
+        let endpoints = FakeEndpoints::new(3, vec![Err(transient()), Err(transient()), Ok(42)]);
+
+        assert_eq!(endpoints.run().unwrap(), 42);
+        assert_eq!(*endpoints.attempted.lock().unwrap(), vec![0, 1, 2]);
+        assert_eq!(*endpoints.failed_over_from.lock().unwrap(), vec![0, 1]);
+        // the working endpoint stays active for the next query
+        assert_eq!(endpoints.active.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn stops_on_permanent_errors() {
+        let endpoints = FakeEndpoints::new(3, vec![Err(permanent()), Ok(42)]);
+
+        let err = endpoints.run().unwrap_err();
+        assert_eq!(classify_query_error(&err), QueryErrorKind::Permanent);
+        assert_eq!(*endpoints.attempted.lock().unwrap(), vec![0]);
+        assert!(endpoints.failed_over_from.lock().unwrap().is_empty());
+        assert_eq!(endpoints.active.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn permanent_error_after_failover() {
+        let endpoints = FakeEndpoints::new(3, vec![Err(transient()), Err(permanent()), Ok(42)]);
+
+        assert!(endpoints.run().is_err());
+        assert_eq!(*endpoints.attempted.lock().unwrap(), vec![0, 1]);
+        assert_eq!(*endpoints.failed_over_from.lock().unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn tries_each_endpoint_once() {
+        let endpoints = FakeEndpoints::new(2, vec![Err(transient()), Err(transient()), Ok(42)]);
+
+        let err = endpoints.run().unwrap_err();
+        assert_eq!(classify_query_error(&err), QueryErrorKind::Transient);
+        assert_eq!(*endpoints.attempted.lock().unwrap(), vec![0, 1]);
+        assert_eq!(*endpoints.failed_over_from.lock().unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn starts_from_the_active_endpoint() {
+        let endpoints = FakeEndpoints::new(3, vec![Err(transient()), Ok(42)]);
+        endpoints.active.store(2, Ordering::SeqCst);
+
+        assert_eq!(endpoints.run().unwrap(), 42);
+        assert_eq!(*endpoints.attempted.lock().unwrap(), vec![2, 0]);
+    }
+}
@@ -1,5 +1,8 @@
 use std::sync::Arc;
 
+use async_trait::async_trait;
+
+use super::{AnyQueryRequest, AnyQueryResponse, QueryMiddlewareMapReq, QueryMiddlewareMapResp};
 use crate::prelude::*;
 
 #[derive(Clone)]
@@ -22,10 +25,23 @@ impl Default for QueryLoggerMiddlewareMapReq {
     }
 }
 
-impl QueryLoggerMiddlewareMapReq {
-    pub async fn map_req<REQ: QueryRequest>(&self, req: REQ) -> Result<REQ> {
-        (self.logger_fn)(format!("{req:?}"));
-        Ok(req)
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        #[async_trait(?Send)]
+        impl QueryMiddlewareMapReq for QueryLoggerMiddlewareMapReq {
+            async fn map_req(&self, req: &mut dyn AnyQueryRequest) -> Result<()> {
+                (self.logger_fn)(format!("{req:?}"));
+                Ok(())
+            }
+        }
+    } else {
+        #[async_trait]
+        impl QueryMiddlewareMapReq for QueryLoggerMiddlewareMapReq {
+            async fn map_req(&self, req: &mut dyn AnyQueryRequest) -> Result<()> {
+                (self.logger_fn)(format!("{req:?}"));
+                Ok(())
+            }
+        }
     }
 }
 
@@ -48,9 +64,22 @@ impl Default for QueryLoggerMiddlewareMapResp {
     }
 }
 
-impl QueryLoggerMiddlewareMapResp {
-    pub async fn map_resp<RESP: std::fmt::Debug + Send>(&self, resp: RESP) -> Result<RESP> {
-        (self.logger_fn)(format!("{resp:?}"));
-        Ok(resp)
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        #[async_trait(?Send)]
+        impl QueryMiddlewareMapResp for QueryLoggerMiddlewareMapResp {
+            async fn map_resp(&self, resp: &mut dyn AnyQueryResponse) -> Result<()> {
+                (self.logger_fn)(format!("{resp:?}"));
+                Ok(())
+            }
+        }
+    } else {
+        #[async_trait]
+        impl QueryMiddlewareMapResp for QueryLoggerMiddlewareMapResp {
+            async fn map_resp(&self, resp: &mut dyn AnyQueryResponse) -> Result<()> {
+                (self.logger_fn)(format!("{resp:?}"));
+                Ok(())
+            }
+        }
     }
 }
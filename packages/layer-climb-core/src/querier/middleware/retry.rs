@@ -1,43 +1,302 @@
+use super::{AnyQueryResponse, QueryMiddlewareRun, QueryNext};
 use crate::prelude::*;
-use std::time::Duration;
+use async_trait::async_trait;
+use futures::future::{select, Either};
+use std::{collections::HashMap, time::Duration};
 
-#[derive(Clone)]
+/// Retries transient failures (see `classify_query_error`), permanent ones are returned immediately
+/// the policy can be overridden per `QueryRequest` type via `with_override`
+#[derive(Clone, Debug, Default)]
 pub struct QueryRetryMiddleware {
+    pub policy: RetryPolicy,
+    /// keyed by `std::any::type_name` of the request
+    pub overrides: HashMap<&'static str, RetryPolicy>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// including the first attempt, i.e. 1 means no retries
     pub max_attempts: u32,
+    /// the delay before the first retry, doubled each time after
     pub backoff: Duration,
+    pub max_backoff: Duration,
+    /// randomizes each delay by up to this fraction in either direction, e.g. 0.2 is +/- 20%
+    pub jitter: f32,
+    /// gives up once this much time has passed across all attempts, if set
+    pub deadline: Option<Duration>,
 }
 
-impl Default for QueryRetryMiddleware {
+impl Default for RetryPolicy {
     fn default() -> Self {
         Self {
             max_attempts: 3,
             backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            jitter: 0.2,
+            deadline: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn no_retries() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    fn delay(&self, retry: u32) -> Duration {
+        let backoff = self
+            .backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff);
+
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return backoff;
         }
+
+        let factor = 1.0 + jitter * (rand::random::<f32>() * 2.0 - 1.0);
+        backoff.mul_f32(factor)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueryErrorKind {
+    /// e.g. a dropped connection or an overloaded node, worth retrying
+    Transient,
+    /// e.g. a missing contract or a bad query message, will fail the same way again
+    Permanent,
+}
+
+/// Best-effort classification of a failed query
+/// typed errors (tonic status codes, io errors) are checked first, then the message, since rpc errors arrive as strings
+pub fn classify_query_error(err: &anyhow::Error) -> QueryErrorKind {
+    for cause in err.chain() {
+        if let Some(status) = cause.downcast_ref::<tonic::Status>() {
+            match status.code() {
+                tonic::Code::Unavailable
+                | tonic::Code::DeadlineExceeded
+                | tonic::Code::ResourceExhausted
+                | tonic::Code::Aborted
+                | tonic::Code::Cancelled => return QueryErrorKind::Transient,
+                // these can be transport errors in disguise, so check the message
+                tonic::Code::Unknown | tonic::Code::Internal => break,
+                _ => return QueryErrorKind::Permanent,
+            }
+        }
+
+        if let Some(err) = cause.downcast_ref::<std::io::Error>() {
+            match err.kind() {
+                std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionRefused
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::BrokenPipe
+                | std::io::ErrorKind::TimedOut
+                | std::io::ErrorKind::UnexpectedEof => return QueryErrorKind::Transient,
+                _ => {}
+            }
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if cause.downcast_ref::<tonic::transport::Error>().is_some() {
+            return QueryErrorKind::Transient;
+        }
+    }
+
+    const TRANSIENT_MESSAGES: &[&str] = &[
+        "connection reset",
+        "connection refused",
+        "connection closed",
+        "broken pipe",
+        "timed out",
+        "timeout",
+        "transport error",
+        "service was not ready",
+        "too many requests",
+        "unexpected status code: 5",
+        "unexpected status code: 429",
+        "bad gateway",
+        "service unavailable",
+        "gateway timeout",
+    ];
+
+    let message = format!("{err:?}").to_lowercase();
+
+    if TRANSIENT_MESSAGES
+        .iter()
+        .any(|transient| message.contains(transient))
+    {
+        QueryErrorKind::Transient
+    } else {
+        QueryErrorKind::Permanent
     }
 }
 
 impl QueryRetryMiddleware {
-    pub async fn run<REQ: QueryRequest>(
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self {
+            policy,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Use a different policy for one type of request
+    /// e.g. `.with_override::<BlockHeightReq>(RetryPolicy::no_retries())`
+    pub fn with_override<REQ: QueryRequest>(mut self, policy: RetryPolicy) -> Self {
+        self.overrides.insert(std::any::type_name::<REQ>(), policy);
+        self
+    }
+
+    async fn retry(&self, next: QueryNext<'_>) -> Result<Box<dyn AnyQueryResponse>> {
+        let type_name = next.req().type_name();
+        let policy = self.overrides.get(type_name).unwrap_or(&self.policy);
+
+        match policy.deadline {
+            None => self.retry_with_policy(next, policy).await,
+            Some(deadline) => {
+                let attempts = Box::pin(self.retry_with_policy(next, policy));
+                match select(attempts, futures_timer::Delay::new(deadline)).await {
+                    Either::Left((res, _)) => res,
+                    Either::Right(_) => {
+                        tracing::warn!(request = type_name, ?deadline, "query retry deadline hit");
+                        Err(anyhow!(
+                            "query {type_name} didn't succeed within {deadline:?}"
+                        ))
+                    }
+                }
+            }
+        }
+    }
+
+    async fn retry_with_policy(
         &self,
-        req: REQ,
-        client: QueryClient,
-    ) -> Result<REQ::QueryResponse> {
+        next: QueryNext<'_>,
+        policy: &RetryPolicy,
+    ) -> Result<Box<dyn AnyQueryResponse>> {
+        let type_name = next.req().type_name();
         let mut attempts = 0;
-        let mut backoff = self.backoff;
 
         loop {
             attempts += 1;
-            match req.request(client.clone()).await {
+            match next.run().await {
                 Ok(resp) => return Ok(resp),
                 Err(err) => {
-                    if attempts < self.max_attempts {
-                        futures_timer::Delay::new(backoff).await;
-                        backoff *= 2;
-                    } else {
+                    if attempts >= policy.max_attempts
+                        || classify_query_error(&err) == QueryErrorKind::Permanent
+                    {
                         return Err(err);
                     }
+
+                    let delay = policy.delay(attempts - 1);
+
+                    tracing::warn!(
+                        request = type_name,
+                        attempt = attempts,
+                        max_attempts = policy.max_attempts,
+                        ?delay,
+                        error = %err,
+                        "retrying query"
+                    );
+
+                    futures_timer::Delay::new(delay).await;
                 }
             }
         }
     }
 }
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        #[async_trait(?Send)]
+        impl QueryMiddlewareRun for QueryRetryMiddleware {
+            async fn run(&self, next: QueryNext<'_>) -> Result<Box<dyn AnyQueryResponse>> {
+                self.retry(next).await
+            }
+        }
+    } else {
+        #[async_trait]
+        impl QueryMiddlewareRun for QueryRetryMiddleware {
+            async fn run(&self, next: QueryNext<'_>) -> Result<Box<dyn AnyQueryResponse>> {
+                self.retry(next).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn classify_status() {
+        let err = anyhow::Error::from(tonic::Status::unavailable("node is syncing"))
+            .context("couldn't get balance");
+        assert_eq!(classify_query_error(&err), QueryErrorKind::Transient);
+
+        let err = anyhow::Error::from(tonic::Status::not_found("contract not found"));
+        assert_eq!(classify_query_error(&err), QueryErrorKind::Permanent);
+
+        // unknown can be a transport failure, so the message decides
+        let err = anyhow::Error::from(tonic::Status::unknown(
+            "error trying to connect: connection refused",
+        ));
+        assert_eq!(classify_query_error(&err), QueryErrorKind::Transient);
+        let err = anyhow::Error::from(tonic::Status::unknown("Generic error: unknown variant"));
+        assert_eq!(classify_query_error(&err), QueryErrorKind::Permanent);
+    }
+
+    #[test]
+    fn classify_io() {
+        let err = anyhow::Error::from(std::io::Error::from(std::io::ErrorKind::ConnectionReset));
+        assert_eq!(classify_query_error(&err), QueryErrorKind::Transient);
+    }
+
+    #[test]
+    fn classify_message() {
+        for message in [
+            "rpc error: HTTP status client error (429 Too Many Requests)",
+            "response error: unexpected status code: 503",
+            "502 Bad Gateway",
+            "request timed out",
+        ] {
+            assert_eq!(
+                classify_query_error(&anyhow!("{message}")),
+                QueryErrorKind::Transient,
+                "{message}"
+            );
+        }
+
+        assert_eq!(
+            classify_query_error(&anyhow!(
+                "query wasm contract failed: Error parsing into type"
+            )),
+            QueryErrorKind::Permanent
+        );
+    }
+
+    #[test]
+    fn delay() {
+        let policy = RetryPolicy {
+            backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(350),
+            jitter: 0.0,
+            ..Default::default()
+        };
+
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(1), Duration::from_millis(200));
+        assert_eq!(policy.delay(2), Duration::from_millis(350));
+        assert_eq!(policy.delay(40), Duration::from_millis(350));
+
+        let policy = RetryPolicy {
+            jitter: 0.5,
+            ..policy
+        };
+        for _ in 0..100 {
+            let delay = policy.delay(0);
+            assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(150));
+        }
+    }
+}
@@ -0,0 +1,586 @@
+use std::future::Future;
+
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use layer_climb_proto::query::{PageRequest, PageResponse};
+
+use super::basic::BlockHeightReq;
+use crate::prelude::*;
+
+/// How to page through a paginated query, see `paginate()`
+#[derive(Clone, Debug, Default)]
+pub struct PaginationOptions {
+    /// items per page, if None the node's default is used (typically 100)
+    pub page_size: Option<u64>,
+    /// newest / highest first, for queries that support it
+    pub reverse: bool,
+    /// where to start, if None from the beginning
+    pub cursor: Option<PageCursor>,
+    /// stop after this many items in total, no more pages are fetched after that
+    pub max_items: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub enum PageCursor {
+    /// the `next_key` of a previous page
+    Key(Vec<u8>),
+    /// skip this many items, only applies to the first page (the following ones use the key the node returns)
+    Offset(u64),
+}
+
+impl PaginationOptions {
+    fn first_page(&self) -> PageRequest {
+        let (key, offset) = match &self.cursor {
+            None => (Vec::new(), 0),
+            Some(PageCursor::Key(key)) => (key.clone(), 0),
+            Some(PageCursor::Offset(offset)) => (Vec::new(), *offset),
+        };
+
+        PageRequest {
+            key,
+            offset,
+            // 0 means the node's default
+            limit: self.page_size.unwrap_or_default(),
+            count_total: false,
+            reverse: self.reverse,
+        }
+    }
+}
+
+/// One page of a paginated query
+#[derive(Clone, Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// empty on the last page
+    pub next_key: Vec<u8>,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, pagination: Option<PageResponse>) -> Self {
+        Self {
+            items,
+            next_key: pagination.map(|p| p.next_key).unwrap_or_default(),
+        }
+    }
+}
+
+/// A paginated query response, i.e. a list of items and a `PageResponse`
+pub trait PaginatedResponse {
+    type Item;
+
+    fn into_page(self) -> Page<Self::Item>;
+}
+
+macro_rules! impl_paginated_response {
+    ($resp:ty, $items:ident, $item:ty) => {
+        impl PaginatedResponse for $resp {
+            type Item = $item;
+
+            fn into_page(self) -> Page<$item> {
+                Page::new(self.$items, self.pagination)
+            }
+        }
+    };
+}
+
+impl_paginated_response!(
+    layer_climb_proto::bank::QueryAllBalancesResponse,
+    balances,
+    layer_climb_proto::Coin
+);
+impl_paginated_response!(
+    layer_climb_proto::bank::QueryTotalSupplyResponse,
+    supply,
+    layer_climb_proto::Coin
+);
+impl_paginated_response!(
+    layer_climb_proto::bank::QuerySpendableBalancesResponse,
+    balances,
+    layer_climb_proto::Coin
+);
+impl_paginated_response!(
+    layer_climb_proto::bank::QueryDenomsMetadataResponse,
+    metadatas,
+    layer_climb_proto::bank::Metadata
+);
+impl_paginated_response!(
+    layer_climb_proto::bank::QuerySendEnabledResponse,
+    send_enabled,
+    layer_climb_proto::bank::SendEnabled
+);
+impl_paginated_response!(
+    layer_climb_proto::wasm::QueryContractsByCodeResponse,
+    contracts,
+    String
+);
+impl_paginated_response!(
+    layer_climb_proto::wasm::QueryContractsByCreatorResponse,
+    contract_addresses,
+    String
+);
+impl_paginated_response!(
+    layer_climb_proto::wasm::QueryCodesResponse,
+    code_infos,
+    layer_climb_proto::wasm::CodeInfoResponse
+);
+impl_paginated_response!(
+    layer_climb_proto::wasm::QueryContractHistoryResponse,
+    entries,
+    layer_climb_proto::wasm::ContractCodeHistoryEntry
+);
+impl_paginated_response!(
+    layer_climb_proto::wasm::QueryAllContractStateResponse,
+    models,
+    layer_climb_proto::wasm::Model
+);
+impl_paginated_response!(
+    layer_climb_proto::staking::QueryValidatorsResponse,
+    validators,
+    layer_climb_proto::staking::Validator
+);
+impl_paginated_response!(
+    layer_climb_proto::staking::QueryDelegatorDelegationsResponse,
+    delegation_responses,
+    layer_climb_proto::staking::DelegationResponse
+);
+impl_paginated_response!(
+    layer_climb_proto::staking::QueryDelegatorUnbondingDelegationsResponse,
+    unbonding_responses,
+    layer_climb_proto::staking::UnbondingDelegation
+);
+impl_paginated_response!(
+    layer_climb_proto::staking::QueryRedelegationsResponse,
+    redelegation_responses,
+    layer_climb_proto::staking::RedelegationResponse
+);
+impl_paginated_response!(
+    layer_climb_proto::gov::v1::QueryProposalsResponse,
+    proposals,
+    layer_climb_proto::gov::v1::Proposal
+);
+impl_paginated_response!(
+    layer_climb_proto::gov::v1::QueryVotesResponse,
+    votes,
+    layer_climb_proto::gov::v1::Vote
+);
+impl_paginated_response!(
+    layer_climb_proto::gov::v1::QueryDepositsResponse,
+    deposits,
+    layer_climb_proto::gov::v1::Deposit
+);
+impl_paginated_response!(
+    layer_climb_proto::feegrant::QueryAllowancesResponse,
+    allowances,
+    layer_climb_proto::feegrant::Grant
+);
+impl_paginated_response!(
+    layer_climb_proto::feegrant::QueryAllowancesByGranterResponse,
+    allowances,
+    layer_climb_proto::feegrant::Grant
+);
+
+/// Turns any paginated query into a stream of its items, fetching pages as the stream is polled
+///
+/// `fetch_page` gets the client and the `PageRequest` for each page, and can do anything with them
+/// e.g. a grpc call, an abci query, or `run_with_middleware()` on a request that takes a `PageRequest`
+///
+/// Unless the client is already pinned via `at_height()`, every page is read at the height of the first one
+/// so the items are a consistent snapshot. The stream ends after the first error.
+pub fn paginate<T, F, Fut>(
+    client: QueryClient,
+    options: PaginationOptions,
+    fetch_page: F,
+) -> impl Stream<Item = Result<T>>
+where
+    F: FnMut(QueryClient, PageRequest) -> Fut,
+    Fut: Future<Output = Result<Page<T>>>,
+{
+    let max_items = options.max_items.unwrap_or(usize::MAX);
+
+    stream::try_unfold(
+        (fetch_page, client, Some(options.first_page())),
+        |(mut fetch_page, client, page_req)| async move {
+            let Some(page_req) = page_req else {
+                return Ok::<_, anyhow::Error>(None);
+            };
+
+            let client = match client.query_height() {
+                Some(_) => client,
+                None => {
+                    let height = BlockHeightReq {}.request(client.clone()).await?;
+                    client.at_height(height)
+                }
+            };
+
+            let page = fetch_page(client.clone(), page_req.clone()).await?;
+
+            let next_page_req = match page.next_key.is_empty() {
+                true => None,
+                false => Some(PageRequest {
+                    key: page.next_key,
+                    offset: 0,
+                    ..page_req
+                }),
+            };
+
+            Ok(Some((page.items, (fetch_page, client, next_page_req))))
+        },
+    )
+    .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+    .try_flatten()
+    .take(max_items)
+}
+
+impl QueryClient {
+    /// Streams any paginated query by its abci path, e.g. for queries climb doesn't wrap
+    /// `make_req` builds the request for each page, e.g. `|pagination| QueryDenomsMetadataRequest { pagination: Some(pagination) }`
+    ///
+    /// This always goes over rpc, for grpc call `paginate()` with the grpc client instead
+    pub fn paginate_abci<REQ, RESP>(
+        &self,
+        path: impl ToString,
+        make_req: impl Fn(PageRequest) -> REQ,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<RESP::Item>>
+    where
+        REQ: layer_climb_proto::Name,
+        RESP: layer_climb_proto::Name + Default + PaginatedResponse,
+    {
+        let path = path.to_string();
+
+        paginate(self.clone(), options, move |client, pagination| {
+            let path = path.clone();
+            let req = make_req(pagination);
+
+            async move {
+                client
+                    .rpc_client()?
+                    .abci_protobuf_query::<_, RESP>(path, req, client.query_height())
+                    .await
+                    .map(RESP::into_page)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use futures::executor::block_on;
+    use layer_climb_proto::bank::{
+        Metadata, QueryDenomsMetadataRequest, QueryDenomsMetadataResponse,
+    };
+
+    use super::*;
+    use crate::testing::{query_client, HEIGHT};
+
+    // a fake page source over the items 0..n, where the key is the (big-endian) index of the next item
+    fn fake_page(n: u64, req: &PageRequest) -> Page<u64> {
+        let start = match req.key.is_empty() {
+            true => req.offset,
+            false => u64::from_be_bytes(req.key.clone().try_into().unwrap()),
+        };
+        let limit = match req.limit {
+            0 => 100,
+            limit => limit,
+        };
+        let end = (start + limit).min(n);
+
+        Page {
+            items: (start..end)
+                .map(|i| if req.reverse { n - 1 - i } else { i })
+                .collect(),
+            next_key: match end < n {
+                true => end.to_be_bytes().to_vec(),
+                false => Vec::new(),
+            },
+        }
+    }
+
+    // the rpc is never reached, since the client is pinned and the page source is fake
+    fn pinned_client() -> QueryClient {
+        query_client(|path, _, _| panic!("unexpected query {path}")).at_height(HEIGHT)
+    }
+
+    // collects the whole stream from `fake_page()`, along with the page requests it made
+    fn collect(options: PaginationOptions, n: u64) -> (Vec<u64>, Vec<PageRequest>) {
+        let requests = Mutex::new(Vec::new());
+
+        let items = block_on(
+            paginate(pinned_client(), options, |client, req| {
+                assert_eq!(client.query_height(), Some(HEIGHT));
+                requests.lock().unwrap().push(req.clone());
+                let page = fake_page(n, &req);
+                async move { Ok(page) }
+            })
+            .try_collect::<Vec<_>>(),
+        )
+        .unwrap();
+
+        (items, requests.into_inner().unwrap())
+    }
+
+    #[test]
+    fn first_page() {
+        let req = PaginationOptions::default().first_page();
+        assert!(req.key.is_empty());
+        assert_eq!(req.offset, 0);
+        assert_eq!(req.limit, 0);
+        assert!(!req.count_total);
+        assert!(!req.reverse);
+
+        let req = PaginationOptions {
+            page_size: Some(25),
+            reverse: true,
+            cursor: Some(PageCursor::Key(vec![1, 2, 3])),
+            max_items: Some(10),
+        }
+        .first_page();
+        assert_eq!(req.key, vec![1, 2, 3]);
+        assert_eq!(req.offset, 0);
+        assert_eq!(req.limit, 25);
+        assert!(req.reverse);
+
+        let req = PaginationOptions {
+            cursor: Some(PageCursor::Offset(40)),
+            ..Default::default()
+        }
+        .first_page();
+        assert!(req.key.is_empty());
+        assert_eq!(req.offset, 40);
+    }
+
+    #[test]
+    fn stops_on_empty_next_key() {
+        let (items, requests) = collect(
+            PaginationOptions {
+                page_size: Some(3),
+                ..Default::default()
+            },
+            10,
+        );
+
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+        // 3 + 3 + 3 + 1, no extra request after the last page
+        assert_eq!(requests.len(), 4);
+        assert!(requests.iter().all(|req| req.limit == 3));
+        assert!(requests[0].key.is_empty());
+        assert_eq!(requests[1].key, 3u64.to_be_bytes());
+        assert_eq!(requests[3].key, 9u64.to_be_bytes());
+
+        // a full last page still ends on the empty key
+        let (items, requests) = collect(
+            PaginationOptions {
+                page_size: Some(5),
+                ..Default::default()
+            },
+            10,
+        );
+        assert_eq!(items.len(), 10);
+        assert_eq!(requests.len(), 2);
+
+        let (items, requests) = collect(PaginationOptions::default(), 0);
+        assert!(items.is_empty());
+        assert_eq!(requests.len(), 1);
+    }
+
+    #[test]
+    fn key_cursor() {
+        let (items, requests) = collect(
+            PaginationOptions {
+                page_size: Some(3),
+                cursor: Some(PageCursor::Key(6u64.to_be_bytes().to_vec())),
+                ..Default::default()
+            },
+            10,
+        );
+
+        assert_eq!(items, vec![6, 7, 8, 9]);
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].key, 6u64.to_be_bytes());
+        assert!(requests.iter().all(|req| req.offset == 0));
+    }
+
+    #[test]
+    fn offset_cursor() {
+        let (items, requests) = collect(
+            PaginationOptions {
+                page_size: Some(3),
+                cursor: Some(PageCursor::Offset(4)),
+                ..Default::default()
+            },
+            10,
+        );
+
+        assert_eq!(items, vec![4, 5, 6, 7, 8, 9]);
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].key.is_empty());
+        assert_eq!(requests[0].offset, 4);
+        // the following pages go by key, the offset would skip items again
+        assert_eq!(requests[1].key, 7u64.to_be_bytes());
+        assert_eq!(requests[1].offset, 0);
+    }
+
+    #[test]
+    fn reverse() {
+        let (items, requests) = collect(
+            PaginationOptions {
+                page_size: Some(4),
+                reverse: true,
+                ..Default::default()
+            },
+            10,
+        );
+
+        assert_eq!(items, (0..10).rev().collect::<Vec<_>>());
+        assert_eq!(requests.len(), 3);
+        assert!(requests.iter().all(|req| req.reverse));
+    }
+
+    #[test]
+    fn max_items() {
+        let (items, requests) = collect(
+            PaginationOptions {
+                page_size: Some(3),
+                max_items: Some(5),
+                ..Default::default()
+            },
+            10,
+        );
+
+        assert_eq!(items, vec![0, 1, 2, 3, 4]);
+        // no page is fetched past the one that reaches the limit
+        assert_eq!(requests.len(), 2);
+
+        let (items, requests) = collect(
+            PaginationOptions {
+                page_size: Some(3),
+                max_items: Some(6),
+                ..Default::default()
+            },
+            10,
+        );
+        assert_eq!(items.len(), 6);
+        assert_eq!(requests.len(), 2);
+
+        let (items, _) = collect(
+            PaginationOptions {
+                max_items: Some(50),
+                ..Default::default()
+            },
+            10,
+        );
+        assert_eq!(items.len(), 10);
+    }
+
+    #[test]
+    fn ends_after_error() {
+        let mut pages = 0;
+
+        let results: Vec<Result<u64>> = block_on(
+            paginate(
+                pinned_client(),
+                PaginationOptions {
+                    page_size: Some(2),
+                    ..Default::default()
+                },
+                |_, req| {
+                    pages += 1;
+                    let page = match pages {
+                        2 => Err(anyhow!("page failed")),
+                        _ => Ok(fake_page(10, &req)),
+                    };
+                    async move { page }
+                },
+            )
+            .collect(),
+        );
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(*results[0].as_ref().unwrap(), 0);
+        assert_eq!(*results[1].as_ref().unwrap(), 1);
+        assert!(results[2].is_err());
+        assert_eq!(pages, 2);
+    }
+
+    #[test]
+    fn unpinned_reads_height_first() {
+        // the fake rpc can't answer the block query, so nothing past it should run
+        let mut pages = 0;
+
+        let results: Vec<Result<u64>> = block_on(
+            paginate(
+                query_client(|path, _, _| panic!("unexpected query {path}")),
+                PaginationOptions::default(),
+                |_, req| {
+                    pages += 1;
+                    let page = fake_page(10, &req);
+                    async move { Ok(page) }
+                },
+            )
+            .collect(),
+        );
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+        assert_eq!(pages, 0);
+    }
+
+    #[test]
+    fn abci() {
+        let heights = Arc::new(Mutex::new(Vec::new()));
+
+        let client = query_client({
+            let heights = heights.clone();
+            move |path, data, height| {
+                assert_eq!(path, "/cosmos.bank.v1beta1.Query/DenomsMetadata");
+                heights.lock().unwrap().push(height);
+
+                let req = QueryDenomsMetadataRequest::decode(data).unwrap();
+                let page = fake_page(5, &req.pagination.unwrap());
+
+                Ok(QueryDenomsMetadataResponse {
+                    metadatas: page
+                        .items
+                        .into_iter()
+                        .map(|i| Metadata {
+                            base: format!("denom-{i}"),
+                            ..Default::default()
+                        })
+                        .collect(),
+                    pagination: Some(PageResponse {
+                        next_key: page.next_key,
+                        total: 0,
+                    }),
+                }
+                .encode_to_vec())
+            }
+        });
+
+        let denoms: Vec<String> = block_on(
+            client
+                .at_height(HEIGHT)
+                .paginate_abci::<_, QueryDenomsMetadataResponse>(
+                    "/cosmos.bank.v1beta1.Query/DenomsMetadata",
+                    |pagination| QueryDenomsMetadataRequest {
+                        pagination: Some(pagination),
+                    },
+                    PaginationOptions {
+                        page_size: Some(2),
+                        reverse: true,
+                        ..Default::default()
+                    },
+                )
+                .map_ok(|metadata| metadata.base)
+                .try_collect(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            denoms,
+            vec!["denom-4", "denom-3", "denom-2", "denom-1", "denom-0"]
+        );
+        // every page is read at the pinned height
+        assert_eq!(*heights.lock().unwrap(), vec![Some(HEIGHT); 3]);
+    }
+}
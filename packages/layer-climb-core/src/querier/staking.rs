@@ -0,0 +1,509 @@
+use futures::{Stream, TryStreamExt};
+use tracing::instrument;
+
+use crate::prelude::*;
+
+use super::pagination::{paginate, Page, PaginatedResponse, PaginationOptions};
+
+// validator addresses are the operator (e.g. `cosmosvaloper1...`) addresses, which don't fit the chain's `AddrKind`
+// so they're taken as plain strings
+
+impl QueryClient {
+    /// All the validators, optionally only those with the given status
+    #[instrument]
+    pub async fn validators(
+        &self,
+        status: Option<layer_climb_proto::staking::BondStatus>,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<layer_climb_proto::staking::Validator>> {
+        self.run_with_middleware(StakingValidatorsReq {
+            status,
+            limit_per_page,
+        })
+        .await
+    }
+
+    /// Like `validators()`, but as a stream that fetches pages as it goes
+    pub fn stream_validators(
+        &self,
+        status: Option<layer_climb_proto::staking::BondStatus>,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<layer_climb_proto::staking::Validator>> {
+        paginate(self.clone(), options, move |client, pagination| {
+            let req = StakingValidatorsPageReq { status, pagination };
+            async move { client.run_with_middleware(req).await }
+        })
+    }
+
+    #[instrument]
+    pub async fn validator(
+        &self,
+        validator_address: impl ToString + std::fmt::Debug,
+    ) -> Result<layer_climb_proto::staking::Validator> {
+        self.run_with_middleware(StakingValidatorReq {
+            validator_address: validator_address.to_string(),
+        })
+        .await
+    }
+
+    /// All the delegations made by `delegator`, along with their balances
+    #[instrument]
+    pub async fn delegations(
+        &self,
+        delegator: Address,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<layer_climb_proto::staking::DelegationResponse>> {
+        self.run_with_middleware(StakingDelegationsReq {
+            delegator,
+            limit_per_page,
+        })
+        .await
+    }
+
+    /// All the delegations `delegator` is currently unbonding
+    #[instrument]
+    pub async fn unbonding_delegations(
+        &self,
+        delegator: Address,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<layer_climb_proto::staking::UnbondingDelegation>> {
+        self.run_with_middleware(StakingUnbondingDelegationsReq {
+            delegator,
+            limit_per_page,
+        })
+        .await
+    }
+
+    /// All the redelegations `delegator` has in progress
+    #[instrument]
+    pub async fn redelegations(
+        &self,
+        delegator: Address,
+        limit_per_page: Option<u64>,
+    ) -> Result<Vec<layer_climb_proto::staking::RedelegationResponse>> {
+        self.run_with_middleware(StakingRedelegationsReq {
+            delegator,
+            limit_per_page,
+        })
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StakingValidatorsReq {
+    /// if None, validators of any status
+    pub status: Option<layer_climb_proto::staking::BondStatus>,
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for StakingValidatorsReq {
+    type QueryResponse = Vec<layer_climb_proto::staking::Validator>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = StakingValidatorsPageReq {
+                status: self.status,
+                pagination,
+            };
+            async move { req.request(client).await }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StakingValidatorsPageReq {
+    /// if None, validators of any status
+    pub status: Option<layer_climb_proto::staking::BondStatus>,
+    pub pagination: layer_climb_proto::query::PageRequest,
+}
+
+impl QueryRequest for StakingValidatorsPageReq {
+    type QueryResponse = Page<layer_climb_proto::staking::Validator>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::staking::QueryValidatorsRequest {
+            status: self
+                .status
+                .map(|status| status.as_str_name().to_string())
+                .unwrap_or_default(),
+            pagination: Some(self.pagination.clone()),
+        };
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client = layer_climb_proto::staking::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                );
+
+                query_client
+                    .validators(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner())
+                    .context("couldn't get validators")?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::staking::QueryValidatorsResponse>(
+                    "/cosmos.staking.v1beta1.Query/Validators",
+                    req,
+                    client.query_height(),
+                )
+                .await
+                .context("couldn't get validators")?,
+        };
+
+        Ok(resp.into_page())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StakingValidatorReq {
+    pub validator_address: String,
+}
+
+impl QueryRequest for StakingValidatorReq {
+    type QueryResponse = layer_climb_proto::staking::Validator;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let req = layer_climb_proto::staking::QueryValidatorRequest {
+            validator_addr: self.validator_address.clone(),
+        };
+
+        let resp = match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                let mut query_client = layer_climb_proto::staking::query_client::QueryClient::new(
+                    client.clone_grpc_channel()?,
+                );
+
+                query_client
+                    .validator(client.grpc_request(req)?)
+                    .await
+                    .map(|res| res.into_inner())
+                    .with_context(|| format!("couldn't get validator {}", self.validator_address))?
+            }
+            ConnectionMode::Rpc => client
+                .rpc_client()?
+                .abci_protobuf_query::<_, layer_climb_proto::staking::QueryValidatorResponse>(
+                    "/cosmos.staking.v1beta1.Query/Validator",
+                    req,
+                    client.query_height(),
+                )
+                .await
+                .with_context(|| format!("couldn't get validator {}", self.validator_address))?,
+        };
+
+        resp.validator
+            .ok_or_else(|| anyhow!("validator {} not found", self.validator_address))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StakingDelegationsReq {
+    pub delegator: Address,
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for StakingDelegationsReq {
+    type QueryResponse = Vec<layer_climb_proto::staking::DelegationResponse>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = layer_climb_proto::staking::QueryDelegatorDelegationsRequest {
+                delegator_addr: self.delegator.to_string(),
+                pagination: Some(pagination),
+            };
+
+            async move {
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            layer_climb_proto::staking::query_client::QueryClient::new(
+                                client.clone_grpc_channel()?,
+                            );
+
+                        query_client
+                            .delegator_delegations(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .context("couldn't get delegations")?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, layer_climb_proto::staking::QueryDelegatorDelegationsResponse>(
+                            "/cosmos.staking.v1beta1.Query/DelegatorDelegations",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .context("couldn't get delegations")?,
+                };
+
+                Ok(resp.into_page())
+            }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StakingUnbondingDelegationsReq {
+    pub delegator: Address,
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for StakingUnbondingDelegationsReq {
+    type QueryResponse = Vec<layer_climb_proto::staking::UnbondingDelegation>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = layer_climb_proto::staking::QueryDelegatorUnbondingDelegationsRequest {
+                delegator_addr: self.delegator.to_string(),
+                pagination: Some(pagination),
+            };
+
+            async move {
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            layer_climb_proto::staking::query_client::QueryClient::new(
+                                client.clone_grpc_channel()?,
+                            );
+
+                        query_client
+                            .delegator_unbonding_delegations(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .context("couldn't get unbonding delegations")?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, layer_climb_proto::staking::QueryDelegatorUnbondingDelegationsResponse>(
+                            "/cosmos.staking.v1beta1.Query/DelegatorUnbondingDelegations",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .context("couldn't get unbonding delegations")?,
+                };
+
+                Ok(resp.into_page())
+            }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StakingRedelegationsReq {
+    pub delegator: Address,
+    pub limit_per_page: Option<u64>,
+}
+
+impl QueryRequest for StakingRedelegationsReq {
+    type QueryResponse = Vec<layer_climb_proto::staking::RedelegationResponse>;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        let options = PaginationOptions {
+            page_size: self.limit_per_page,
+            ..Default::default()
+        };
+
+        paginate(client, options, |client, pagination| {
+            let req = layer_climb_proto::staking::QueryRedelegationsRequest {
+                delegator_addr: self.delegator.to_string(),
+                src_validator_addr: String::new(),
+                dst_validator_addr: String::new(),
+                pagination: Some(pagination),
+            };
+
+            async move {
+                let resp = match client.get_connection_mode() {
+                    ConnectionMode::Grpc => {
+                        let mut query_client =
+                            layer_climb_proto::staking::query_client::QueryClient::new(
+                                client.clone_grpc_channel()?,
+                            );
+
+                        query_client
+                            .redelegations(client.grpc_request(req)?)
+                            .await
+                            .map(|res| res.into_inner())
+                            .context("couldn't get redelegations")?
+                    }
+                    ConnectionMode::Rpc => client
+                        .rpc_client()?
+                        .abci_protobuf_query::<_, layer_climb_proto::staking::QueryRedelegationsResponse>(
+                            "/cosmos.staking.v1beta1.Query/Redelegations",
+                            req,
+                            client.query_height(),
+                        )
+                        .await
+                        .context("couldn't get redelegations")?,
+                };
+
+                Ok(resp.into_page())
+            }
+        })
+        .try_collect()
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use layer_climb_proto::{
+        query::PageResponse,
+        staking::{
+            BondStatus, Delegation, DelegationResponse, QueryDelegatorDelegationsRequest,
+            QueryDelegatorDelegationsResponse, QueryValidatorRequest, QueryValidatorResponse,
+            QueryValidatorsRequest, QueryValidatorsResponse, Validator,
+        },
+    };
+
+    use super::*;
+    use crate::testing::{address, query_client, HEIGHT};
+
+    fn validator(n: u8) -> Validator {
+        Validator {
+            operator_address: format!("cosmosvaloper{n}"),
+            status: BondStatus::Bonded.into(),
+            ..Default::default()
+        }
+    }
+
+    // validators 1..=3, two per page, keyed by the next validator
+    fn validators_page(
+        pagination: Option<layer_climb_proto::query::PageRequest>,
+    ) -> (Vec<u8>, Vec<u8>) {
+        match pagination.unwrap().key.as_slice() {
+            [] => (vec![1, 2], vec![3]),
+            [3] => (vec![3], Vec::new()),
+            key => panic!("unexpected key {key:?}"),
+        }
+    }
+
+    #[test]
+    fn validators() {
+        let client = query_client(|path, data, _| {
+            assert_eq!(path, "/cosmos.staking.v1beta1.Query/Validators");
+            let req = QueryValidatorsRequest::decode(data).unwrap();
+
+            let (validators, next_key) = match req.status.as_str() {
+                "" => validators_page(req.pagination),
+                // only the first one is bonded
+                "BOND_STATUS_BONDED" => (vec![1], Vec::new()),
+                status => panic!("unexpected status {status}"),
+            };
+
+            Ok(QueryValidatorsResponse {
+                validators: validators.into_iter().map(validator).collect(),
+                pagination: Some(PageResponse { next_key, total: 0 }),
+            }
+            .encode_to_vec())
+        })
+        // otherwise the pages are pinned to the latest block height, which the fake rpc can't answer
+        .at_height(HEIGHT);
+
+        let validators = block_on(client.validators(None, Some(2))).unwrap();
+        assert_eq!(validators, vec![validator(1), validator(2), validator(3)]);
+
+        let validators = block_on(client.validators(Some(BondStatus::Bonded), None)).unwrap();
+        assert_eq!(validators, vec![validator(1)]);
+
+        let validators: Vec<Validator> = block_on(
+            client
+                .stream_validators(
+                    None,
+                    PaginationOptions {
+                        page_size: Some(2),
+                        max_items: Some(2),
+                        ..Default::default()
+                    },
+                )
+                .try_collect(),
+        )
+        .unwrap();
+        assert_eq!(validators, vec![validator(1), validator(2)]);
+    }
+
+    #[test]
+    fn validator_not_found() {
+        let client = query_client(|path, data, _| {
+            assert_eq!(path, "/cosmos.staking.v1beta1.Query/Validator");
+            let req = QueryValidatorRequest::decode(data).unwrap();
+
+            Ok(QueryValidatorResponse {
+                validator: (req.validator_addr == "cosmosvaloper1").then(|| validator(1)),
+            }
+            .encode_to_vec())
+        });
+
+        assert_eq!(
+            block_on(client.validator("cosmosvaloper1")).unwrap(),
+            validator(1)
+        );
+
+        let err = block_on(client.validator("cosmosvaloper2")).unwrap_err();
+        assert!(err.to_string().contains("not found"), "{err}");
+    }
+
+    #[test]
+    fn delegations() {
+        let client = query_client(|path, data, height| {
+            assert_eq!(path, "/cosmos.staking.v1beta1.Query/DelegatorDelegations");
+            assert_eq!(height, Some(HEIGHT));
+
+            let req = QueryDelegatorDelegationsRequest::decode(data).unwrap();
+            assert_eq!(req.delegator_addr, address(0).to_string());
+
+            let (validators, next_key) = validators_page(req.pagination);
+
+            Ok(QueryDelegatorDelegationsResponse {
+                delegation_responses: validators
+                    .into_iter()
+                    .map(|n| DelegationResponse {
+                        delegation: Some(Delegation {
+                            delegator_address: req.delegator_addr.clone(),
+                            validator_address: format!("cosmosvaloper{n}"),
+                            shares: "1000000000000000000000".to_string(),
+                        }),
+                        balance: Some(new_coin(1000, "uatom")),
+                    })
+                    .collect(),
+                pagination: Some(PageResponse { next_key, total: 0 }),
+            }
+            .encode_to_vec())
+        });
+
+        let delegations =
+            block_on(client.at_height(HEIGHT).delegations(address(0), Some(2))).unwrap();
+
+        assert_eq!(
+            delegations
+                .iter()
+                .map(|d| d.delegation.as_ref().unwrap().validator_address.as_str())
+                .collect::<Vec<_>>(),
+            vec!["cosmosvaloper1", "cosmosvaloper2", "cosmosvaloper3"]
+        );
+    }
+}
@@ -3,7 +3,7 @@
 
 use std::time::Duration;
 
-use crate::prelude::*;
+use crate::{network::rpc::AbciQueryError, prelude::*};
 
 impl QueryClient {
     pub async fn simulate_tx(
@@ -23,13 +23,26 @@ impl QueryClient {
                     .simulate(req)
                     .await
                     .map(|res| res.into_inner())
-                    .map_err(|e| anyhow!("couldn't simulate tx: {e:?}"))
+                    .map_err(|status| match status.code() {
+                        // what the sdk returns when the tx itself failed
+                        tonic::Code::Unknown => {
+                            ClimbError::from(TxError::simulation(status.message(), None)).into()
+                        }
+                        _ => anyhow!("couldn't simulate tx: {status:?}"),
+                    })
             }
             ConnectionMode::Rpc => self
                 .rpc_client()?
                 .abci_protobuf_query("/cosmos.tx.v1beta1.Service/Simulate", req, None)
                 .await
-                .map_err(|e| anyhow!("couldn't simulate tx: {e:?}")),
+                .map_err(|err| match err.downcast_ref::<AbciQueryError>() {
+                    Some(err) => ClimbError::from(TxError::simulation(
+                        &err.log,
+                        Some((&err.codespace, err.code)),
+                    ))
+                    .into(),
+                    None => anyhow!("couldn't simulate tx: {err:?}"),
+                }),
         }
     }
 
@@ -155,7 +168,11 @@ impl QueryClient {
             futures_timer::Delay::new(sleep_duration).await;
             total_duration += sleep_duration;
             if total_duration >= timeout_duration {
-                return Err(anyhow!("timeout"));
+                return Err(ClimbError::PollTimeout {
+                    tx_hash,
+                    elapsed: total_duration,
+                }
+                .into());
             }
         }
     }
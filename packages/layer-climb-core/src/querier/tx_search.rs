@@ -0,0 +1,423 @@
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use layer_climb_proto::{Message, Name};
+
+use crate::prelude::*;
+
+use super::pagination::{PageCursor, PaginationOptions};
+
+// the most the rpc allows per page
+const MAX_TX_SEARCH_PAGE_SIZE: u64 = 100;
+const DEFAULT_TX_SEARCH_PAGE_SIZE: u64 = 50;
+
+/// An event query for `QueryClient::search_txs()`, all the conditions must match
+///
+/// e.g. `TxQuery::new().sender(&addr).contract(&contract_addr)`
+#[derive(Clone, Debug, Default)]
+pub struct TxQuery {
+    conditions: Vec<String>,
+    // set by `search_txs()`, so paging doesn't pick up txs that land while it's running
+    pinned_height: Option<u64>,
+}
+
+impl TxQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `{event_type}.{attr_key}='{value}'`, errors if the value contains a single quote
+    /// since the query syntax has no way to escape it
+    pub fn attr(self, event_type: &str, attr_key: &str, value: impl ToString) -> Result<Self> {
+        let value = value.to_string();
+        if value.contains('\'') {
+            bail!("tx query values can't contain single quotes: {value}");
+        }
+
+        Ok(self.quoted_attr(event_type, attr_key, &value))
+    }
+
+    // for values that can't contain a quote, e.g. addresses
+    fn quoted_attr(self, event_type: &str, attr_key: &str, value: &str) -> Self {
+        self.condition(format!("{event_type}.{attr_key}='{value}'"))
+    }
+
+    /// Any condition in the cometbft query syntax, e.g. `transfer.amount>1000`
+    pub fn condition(mut self, condition: impl ToString) -> Self {
+        self.conditions.push(condition.to_string());
+        self
+    }
+
+    /// `message.sender='{addr}'`
+    pub fn sender(self, addr: &Address) -> Self {
+        self.quoted_attr("message", "sender", &addr.to_string())
+    }
+
+    /// `message.action='{type_url}'`, e.g. `/cosmwasm.wasm.v1.MsgExecuteContract`
+    pub fn message_type(self, type_url: impl ToString) -> Result<Self> {
+        self.attr("message", "action", type_url)
+    }
+
+    /// `wasm._contract_address='{addr}'`, i.e. txs where the contract emitted attributes
+    pub fn contract(self, addr: &Address) -> Self {
+        self.quoted_attr("wasm", "_contract_address", &addr.to_string())
+    }
+
+    /// `execute._contract_address='{addr}'`, i.e. every execution of the contract
+    pub fn contract_executed(self, addr: &Address) -> Self {
+        self.quoted_attr("execute", "_contract_address", &addr.to_string())
+    }
+
+    /// `tx.height>={height}`
+    pub fn min_height(self, height: u64) -> Self {
+        self.condition(format!("tx.height>={height}"))
+    }
+
+    /// `tx.height<={height}`
+    pub fn max_height(self, height: u64) -> Self {
+        self.condition(format!("tx.height<={height}"))
+    }
+
+    pub fn conditions(&self) -> &[String] {
+        &self.conditions
+    }
+
+    /// The conditions cosmos-sdk before 0.50 accepts in `GetTxsEventRequest.events`,
+    /// i.e. only `{event_type}.{attr_key}={value}`, it rejects anything else (like `tx.height>=10`)
+    pub fn legacy_events(&self) -> Vec<String> {
+        self.conditions
+            .iter()
+            .filter(|condition| is_legacy_event(condition))
+            .cloned()
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+    }
+}
+
+// the sdk checks events against `^[a-zA-Z_]+\.[a-zA-Z_]+=\S+$`
+fn is_legacy_event(condition: &str) -> bool {
+    let Some((key, value)) = condition.split_once('=') else {
+        return false;
+    };
+    let Some((event_type, attr_key)) = key.split_once('.') else {
+        return false;
+    };
+
+    let is_name = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphabetic() || c == '_');
+
+    is_name(event_type)
+        && is_name(attr_key)
+        && !value.is_empty()
+        && !value.chars().any(char::is_whitespace)
+}
+
+impl std::fmt::Display for TxQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.conditions.join(" AND "))?;
+
+        if let Some(height) = self.pinned_height {
+            write!(f, " AND tx.height<={height}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SearchTxResponse {
+    pub tx: layer_climb_proto::tx::Tx,
+    /// over rpc, the `timestamp` and `logs` are left empty
+    pub tx_response: layer_climb_proto::abci::TxResponse,
+}
+
+impl QueryClient {
+    /// Streams the txs matching `query`, oldest first unless `options.reverse` is set
+    ///
+    /// Tx search pages by number, so `options.cursor` can only be an offset
+    /// the search is limited to txs up to the height when it started, so new txs don't shift the pages
+    /// over grpc this requires cosmos-sdk 0.47 or later, and the node must index txs either way
+    ///
+    /// Before cosmos-sdk 0.50, grpc only applies the `key='value'` conditions (see `TxQuery::legacy_events()`),
+    /// height ranges and other comparisons are dropped. The starting height is still enforced on the results,
+    /// but with `reverse` set new txs can shift the pages there
+    pub fn search_txs(
+        &self,
+        query: TxQuery,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = Result<SearchTxResponse>> {
+        let limit = options
+            .page_size
+            .unwrap_or(DEFAULT_TX_SEARCH_PAGE_SIZE)
+            .clamp(1, MAX_TX_SEARCH_PAGE_SIZE);
+
+        let (first_page, skip) = match search_start(options.cursor.as_ref(), limit) {
+            Ok((first_page, skip)) => (Ok(first_page), skip),
+            Err(err) => (Err(err), 0),
+        };
+
+        let reverse = options.reverse;
+
+        // the query is only pinned to a height once the first page is requested
+        stream::try_unfold(
+            (self.clone(), query, Some(first_page)),
+            move |(client, mut query, page)| async move {
+                let Some(page) = page else {
+                    return Ok::<_, anyhow::Error>(None);
+                };
+                let page = page?;
+
+                if query.is_empty() {
+                    bail!("tx search needs at least one condition");
+                }
+
+                if query.pinned_height.is_none() {
+                    query.pinned_height = Some(client.block_height().await?);
+                }
+
+                let mut resp = client
+                    .run_with_middleware(SearchTxsPageReq {
+                        query: query.to_string(),
+                        events: query.legacy_events(),
+                        page,
+                        limit,
+                        reverse,
+                    })
+                    .await?;
+
+                let next_page = match resp.txs.is_empty() || page * limit >= resp.total {
+                    true => None,
+                    false => Some(Ok(page + 1)),
+                };
+
+                // older nodes over grpc don't apply the height condition
+                if let Some(pinned_height) = query.pinned_height {
+                    resp.txs.retain(|tx| {
+                        u64::try_from(tx.tx_response.height)
+                            .is_ok_and(|height| height <= pinned_height)
+                    });
+                }
+
+                Ok(Some((resp.txs, (client, query, next_page))))
+            },
+        )
+        .map_ok(|txs| stream::iter(txs.into_iter().map(Ok)))
+        .try_flatten()
+        .skip(skip as usize)
+        .take(options.max_items.unwrap_or(usize::MAX))
+    }
+}
+
+// the page to start from (pages start at 1) and how many txs to skip on it
+fn search_start(cursor: Option<&PageCursor>, limit: u64) -> Result<(u64, u64)> {
+    match cursor {
+        None => Ok((1, 0)),
+        Some(PageCursor::Offset(offset)) => Ok((offset / limit + 1, offset % limit)),
+        Some(PageCursor::Key(_)) => Err(anyhow!("tx search can't start from a key")),
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SearchTxsPage {
+    pub txs: Vec<SearchTxResponse>,
+    /// the number of matching txs over all pages
+    pub total: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct SearchTxsPageReq {
+    pub query: String,
+    /// what's sent to cosmos-sdk before 0.50 over grpc instead of `query`, see `TxQuery::legacy_events()`
+    pub events: Vec<String>,
+    /// starts at 1
+    pub page: u64,
+    pub limit: u64,
+    /// newest first
+    pub reverse: bool,
+}
+
+impl QueryRequest for SearchTxsPageReq {
+    type QueryResponse = SearchTxsPage;
+
+    async fn request(&self, client: QueryClient) -> Result<Self::QueryResponse> {
+        if self.query.is_empty() {
+            bail!("tx search needs at least one condition");
+        }
+
+        match client.get_connection_mode() {
+            ConnectionMode::Grpc => {
+                #[allow(deprecated)]
+                let req = layer_climb_proto::tx::GetTxsEventRequest {
+                    // older nodes only look at the events, newer ones only at the query
+                    events: self.events.clone(),
+                    pagination: None,
+                    order_by: match self.reverse {
+                        true => layer_climb_proto::tx::OrderBy::Desc,
+                        false => layer_climb_proto::tx::OrderBy::Asc,
+                    }
+                    .into(),
+                    page: self.page,
+                    limit: self.limit,
+                    query: self.query.clone(),
+                };
+
+                let mut query_client = layer_climb_proto::tx::service_client::ServiceClient::new(
+                    client.clone_grpc_channel()?,
+                );
+
+                let resp = query_client
+                    .get_txs_event(req)
+                    .await
+                    .map(|res| res.into_inner())
+                    .context("couldn't search txs")?;
+
+                Ok(SearchTxsPage {
+                    txs: resp
+                        .txs
+                        .into_iter()
+                        .zip(resp.tx_responses)
+                        .map(|(tx, tx_response)| SearchTxResponse { tx, tx_response })
+                        .collect(),
+                    total: resp.total,
+                })
+            }
+            ConnectionMode::Rpc => {
+                let resp = client
+                    .rpc_client()?
+                    .tx_search(
+                        &self.query,
+                        self.page.try_into()?,
+                        self.limit.try_into()?,
+                        match self.reverse {
+                            true => tendermint_rpc::Order::Descending,
+                            false => tendermint_rpc::Order::Ascending,
+                        },
+                    )
+                    .await
+                    .context("couldn't search txs")?;
+
+                Ok(SearchTxsPage {
+                    txs: resp
+                        .txs
+                        .into_iter()
+                        .map(search_tx_response_from_rpc)
+                        .collect::<Result<_>>()?,
+                    total: resp.total_count.into(),
+                })
+            }
+        }
+    }
+}
+
+fn search_tx_response_from_rpc(
+    resp: tendermint_rpc::endpoint::tx::Response,
+) -> Result<SearchTxResponse> {
+    let tx = layer_climb_proto::tx::Tx::decode(resp.tx.as_slice())
+        .with_context(|| format!("couldn't decode tx {}", resp.hash))?;
+
+    let result = resp.tx_result;
+
+    let tx_response = layer_climb_proto::abci::TxResponse {
+        height: resp.height.value().try_into()?,
+        txhash: resp.hash.to_string(),
+        codespace: result.codespace,
+        code: result.code.value(),
+        data: const_hex::encode_upper(&result.data),
+        raw_log: result.log,
+        logs: Vec::new(),
+        info: result.info,
+        gas_wanted: result.gas_wanted,
+        gas_used: result.gas_used,
+        tx: Some(layer_climb_proto::Any {
+            type_url: layer_climb_proto::tx::Tx::type_url(),
+            value: resp.tx,
+        }),
+        timestamp: String::new(),
+        events: result.events.into_iter().map(Into::into).collect(),
+    };
+
+    Ok(SearchTxResponse { tx, tx_response })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn query_string() {
+        let query = TxQuery::new()
+            .message_type("/cosmwasm.wasm.v1.MsgExecuteContract")
+            .unwrap()
+            .attr("transfer", "amount", "100uatom")
+            .unwrap()
+            .min_height(10)
+            .condition("transfer.amount>1000");
+
+        assert_eq!(
+            query.to_string(),
+            "message.action='/cosmwasm.wasm.v1.MsgExecuteContract' AND transfer.amount='100uatom' AND tx.height>=10 AND transfer.amount>1000"
+        );
+
+        let pinned = TxQuery {
+            pinned_height: Some(42),
+            ..query
+        };
+        assert!(pinned.to_string().ends_with(" AND tx.height<=42"));
+    }
+
+    #[test]
+    fn legacy_events() {
+        let query = TxQuery::new()
+            .message_type("/cosmwasm.wasm.v1.MsgExecuteContract")
+            .unwrap()
+            .attr("wasm", "_contract_address", "cosmos1contract")
+            .unwrap()
+            .min_height(10)
+            .condition("transfer.amount>1000")
+            .attr("transfer", "amount", "100 uatom")
+            .unwrap();
+
+        assert_eq!(
+            query.legacy_events(),
+            vec![
+                "message.action='/cosmwasm.wasm.v1.MsgExecuteContract'",
+                "wasm._contract_address='cosmos1contract'",
+            ]
+        );
+
+        // the pinned height is only part of the query
+        let pinned = TxQuery {
+            pinned_height: Some(42),
+            ..query
+        };
+        assert_eq!(pinned.legacy_events().len(), 2);
+    }
+
+    #[test]
+    fn attr_rejects_quotes() {
+        assert!(TxQuery::new().attr("wasm", "action", "it's").is_err());
+        assert!(TxQuery::new().message_type("/x' OR 'a'='a").is_err());
+    }
+
+    #[test]
+    fn start_from_offset() {
+        assert_eq!(search_start(None, 50).unwrap(), (1, 0));
+        assert_eq!(
+            search_start(Some(&PageCursor::Offset(0)), 50).unwrap(),
+            (1, 0)
+        );
+        assert_eq!(
+            search_start(Some(&PageCursor::Offset(49)), 50).unwrap(),
+            (1, 49)
+        );
+        assert_eq!(
+            search_start(Some(&PageCursor::Offset(50)), 50).unwrap(),
+            (2, 0)
+        );
+        assert_eq!(
+            search_start(Some(&PageCursor::Offset(120)), 50).unwrap(),
+            (3, 20)
+        );
+        assert!(search_start(Some(&PageCursor::Key(vec![1])), 50).is_err());
+    }
+}
@@ -2,7 +2,7 @@ use tracing::instrument;
 
 use crate::prelude::*;
 
-use super::basic::BlockHeightReq;
+use super::{basic::BlockHeightReq, middleware::cache::QueryCachePolicy};
 
 impl QueryClient {
     #[instrument]
@@ -13,26 +13,33 @@ impl QueryClient {
     ) -> Result<layer_climb_proto::tendermint::ValidatorSet> {
         self.run_with_middleware(ValidatorSetReq {
             height,
-            proposer_address,
+            proposer_address: proposer_address.map(|addr| addr.to_vec()),
         })
         .await
     }
 }
 
 #[derive(Clone, Debug)]
-pub struct ValidatorSetReq<'a> {
+pub struct ValidatorSetReq {
     pub height: Option<u64>,
-    pub proposer_address: Option<&'a [u8]>,
+    pub proposer_address: Option<Vec<u8>>,
 }
 
-impl QueryRequest for ValidatorSetReq<'_> {
+impl QueryRequest for ValidatorSetReq {
     type QueryResponse = layer_climb_proto::tendermint::ValidatorSet;
 
+    fn cache_policy(&self) -> QueryCachePolicy {
+        match self.height {
+            Some(_) => QueryCachePolicy::Forever,
+            None => QueryCachePolicy::UntilNextBlock,
+        }
+    }
+
     async fn request(
         &self,
         client: QueryClient,
     ) -> Result<layer_climb_proto::tendermint::ValidatorSet> {
-        let height = match self.height {
+        let height = match self.height.or(client.query_height()) {
             Some(height) => height,
             None => BlockHeightReq {}.request(client.clone()).await?,
         };
@@ -44,7 +51,8 @@ impl QueryRequest for ValidatorSetReq<'_> {
 
         let proposer_address = self
             .proposer_address
-            .map(|addr| tendermint::account::Id::try_from(addr.to_vec()))
+            .clone()
+            .map(tendermint::account::Id::try_from)
             .transpose()?;
 
         let mut grpc_query_client = match client.get_connection_mode() {
@@ -1,8 +1,12 @@
 pub mod authz;
 pub mod contract;
+pub mod distribution;
+pub mod feegrant;
+pub mod gov;
 pub mod ibc;
 pub mod middleware;
 pub mod msg;
+pub mod staking;
 
 use crate::{
     cache::ClimbCache,
@@ -11,7 +15,9 @@ use crate::{
     transaction::{SequenceStrategy, SequenceStrategyKind},
 };
 use layer_climb_signer::TxSigner;
-use middleware::{SigningMiddlewareMapBody, SigningMiddlewareMapResp};
+use middleware::{
+    SigningMiddlewareMapAuthInfo, SigningMiddlewareMapBody, SigningMiddlewareMapResp,
+};
 use std::sync::Arc;
 use tracing::instrument;
 
@@ -22,10 +28,12 @@ pub struct SigningClient {
     pub signer: Arc<dyn TxSigner>,
     pub addr: Address,
     pub account_number: u64,
-    /// Middleware to run before the tx is broadcast
-    pub middleware_map_body: Arc<Vec<SigningMiddlewareMapBody>>,
+    /// Middleware to run on the body before the tx is simulated and signed
+    pub middleware_map_body: Arc<Vec<Arc<dyn SigningMiddlewareMapBody>>>,
+    /// Middleware to run on the auth info (i.e. once the fee is known) before the tx is signed
+    pub middleware_map_auth_info: Arc<Vec<Arc<dyn SigningMiddlewareMapAuthInfo>>>,
     /// Middleware to run after the tx is broadcast
-    pub middleware_map_resp: Arc<Vec<SigningMiddlewareMapResp>>,
+    pub middleware_map_resp: Arc<Vec<Arc<dyn SigningMiddlewareMapResp>>>,
     /// Strategy for determining the sequence number for txs
     /// it will be applied when calling `tx_builder()`
     /// (i.e. it's always possible to manually construct a TxBuilder and override it)
@@ -65,7 +73,7 @@ impl SigningClient {
         cache: ClimbCache,
         connection: Option<Connection>,
     ) -> Result<Self> {
-        let addr = chain_config.address_from_pub_key(&signer.public_key().await?)?;
+        let addr = signer.address(&chain_config).await?;
 
         let querier = QueryClient::new_with_cache(chain_config.clone(), cache, connection).await?;
 
@@ -76,8 +84,9 @@ impl SigningClient {
             querier,
             addr,
             account_number: base_account.account_number,
-            middleware_map_body: Arc::new(middleware::SigningMiddlewareMapBody::default_list()),
-            middleware_map_resp: Arc::new(middleware::SigningMiddlewareMapResp::default_list()),
+            middleware_map_body: Arc::new(middleware::default_middleware_map_body()),
+            middleware_map_auth_info: Arc::new(middleware::default_middleware_map_auth_info()),
+            middleware_map_resp: Arc::new(middleware::default_middleware_map_resp()),
             sequence_strategy: SequenceStrategy::new(SequenceStrategyKind::Query),
         })
     }
@@ -86,10 +95,7 @@ impl SigningClient {
     // such as when using browser-based async wallets like Keplr
     #[instrument]
     pub async fn refresh_signer(&mut self) -> Result<()> {
-        self.addr = self
-            .querier
-            .chain_config
-            .address_from_pub_key(&self.signer.public_key().await?)?;
+        self.addr = self.signer.address(&self.querier.chain_config).await?;
 
         self.account_number = self.querier.base_account(&self.addr).await?.account_number;
 
@@ -116,6 +122,10 @@ impl SigningClient {
             tx_builder.set_middleware_map_body(self.middleware_map_body.clone());
         }
 
+        if !self.middleware_map_auth_info.is_empty() {
+            tx_builder.set_middleware_map_auth_info(self.middleware_map_auth_info.clone());
+        }
+
         if !self.middleware_map_resp.is_empty() {
             tx_builder.set_middleware_map_resp(self.middleware_map_resp.clone());
         }
@@ -141,4 +151,24 @@ impl SigningClient {
 
         Ok(res)
     }
+
+    /// Pays many recipients in a single tx, see `multi_send_msg()`
+    pub async fn multi_send(
+        &self,
+        outputs: Vec<(Address, Vec<layer_climb_proto::Coin>)>,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        tracing::debug!(
+            "multi send to {} recipients from {}",
+            outputs.len(),
+            self.addr
+        );
+
+        let res = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([proto_into_any(&self.multi_send_msg(outputs)?)?])
+            .await?;
+
+        Ok(res)
+    }
 }
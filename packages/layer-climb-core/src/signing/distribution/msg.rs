@@ -0,0 +1,198 @@
+use anyhow::Result;
+
+use crate::prelude::*;
+
+impl SigningClient {
+    pub fn distribution_withdraw_delegator_reward_msg(
+        &self,
+        validator_address: impl ToString,
+    ) -> Result<layer_climb_proto::distribution::MsgWithdrawDelegatorReward> {
+        Ok(
+            layer_climb_proto::distribution::MsgWithdrawDelegatorReward {
+                delegator_address: self.addr.to_string(),
+                validator_address: validator_address.to_string(),
+            },
+        )
+    }
+
+    /// One withdraw message for every validator this client has pending rewards from
+    /// empty if there are no rewards to withdraw
+    pub async fn distribution_withdraw_all_delegator_rewards_msgs(
+        &self,
+    ) -> Result<Vec<layer_climb_proto::distribution::MsgWithdrawDelegatorReward>> {
+        self.querier
+            .delegator_rewards(self.addr.clone())
+            .await?
+            .rewards
+            .into_iter()
+            .filter(|reward| !reward.reward.is_empty())
+            .map(|reward| self.distribution_withdraw_delegator_reward_msg(reward.validator_address))
+            .collect()
+    }
+
+    /// Where staking rewards (and validator commission) are sent when withdrawn, by default the delegator itself
+    pub fn distribution_set_withdraw_address_msg(
+        &self,
+        withdraw_address: &Address,
+    ) -> Result<layer_climb_proto::distribution::MsgSetWithdrawAddress> {
+        Ok(layer_climb_proto::distribution::MsgSetWithdrawAddress {
+            delegator_address: self.addr.to_string(),
+            withdraw_address: withdraw_address.to_string(),
+        })
+    }
+
+    /// must be signed by the validator's operator account
+    pub fn distribution_withdraw_validator_commission_msg(
+        &self,
+        validator_address: impl ToString,
+    ) -> Result<layer_climb_proto::distribution::MsgWithdrawValidatorCommission> {
+        Ok(
+            layer_climb_proto::distribution::MsgWithdrawValidatorCommission {
+                validator_address: validator_address.to_string(),
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use layer_climb_proto::distribution::{
+        DelegationDelegatorReward, MsgSetWithdrawAddress, MsgWithdrawDelegatorReward,
+        MsgWithdrawValidatorCommission, QueryDelegationTotalRewardsRequest,
+        QueryDelegationTotalRewardsResponse,
+    };
+
+    use super::*;
+    use crate::testing::{address, query_client, signing_client};
+
+    // rewards from each validator, an empty list means nothing pending from that one
+    fn client(rewards: Vec<(&'static str, Vec<&'static str>)>) -> SigningClient {
+        signing_client(query_client(move |path, data, _| {
+            assert_eq!(
+                path,
+                "/cosmos.distribution.v1beta1.Query/DelegationTotalRewards"
+            );
+            let req = QueryDelegationTotalRewardsRequest::decode(data).unwrap();
+            assert_eq!(req.delegator_address, address(0).to_string());
+
+            Ok(QueryDelegationTotalRewardsResponse {
+                rewards: rewards
+                    .iter()
+                    .map(|(validator, amounts)| DelegationDelegatorReward {
+                        validator_address: validator.to_string(),
+                        reward: amounts
+                            .iter()
+                            .map(|amount| layer_climb_proto::DecCoin {
+                                denom: "uatom".to_string(),
+                                amount: amount.to_string(),
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+                total: Vec::new(),
+            }
+            .encode_to_vec())
+        }))
+    }
+
+    #[test]
+    fn withdraw_delegator_reward() {
+        let client = client(Vec::new());
+
+        let msg = client
+            .distribution_withdraw_delegator_reward_msg("cosmosvaloper1")
+            .unwrap();
+        assert_eq!(
+            msg,
+            MsgWithdrawDelegatorReward {
+                delegator_address: client.addr.to_string(),
+                validator_address: "cosmosvaloper1".to_string(),
+            }
+        );
+        assert_eq!(
+            proto_into_any(&msg).unwrap().type_url,
+            "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward"
+        );
+    }
+
+    #[test]
+    fn withdraw_all_delegator_rewards() {
+        let client = client(vec![
+            ("cosmosvaloper1", vec!["1500000000000000000000"]),
+            // still delegated, but nothing to withdraw
+            ("cosmosvaloper2", Vec::new()),
+            ("cosmosvaloper3", vec!["10000000000000000"]),
+        ]);
+        // the signing client's address is the first account of the test mnemonic
+        assert_eq!(client.addr, address(0));
+
+        let msgs = block_on(client.distribution_withdraw_all_delegator_rewards_msgs()).unwrap();
+
+        assert_eq!(
+            msgs,
+            vec![
+                MsgWithdrawDelegatorReward {
+                    delegator_address: client.addr.to_string(),
+                    validator_address: "cosmosvaloper1".to_string(),
+                },
+                MsgWithdrawDelegatorReward {
+                    delegator_address: client.addr.to_string(),
+                    validator_address: "cosmosvaloper3".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn withdraw_all_without_rewards() {
+        let client = client(vec![("cosmosvaloper1", Vec::new())]);
+
+        assert!(
+            block_on(client.distribution_withdraw_all_delegator_rewards_msgs())
+                .unwrap()
+                .is_empty()
+        );
+
+        // errors before anything is broadcast
+        let err = block_on(client.distribution_withdraw_all_delegator_rewards(None)).unwrap_err();
+        assert!(err.to_string().contains("no rewards"), "{err}");
+    }
+
+    #[test]
+    fn set_withdraw_address() {
+        let client = client(Vec::new());
+
+        let msg = client
+            .distribution_set_withdraw_address_msg(&address(1))
+            .unwrap();
+        assert_eq!(
+            msg,
+            MsgSetWithdrawAddress {
+                delegator_address: client.addr.to_string(),
+                withdraw_address: address(1).to_string(),
+            }
+        );
+        assert_eq!(
+            proto_into_any(&msg).unwrap().type_url,
+            "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress"
+        );
+    }
+
+    #[test]
+    fn withdraw_validator_commission() {
+        let msg = client(Vec::new())
+            .distribution_withdraw_validator_commission_msg("cosmosvaloper1")
+            .unwrap();
+        assert_eq!(
+            msg,
+            MsgWithdrawValidatorCommission {
+                validator_address: "cosmosvaloper1".to_string(),
+            }
+        );
+        assert_eq!(
+            proto_into_any(&msg).unwrap().type_url,
+            "/cosmos.distribution.v1beta1.MsgWithdrawValidatorCommission"
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use crate::prelude::*;
+
+impl SigningClient {
+    pub async fn distribution_withdraw_delegator_reward(
+        &self,
+        validator_address: impl ToString,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([proto_into_any(
+                &self.distribution_withdraw_delegator_reward_msg(validator_address)?,
+            )?])
+            .await?;
+
+        Ok(resp)
+    }
+
+    /// Withdraws the rewards from every validator in a single tx
+    /// errors if there are no rewards to withdraw
+    pub async fn distribution_withdraw_all_delegator_rewards(
+        &self,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let msgs = self
+            .distribution_withdraw_all_delegator_rewards_msgs()
+            .await?
+            .iter()
+            .map(proto_into_any)
+            .collect::<Result<Vec<_>>>()?;
+
+        if msgs.is_empty() {
+            bail!("no rewards to withdraw for {}", self.addr);
+        }
+
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast(msgs)
+            .await?;
+
+        Ok(resp)
+    }
+
+    pub async fn distribution_set_withdraw_address(
+        &self,
+        withdraw_address: &Address,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([proto_into_any(
+                &self.distribution_set_withdraw_address_msg(withdraw_address)?,
+            )?])
+            .await?;
+
+        Ok(resp)
+    }
+
+    pub async fn distribution_withdraw_validator_commission(
+        &self,
+        validator_address: impl ToString,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([proto_into_any(
+                &self.distribution_withdraw_validator_commission_msg(validator_address)?,
+            )?])
+            .await?;
+
+        Ok(resp)
+    }
+}
@@ -0,0 +1,260 @@
+use anyhow::Result;
+
+use crate::prelude::*;
+
+impl SigningClient {
+    pub fn feegrant_grant_allowance_msg(
+        &self,
+        granter: Option<Address>,
+        grantee: Address,
+        allowance: layer_climb_proto::Any,
+    ) -> Result<layer_climb_proto::feegrant::MsgGrantAllowance> {
+        Ok(layer_climb_proto::feegrant::MsgGrantAllowance {
+            granter: granter
+                .map(|a| a.to_string())
+                .unwrap_or_else(|| self.addr.to_string()),
+            grantee: grantee.to_string(),
+            allowance: Some(allowance),
+        })
+    }
+
+    /// an empty spend_limit means no limit
+    pub fn feegrant_grant_basic_allowance_msg(
+        &self,
+        granter: Option<Address>,
+        grantee: Address,
+        spend_limit: Vec<layer_climb_proto::Coin>,
+        expiration: Option<layer_climb_proto::Timestamp>,
+    ) -> Result<layer_climb_proto::feegrant::MsgGrantAllowance> {
+        let allowance = proto_into_any(&layer_climb_proto::feegrant::BasicAllowance {
+            spend_limit,
+            expiration,
+        })?;
+
+        self.feegrant_grant_allowance_msg(granter, grantee, allowance)
+    }
+
+    /// spend_limit and expiration are for the allowance overall,
+    /// period_spend_limit is how much can be spent in each period
+    #[allow(clippy::too_many_arguments)]
+    pub fn feegrant_grant_periodic_allowance_msg(
+        &self,
+        granter: Option<Address>,
+        grantee: Address,
+        spend_limit: Vec<layer_climb_proto::Coin>,
+        expiration: Option<layer_climb_proto::Timestamp>,
+        period: std::time::Duration,
+        period_spend_limit: Vec<layer_climb_proto::Coin>,
+    ) -> Result<layer_climb_proto::feegrant::MsgGrantAllowance> {
+        let allowance = proto_into_any(&layer_climb_proto::feegrant::PeriodicAllowance {
+            basic: Some(layer_climb_proto::feegrant::BasicAllowance {
+                spend_limit,
+                expiration,
+            }),
+            period: Some(layer_climb_proto::Duration {
+                seconds: period.as_secs().try_into()?,
+                nanos: period.subsec_nanos().try_into()?,
+            }),
+            period_can_spend: period_spend_limit.clone(),
+            period_spend_limit,
+            // set by the chain when the grant is made
+            period_reset: None,
+        })?;
+
+        self.feegrant_grant_allowance_msg(granter, grantee, allowance)
+    }
+
+    /// restricts an existing allowance (e.g. a BasicAllowance or PeriodicAllowance) to only these message type urls
+    pub fn feegrant_grant_allowed_msg_allowance_msg(
+        &self,
+        granter: Option<Address>,
+        grantee: Address,
+        allowance: layer_climb_proto::Any,
+        allowed_messages: Vec<String>,
+    ) -> Result<layer_climb_proto::feegrant::MsgGrantAllowance> {
+        let allowance = proto_into_any(&layer_climb_proto::feegrant::AllowedMsgAllowance {
+            allowance: Some(allowance),
+            allowed_messages,
+        })?;
+
+        self.feegrant_grant_allowance_msg(granter, grantee, allowance)
+    }
+
+    pub fn feegrant_revoke_allowance_msg(
+        &self,
+        granter: Option<Address>,
+        grantee: Address,
+    ) -> Result<layer_climb_proto::feegrant::MsgRevokeAllowance> {
+        Ok(layer_climb_proto::feegrant::MsgRevokeAllowance {
+            granter: granter
+                .map(|a| a.to_string())
+                .unwrap_or_else(|| self.addr.to_string()),
+            grantee: grantee.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use layer_climb_proto::feegrant::{
+        AllowedMsgAllowance, BasicAllowance, MsgGrantAllowance, PeriodicAllowance,
+    };
+
+    use super::*;
+    use crate::testing::{address, query_client, signing_client};
+
+    fn client() -> SigningClient {
+        signing_client(query_client(|path, _, _| panic!("unexpected query {path}")))
+    }
+
+    fn expiration() -> layer_climb_proto::Timestamp {
+        layer_climb_proto::Timestamp {
+            seconds: 1_700_000_000,
+            nanos: 0,
+        }
+    }
+
+    #[test]
+    fn basic_allowance() {
+        let client = client();
+
+        let msg = client
+            .feegrant_grant_basic_allowance_msg(
+                None,
+                address(1),
+                vec![new_coin(1000, "uatom")],
+                Some(expiration()),
+            )
+            .unwrap();
+
+        // the granter defaults to the signer
+        assert_eq!(msg.granter, client.addr.to_string());
+        assert_eq!(msg.grantee, address(1).to_string());
+
+        let allowance = msg.allowance.unwrap();
+        assert_eq!(
+            allowance.type_url,
+            "/cosmos.feegrant.v1beta1.BasicAllowance"
+        );
+        assert_eq!(
+            BasicAllowance::decode(allowance.value.as_slice()).unwrap(),
+            BasicAllowance {
+                spend_limit: vec![new_coin(1000, "uatom")],
+                expiration: Some(expiration()),
+            }
+        );
+    }
+
+    #[test]
+    fn periodic_allowance() {
+        let msg = client()
+            .feegrant_grant_periodic_allowance_msg(
+                Some(address(2)),
+                address(1),
+                vec![new_coin(1000, "uatom")],
+                None,
+                std::time::Duration::new(86_400, 500),
+                vec![new_coin(100, "uatom")],
+            )
+            .unwrap();
+
+        assert_eq!(msg.granter, address(2).to_string());
+
+        let allowance = msg.allowance.unwrap();
+        assert_eq!(
+            allowance.type_url,
+            "/cosmos.feegrant.v1beta1.PeriodicAllowance"
+        );
+        assert_eq!(
+            PeriodicAllowance::decode(allowance.value.as_slice()).unwrap(),
+            PeriodicAllowance {
+                basic: Some(BasicAllowance {
+                    spend_limit: vec![new_coin(1000, "uatom")],
+                    expiration: None,
+                }),
+                period: Some(layer_climb_proto::Duration {
+                    seconds: 86_400,
+                    nanos: 500,
+                }),
+                period_spend_limit: vec![new_coin(100, "uatom")],
+                // the whole limit is available in the first period
+                period_can_spend: vec![new_coin(100, "uatom")],
+                period_reset: None,
+            }
+        );
+    }
+
+    #[test]
+    fn allowed_msg_allowance() {
+        let client = client();
+
+        let periodic = client
+            .feegrant_grant_periodic_allowance_msg(
+                None,
+                address(1),
+                Vec::new(),
+                Some(expiration()),
+                std::time::Duration::from_secs(3600),
+                vec![new_coin(10, "uatom")],
+            )
+            .unwrap()
+            .allowance
+            .unwrap();
+
+        let allowed_messages = vec![
+            "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            "/cosmwasm.wasm.v1.MsgExecuteContract".to_string(),
+        ];
+
+        let msg = client
+            .feegrant_grant_allowed_msg_allowance_msg(
+                None,
+                address(1),
+                periodic.clone(),
+                allowed_messages.clone(),
+            )
+            .unwrap();
+
+        let allowance = msg.allowance.clone().unwrap();
+        assert_eq!(
+            allowance.type_url,
+            "/cosmos.feegrant.v1beta1.AllowedMsgAllowance"
+        );
+
+        // the inner allowance is kept as-is, still wrapped in its own Any
+        let allowed = AllowedMsgAllowance::decode(allowance.value.as_slice()).unwrap();
+        assert_eq!(allowed.allowed_messages, allowed_messages);
+        assert_eq!(allowed.allowance, Some(periodic.clone()));
+        assert_eq!(
+            periodic.type_url,
+            "/cosmos.feegrant.v1beta1.PeriodicAllowance"
+        );
+
+        let any = proto_into_any(&msg).unwrap();
+        assert_eq!(any.type_url, "/cosmos.feegrant.v1beta1.MsgGrantAllowance");
+        assert_eq!(
+            MsgGrantAllowance::decode(any.value.as_slice()).unwrap(),
+            msg
+        );
+    }
+
+    #[test]
+    fn revoke_allowance() {
+        let client = client();
+
+        let msg = client
+            .feegrant_revoke_allowance_msg(None, address(1))
+            .unwrap();
+        assert_eq!(msg.granter, client.addr.to_string());
+        assert_eq!(msg.grantee, address(1).to_string());
+
+        let msg = client
+            .feegrant_revoke_allowance_msg(Some(address(2)), address(1))
+            .unwrap();
+        assert_eq!(msg.granter, address(2).to_string());
+        assert_eq!(
+            proto_into_any(&msg).unwrap().type_url,
+            "/cosmos.feegrant.v1beta1.MsgRevokeAllowance"
+        );
+    }
+}
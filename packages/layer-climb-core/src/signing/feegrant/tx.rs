@@ -0,0 +1,108 @@
+use crate::prelude::*;
+
+impl SigningClient {
+    pub async fn feegrant_grant_allowance(
+        &self,
+        granter: Option<Address>,
+        grantee: Address,
+        allowance: layer_climb_proto::Any,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([proto_into_any(
+                &self.feegrant_grant_allowance_msg(granter, grantee, allowance)?,
+            )?])
+            .await?;
+
+        Ok(resp)
+    }
+
+    pub async fn feegrant_grant_basic_allowance(
+        &self,
+        granter: Option<Address>,
+        grantee: Address,
+        spend_limit: Vec<layer_climb_proto::Coin>,
+        expiration: Option<layer_climb_proto::Timestamp>,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([proto_into_any(&self.feegrant_grant_basic_allowance_msg(
+                granter,
+                grantee,
+                spend_limit,
+                expiration,
+            )?)?])
+            .await?;
+
+        Ok(resp)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn feegrant_grant_periodic_allowance(
+        &self,
+        granter: Option<Address>,
+        grantee: Address,
+        spend_limit: Vec<layer_climb_proto::Coin>,
+        expiration: Option<layer_climb_proto::Timestamp>,
+        period: std::time::Duration,
+        period_spend_limit: Vec<layer_climb_proto::Coin>,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([proto_into_any(
+                &self.feegrant_grant_periodic_allowance_msg(
+                    granter,
+                    grantee,
+                    spend_limit,
+                    expiration,
+                    period,
+                    period_spend_limit,
+                )?,
+            )?])
+            .await?;
+
+        Ok(resp)
+    }
+
+    pub async fn feegrant_grant_allowed_msg_allowance(
+        &self,
+        granter: Option<Address>,
+        grantee: Address,
+        allowance: layer_climb_proto::Any,
+        allowed_messages: Vec<String>,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([proto_into_any(
+                &self.feegrant_grant_allowed_msg_allowance_msg(
+                    granter,
+                    grantee,
+                    allowance,
+                    allowed_messages,
+                )?,
+            )?])
+            .await?;
+
+        Ok(resp)
+    }
+
+    pub async fn feegrant_revoke_allowance(
+        &self,
+        granter: Option<Address>,
+        grantee: Address,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([proto_into_any(
+                &self.feegrant_revoke_allowance_msg(granter, grantee)?,
+            )?])
+            .await?;
+
+        Ok(resp)
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::Result;
+use layer_climb_proto::gov::{v1, v1beta1};
+
+use crate::{
+    prelude::*,
+    querier::gov::{legacy_dec_from_str, GovVersion},
+};
+
+impl SigningClient {
+    /// A proposal to execute arbitrary messages (e.g. `MsgStoreCode` or `MsgSoftwareUpgrade`) with the gov module as their signer
+    /// requires gov v1, for v1beta1 nodes see `gov_submit_legacy_proposal_msg()`
+    pub fn gov_submit_proposal_msg(
+        &self,
+        messages: Vec<layer_climb_proto::Any>,
+        initial_deposit: Vec<layer_climb_proto::Coin>,
+        title: impl ToString,
+        summary: impl ToString,
+        metadata: Option<String>,
+        expedited: bool,
+    ) -> Result<v1::MsgSubmitProposal> {
+        Ok(v1::MsgSubmitProposal {
+            messages,
+            initial_deposit,
+            proposer: self.addr.to_string(),
+            metadata: metadata.unwrap_or_default(),
+            title: title.to_string(),
+            summary: summary.to_string(),
+            expedited,
+        })
+    }
+
+    /// A proposal with a single legacy `Content` (e.g. a `TextProposal`), accepted by v1beta1 and v1 nodes alike
+    pub fn gov_submit_legacy_proposal_msg(
+        &self,
+        content: layer_climb_proto::Any,
+        initial_deposit: Vec<layer_climb_proto::Coin>,
+    ) -> Result<v1beta1::MsgSubmitProposal> {
+        Ok(v1beta1::MsgSubmitProposal {
+            content: Some(content),
+            initial_deposit,
+            proposer: self.addr.to_string(),
+        })
+    }
+
+    // the messages below are built for the node's gov version (see `QueryClient::gov_version()`), hence `Any`
+
+    /// metadata is only sent to v1 nodes
+    pub async fn gov_vote_msg(
+        &self,
+        proposal_id: u64,
+        option: v1::VoteOption,
+        metadata: Option<String>,
+    ) -> Result<layer_climb_proto::Any> {
+        match self.querier.gov_version().await? {
+            GovVersion::V1 => proto_into_any(&v1::MsgVote {
+                proposal_id,
+                voter: self.addr.to_string(),
+                option: option as i32,
+                metadata: metadata.unwrap_or_default(),
+            }),
+            GovVersion::V1Beta1 => proto_into_any(&v1beta1::MsgVote {
+                proposal_id,
+                voter: self.addr.to_string(),
+                option: option as i32,
+            }),
+        }
+    }
+
+    /// the weights are decimal strings (e.g. "0.5") and must add up to 1
+    /// metadata is only sent to v1 nodes
+    pub async fn gov_vote_weighted_msg(
+        &self,
+        proposal_id: u64,
+        options: Vec<v1::WeightedVoteOption>,
+        metadata: Option<String>,
+    ) -> Result<layer_climb_proto::Any> {
+        match self.querier.gov_version().await? {
+            GovVersion::V1 => proto_into_any(&v1::MsgVoteWeighted {
+                proposal_id,
+                voter: self.addr.to_string(),
+                options,
+                metadata: metadata.unwrap_or_default(),
+            }),
+            GovVersion::V1Beta1 => proto_into_any(&v1beta1::MsgVoteWeighted {
+                proposal_id,
+                voter: self.addr.to_string(),
+                options: options
+                    .into_iter()
+                    .map(|option| {
+                        Ok(v1beta1::WeightedVoteOption {
+                            option: option.option,
+                            weight: legacy_dec_from_str(&option.weight)?,
+                        })
+                    })
+                    .collect::<Result<_>>()?,
+            }),
+        }
+    }
+
+    pub async fn gov_deposit_msg(
+        &self,
+        proposal_id: u64,
+        amount: Vec<layer_climb_proto::Coin>,
+    ) -> Result<layer_climb_proto::Any> {
+        match self.querier.gov_version().await? {
+            GovVersion::V1 => proto_into_any(&v1::MsgDeposit {
+                proposal_id,
+                depositor: self.addr.to_string(),
+                amount,
+            }),
+            GovVersion::V1Beta1 => proto_into_any(&v1beta1::MsgDeposit {
+                proposal_id,
+                depositor: self.addr.to_string(),
+                amount,
+            }),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use layer_climb_proto::gov::v1;
+
+use crate::{
+    events::{EVENT_ATTR_GOV_PROPOSAL_ID, EVENT_TYPE_GOV_SUBMIT_PROPOSAL},
+    prelude::*,
+    querier::gov::GovVersion,
+};
+
+impl SigningClient {
+    // returns the proposal id
+    #[allow(clippy::too_many_arguments)]
+    pub async fn gov_submit_proposal(
+        &self,
+        messages: Vec<layer_climb_proto::Any>,
+        initial_deposit: Vec<layer_climb_proto::Coin>,
+        title: impl ToString,
+        summary: impl ToString,
+        metadata: Option<String>,
+        expedited: bool,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<(u64, layer_climb_proto::abci::TxResponse)> {
+        if self.querier.gov_version().await? == GovVersion::V1Beta1 {
+            bail!("the node only supports gov v1beta1, which can't execute messages, submit a legacy proposal instead");
+        }
+
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([proto_into_any(&self.gov_submit_proposal_msg(
+                messages,
+                initial_deposit,
+                title,
+                summary,
+                metadata,
+                expedited,
+            )?)?])
+            .await?;
+
+        let proposal_id = proposal_id_from_tx_resp(&resp)?;
+
+        Ok((proposal_id, resp))
+    }
+
+    // returns the proposal id
+    pub async fn gov_submit_legacy_proposal(
+        &self,
+        content: layer_climb_proto::Any,
+        initial_deposit: Vec<layer_climb_proto::Coin>,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<(u64, layer_climb_proto::abci::TxResponse)> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([proto_into_any(
+                &self.gov_submit_legacy_proposal_msg(content, initial_deposit)?,
+            )?])
+            .await?;
+
+        let proposal_id = proposal_id_from_tx_resp(&resp)?;
+
+        Ok((proposal_id, resp))
+    }
+
+    pub async fn gov_vote(
+        &self,
+        proposal_id: u64,
+        option: v1::VoteOption,
+        metadata: Option<String>,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([self.gov_vote_msg(proposal_id, option, metadata).await?])
+            .await?;
+
+        Ok(resp)
+    }
+
+    pub async fn gov_vote_weighted(
+        &self,
+        proposal_id: u64,
+        options: Vec<v1::WeightedVoteOption>,
+        metadata: Option<String>,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([self
+                .gov_vote_weighted_msg(proposal_id, options, metadata)
+                .await?])
+            .await?;
+
+        Ok(resp)
+    }
+
+    pub async fn gov_deposit(
+        &self,
+        proposal_id: u64,
+        amount: Vec<layer_climb_proto::Coin>,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([self.gov_deposit_msg(proposal_id, amount).await?])
+            .await?;
+
+        Ok(resp)
+    }
+}
+
+fn proposal_id_from_tx_resp(resp: &layer_climb_proto::abci::TxResponse) -> Result<u64> {
+    Ok(CosmosTxEvents::from(resp)
+        .attr_first(EVENT_TYPE_GOV_SUBMIT_PROPOSAL, EVENT_ATTR_GOV_PROPOSAL_ID)?
+        .value()
+        .parse()?)
+}
@@ -1,41 +1,93 @@
+pub mod allowlist;
+pub mod fee_limit;
 pub mod logger;
+pub mod memo;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
 
 use crate::{prelude::*, querier::tx::AnyTxResponse};
-use logger::{SigningLoggerMiddlewareMapBody, SigningLoggerMiddlewareMapResp};
 
-pub enum SigningMiddlewareMapBody {
-    Logger(SigningLoggerMiddlewareMapBody),
-}
+// Any middleware can abort the tx by returning an error
+// return a `TxRejection` (via `ClimbError::Rejected`) so callers can tell a veto apart from a network failure
 
-impl SigningMiddlewareMapBody {
-    pub async fn map_body(
-        &self,
-        req: layer_climb_proto::tx::TxBody,
-    ) -> Result<layer_climb_proto::tx::TxBody> {
-        match self {
-            Self::Logger(m) => m.map_body(req).await,
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        /// Runs before simulation and signing, may rewrite the body
+        #[async_trait(?Send)]
+        pub trait SigningMiddlewareMapBody: Send + Sync {
+            async fn map_body(
+                &self,
+                body: layer_climb_proto::tx::TxBodyV053,
+            ) -> Result<layer_climb_proto::tx::TxBodyV053>;
+        }
+
+        /// Runs once the fee is known, right before signing
+        /// the body is final at this point (including timeouts)
+        /// note that this also runs for the simulation, where the fee is zero
+        #[async_trait(?Send)]
+        pub trait SigningMiddlewareMapAuthInfo: Send + Sync {
+            async fn map_auth_info(
+                &self,
+                body: &layer_climb_proto::tx::TxBodyV053,
+                auth_info: layer_climb_proto::tx::AuthInfo,
+            ) -> Result<layer_climb_proto::tx::AuthInfo>;
+        }
+
+        /// Runs after the tx is broadcast successfully
+        /// `on_error` runs instead if broadcasting fails (rejected in CheckTx, failed in DeliverTx, or timed out polling)
+        #[async_trait(?Send)]
+        pub trait SigningMiddlewareMapResp: Send + Sync {
+            async fn map_resp(&self, resp: AnyTxResponse) -> Result<AnyTxResponse>;
+
+            async fn on_error(&self, _err: &anyhow::Error) {}
+        }
+    } else {
+        /// Runs before simulation and signing, may rewrite the body
+        #[async_trait]
+        pub trait SigningMiddlewareMapBody: Send + Sync {
+            async fn map_body(
+                &self,
+                body: layer_climb_proto::tx::TxBodyV053,
+            ) -> Result<layer_climb_proto::tx::TxBodyV053>;
+        }
+
+        /// Runs once the fee is known, right before signing
+        /// the body is final at this point (including timeouts)
+        /// note that this also runs for the simulation, where the fee is zero
+        #[async_trait]
+        pub trait SigningMiddlewareMapAuthInfo: Send + Sync {
+            async fn map_auth_info(
+                &self,
+                body: &layer_climb_proto::tx::TxBodyV053,
+                auth_info: layer_climb_proto::tx::AuthInfo,
+            ) -> Result<layer_climb_proto::tx::AuthInfo>;
+        }
+
+        /// Runs after the tx is broadcast successfully
+        /// `on_error` runs instead if broadcasting fails (rejected in CheckTx, failed in DeliverTx, or timed out polling)
+        #[async_trait]
+        pub trait SigningMiddlewareMapResp: Send + Sync {
+            async fn map_resp(&self, resp: AnyTxResponse) -> Result<AnyTxResponse>;
+
+            async fn on_error(&self, _err: &anyhow::Error) {}
         }
-    }
-    pub fn default_list() -> Vec<Self> {
-        vec![
-            //Self::Logger(SigningLoggerMiddlewareMapBody::default()),
-        ]
     }
 }
 
-pub enum SigningMiddlewareMapResp {
-    Logger(SigningLoggerMiddlewareMapResp),
+pub fn default_middleware_map_body() -> Vec<Arc<dyn SigningMiddlewareMapBody>> {
+    vec![
+        //Arc::new(logger::SigningLoggerMiddlewareMapBody::default()),
+    ]
 }
 
-impl SigningMiddlewareMapResp {
-    pub async fn map_resp(&self, resp: AnyTxResponse) -> Result<AnyTxResponse> {
-        match self {
-            Self::Logger(m) => m.map_resp(resp).await,
-        }
-    }
-    pub fn default_list() -> Vec<Self> {
-        vec![
-            //Self::Logger(SigningLoggerMiddlewareMapResp::default()),
-        ]
-    }
+pub fn default_middleware_map_auth_info() -> Vec<Arc<dyn SigningMiddlewareMapAuthInfo>> {
+    vec![]
+}
+
+pub fn default_middleware_map_resp() -> Vec<Arc<dyn SigningMiddlewareMapResp>> {
+    vec![
+        //Arc::new(logger::SigningLoggerMiddlewareMapResp::default()),
+    ]
 }
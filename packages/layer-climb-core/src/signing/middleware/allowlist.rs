@@ -0,0 +1,61 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+
+use super::SigningMiddlewareMapBody;
+use crate::prelude::*;
+
+/// Rejects any tx with a message whose type isn't in the list
+/// type urls are the full proto names, e.g. "/cosmwasm.wasm.v1.MsgExecuteContract"
+#[derive(Clone, Debug)]
+pub struct SigningMsgAllowlistMiddleware {
+    pub type_urls: HashSet<String>,
+}
+
+impl SigningMsgAllowlistMiddleware {
+    pub fn new(type_urls: impl IntoIterator<Item = impl ToString>) -> Self {
+        Self {
+            type_urls: type_urls.into_iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn check(&self, body: &layer_climb_proto::tx::TxBodyV053) -> Result<()> {
+        match body
+            .messages
+            .iter()
+            .find(|msg| !self.type_urls.contains(&msg.type_url))
+        {
+            Some(msg) => Err(ClimbError::Rejected(TxRejection::MsgNotAllowed {
+                type_url: msg.type_url.clone(),
+            })
+            .into()),
+            None => Ok(()),
+        }
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        #[async_trait(?Send)]
+        impl SigningMiddlewareMapBody for SigningMsgAllowlistMiddleware {
+            async fn map_body(
+                &self,
+                body: layer_climb_proto::tx::TxBodyV053,
+            ) -> Result<layer_climb_proto::tx::TxBodyV053> {
+                self.check(&body)?;
+                Ok(body)
+            }
+        }
+    } else {
+        #[async_trait]
+        impl SigningMiddlewareMapBody for SigningMsgAllowlistMiddleware {
+            async fn map_body(
+                &self,
+                body: layer_climb_proto::tx::TxBodyV053,
+            ) -> Result<layer_climb_proto::tx::TxBodyV053> {
+                self.check(&body)?;
+                Ok(body)
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use super::SigningMiddlewareMapAuthInfo;
+use crate::prelude::*;
+
+/// Rejects any tx whose fee is above the limit for its denom
+/// fees in a denom without a limit are rejected too
+#[derive(Clone, Debug)]
+pub struct SigningFeeLimitMiddleware {
+    /// denom -> max amount
+    pub limits: HashMap<String, u128>,
+}
+
+impl SigningFeeLimitMiddleware {
+    pub fn new(limits: impl IntoIterator<Item = (impl ToString, u128)>) -> Self {
+        Self {
+            limits: limits
+                .into_iter()
+                .map(|(denom, limit)| (denom.to_string(), limit))
+                .collect(),
+        }
+    }
+
+    fn check(&self, auth_info: &layer_climb_proto::tx::AuthInfo) -> Result<()> {
+        let coins = auth_info.fee.iter().flat_map(|fee| fee.amount.iter());
+
+        for coin in coins {
+            let amount: u128 = coin
+                .amount
+                .parse()
+                .with_context(|| format!("invalid fee amount: {}", coin.amount))?;
+
+            // nothing is paid, e.g. the zero fee used for simulation
+            if amount == 0 {
+                continue;
+            }
+
+            let rejection = match self.limits.get(&coin.denom) {
+                None => TxRejection::FeeDenomNotAllowed {
+                    denom: coin.denom.clone(),
+                },
+                Some(limit) if amount > *limit => TxRejection::FeeTooHigh {
+                    denom: coin.denom.clone(),
+                    amount,
+                    limit: *limit,
+                },
+                Some(_) => continue,
+            };
+
+            return Err(ClimbError::Rejected(rejection).into());
+        }
+
+        Ok(())
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        #[async_trait(?Send)]
+        impl SigningMiddlewareMapAuthInfo for SigningFeeLimitMiddleware {
+            async fn map_auth_info(
+                &self,
+                _body: &layer_climb_proto::tx::TxBodyV053,
+                auth_info: layer_climb_proto::tx::AuthInfo,
+            ) -> Result<layer_climb_proto::tx::AuthInfo> {
+                self.check(&auth_info)?;
+                Ok(auth_info)
+            }
+        }
+    } else {
+        #[async_trait]
+        impl SigningMiddlewareMapAuthInfo for SigningFeeLimitMiddleware {
+            async fn map_auth_info(
+                &self,
+                _body: &layer_climb_proto::tx::TxBodyV053,
+                auth_info: layer_climb_proto::tx::AuthInfo,
+            ) -> Result<layer_climb_proto::tx::AuthInfo> {
+                self.check(&auth_info)?;
+                Ok(auth_info)
+            }
+        }
+    }
+}
@@ -1,15 +1,18 @@
 use crate::{prelude::*, querier::tx::AnyTxResponse};
 
+use async_trait::async_trait;
 use std::sync::Arc;
 
+use super::{SigningMiddlewareMapBody, SigningMiddlewareMapResp};
+
 #[derive(Clone)]
 pub struct SigningLoggerMiddlewareMapBody {
-    pub logger_fn: Arc<dyn Fn(&layer_climb_proto::tx::TxBody) + Send + Sync>,
+    pub logger_fn: Arc<dyn Fn(&layer_climb_proto::tx::TxBodyV053) + Send + Sync>,
 }
 impl SigningLoggerMiddlewareMapBody {
     pub fn new<F>(logger_fn: F) -> Self
     where
-        F: Fn(&layer_climb_proto::tx::TxBody) + Send + Sync + 'static,
+        F: Fn(&layer_climb_proto::tx::TxBodyV053) + Send + Sync + 'static,
     {
         Self {
             logger_fn: Arc::new(logger_fn),
@@ -22,13 +25,29 @@ impl Default for SigningLoggerMiddlewareMapBody {
     }
 }
 
-impl SigningLoggerMiddlewareMapBody {
-    pub async fn map_body(
-        &self,
-        body: layer_climb_proto::tx::TxBody,
-    ) -> Result<layer_climb_proto::tx::TxBody> {
-        (self.logger_fn)(&body);
-        Ok(body)
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        #[async_trait(?Send)]
+        impl SigningMiddlewareMapBody for SigningLoggerMiddlewareMapBody {
+            async fn map_body(
+                &self,
+                body: layer_climb_proto::tx::TxBodyV053,
+            ) -> Result<layer_climb_proto::tx::TxBodyV053> {
+                (self.logger_fn)(&body);
+                Ok(body)
+            }
+        }
+    } else {
+        #[async_trait]
+        impl SigningMiddlewareMapBody for SigningLoggerMiddlewareMapBody {
+            async fn map_body(
+                &self,
+                body: layer_climb_proto::tx::TxBodyV053,
+            ) -> Result<layer_climb_proto::tx::TxBodyV053> {
+                (self.logger_fn)(&body);
+                Ok(body)
+            }
+        }
     }
 }
 
@@ -51,9 +70,22 @@ impl Default for SigningLoggerMiddlewareMapResp {
     }
 }
 
-impl SigningLoggerMiddlewareMapResp {
-    pub async fn map_resp(&self, resp: AnyTxResponse) -> Result<AnyTxResponse> {
-        (self.logger_fn)(&resp);
-        Ok(resp)
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        #[async_trait(?Send)]
+        impl SigningMiddlewareMapResp for SigningLoggerMiddlewareMapResp {
+            async fn map_resp(&self, resp: AnyTxResponse) -> Result<AnyTxResponse> {
+                (self.logger_fn)(&resp);
+                Ok(resp)
+            }
+        }
+    } else {
+        #[async_trait]
+        impl SigningMiddlewareMapResp for SigningLoggerMiddlewareMapResp {
+            async fn map_resp(&self, resp: AnyTxResponse) -> Result<AnyTxResponse> {
+                (self.logger_fn)(&resp);
+                Ok(resp)
+            }
+        }
     }
 }
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+
+use super::SigningMiddlewareMapBody;
+use crate::prelude::*;
+
+/// Sets the memo on every tx
+/// if `overwrite` is false, a memo that was already set (e.g. via `TxBuilder::set_memo`) is kept
+#[derive(Clone, Debug)]
+pub struct SigningMemoMiddleware {
+    pub memo: String,
+    pub overwrite: bool,
+}
+
+impl SigningMemoMiddleware {
+    pub fn new(memo: impl ToString) -> Self {
+        Self {
+            memo: memo.to_string(),
+            overwrite: false,
+        }
+    }
+
+    fn apply(
+        &self,
+        mut body: layer_climb_proto::tx::TxBodyV053,
+    ) -> layer_climb_proto::tx::TxBodyV053 {
+        if self.overwrite || body.memo.is_empty() {
+            body.memo = self.memo.clone();
+        }
+        body
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        #[async_trait(?Send)]
+        impl SigningMiddlewareMapBody for SigningMemoMiddleware {
+            async fn map_body(
+                &self,
+                body: layer_climb_proto::tx::TxBodyV053,
+            ) -> Result<layer_climb_proto::tx::TxBodyV053> {
+                Ok(self.apply(body))
+            }
+        }
+    } else {
+        #[async_trait]
+        impl SigningMiddlewareMapBody for SigningMemoMiddleware {
+            async fn map_body(
+                &self,
+                body: layer_climb_proto::tx::TxBodyV053,
+            ) -> Result<layer_climb_proto::tx::TxBodyV053> {
+                Ok(self.apply(body))
+            }
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use crate::prelude::*;
 
 impl SigningClient {
@@ -20,4 +22,53 @@ impl SigningClient {
             amount: vec![amount],
         })
     }
+
+    /// Pays many recipients in one message, from this client's address
+    /// a recipient can be listed more than once, and each can get several denoms
+    pub fn multi_send_msg(
+        &self,
+        outputs: Vec<(Address, Vec<layer_climb_proto::Coin>)>,
+    ) -> Result<layer_climb_proto::bank::MsgMultiSend> {
+        if outputs.is_empty() {
+            bail!("multi send needs at least one recipient");
+        }
+
+        // the chain expects each list of coins to be sorted by denom with no duplicates
+        // and the single input to add up to the outputs exactly
+        let mut total = BTreeMap::new();
+
+        let outputs = outputs
+            .into_iter()
+            .map(|(recipient, coins)| {
+                let mut amounts = BTreeMap::new();
+                for coin in coins {
+                    let amount = coin
+                        .amount
+                        .parse::<u128>()
+                        .with_context(|| format!("couldn't parse amount of {}", coin.denom))?;
+                    *amounts.entry(coin.denom.clone()).or_insert(0u128) += amount;
+                    *total.entry(coin.denom).or_insert(0u128) += amount;
+                }
+
+                Ok(layer_climb_proto::bank::Output {
+                    address: recipient.to_string(),
+                    coins: amounts
+                        .into_iter()
+                        .map(|(denom, amount)| new_coin(amount, denom))
+                        .collect(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(layer_climb_proto::bank::MsgMultiSend {
+            inputs: vec![layer_climb_proto::bank::Input {
+                address: self.addr.to_string(),
+                coins: total
+                    .into_iter()
+                    .map(|(denom, amount)| new_coin(amount, denom))
+                    .collect(),
+            }],
+            outputs,
+        })
+    }
 }
@@ -0,0 +1,2 @@
+mod msg;
+mod tx;
@@ -0,0 +1,174 @@
+use anyhow::Result;
+
+use crate::prelude::*;
+
+// validator addresses are the operator (e.g. `cosmosvaloper1...`) addresses, so they're taken as plain strings
+
+impl SigningClient {
+    pub fn staking_delegate_msg(
+        &self,
+        validator_address: impl ToString,
+        amount: layer_climb_proto::Coin,
+    ) -> Result<layer_climb_proto::staking::MsgDelegate> {
+        Ok(layer_climb_proto::staking::MsgDelegate {
+            delegator_address: self.addr.to_string(),
+            validator_address: validator_address.to_string(),
+            amount: Some(amount),
+        })
+    }
+
+    pub fn staking_undelegate_msg(
+        &self,
+        validator_address: impl ToString,
+        amount: layer_climb_proto::Coin,
+    ) -> Result<layer_climb_proto::staking::MsgUndelegate> {
+        Ok(layer_climb_proto::staking::MsgUndelegate {
+            delegator_address: self.addr.to_string(),
+            validator_address: validator_address.to_string(),
+            amount: Some(amount),
+        })
+    }
+
+    pub fn staking_redelegate_msg(
+        &self,
+        src_validator_address: impl ToString,
+        dst_validator_address: impl ToString,
+        amount: layer_climb_proto::Coin,
+    ) -> Result<layer_climb_proto::staking::MsgBeginRedelegate> {
+        Ok(layer_climb_proto::staking::MsgBeginRedelegate {
+            delegator_address: self.addr.to_string(),
+            validator_src_address: src_validator_address.to_string(),
+            validator_dst_address: dst_validator_address.to_string(),
+            amount: Some(amount),
+        })
+    }
+
+    /// creation_height is the height the unbonding started at, see `QueryClient::unbonding_delegations()`
+    /// amount can be less than the unbonding entry's balance, to only cancel part of it
+    pub fn staking_cancel_unbonding_delegation_msg(
+        &self,
+        validator_address: impl ToString,
+        amount: layer_climb_proto::Coin,
+        creation_height: u64,
+    ) -> Result<layer_climb_proto::staking::MsgCancelUnbondingDelegation> {
+        Ok(layer_climb_proto::staking::MsgCancelUnbondingDelegation {
+            delegator_address: self.addr.to_string(),
+            validator_address: validator_address.to_string(),
+            amount: Some(amount),
+            creation_height: creation_height.try_into()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use layer_climb_proto::staking::{
+        MsgBeginRedelegate, MsgCancelUnbondingDelegation, MsgDelegate, MsgUndelegate,
+    };
+
+    use super::*;
+    use crate::testing::{query_client, signing_client};
+
+    fn client() -> SigningClient {
+        signing_client(query_client(|path, _, _| panic!("unexpected query {path}")))
+    }
+
+    #[test]
+    fn delegate() {
+        let client = client();
+
+        let msg = client
+            .staking_delegate_msg("cosmosvaloper1", new_coin(1000, "uatom"))
+            .unwrap();
+        assert_eq!(
+            msg,
+            MsgDelegate {
+                delegator_address: client.addr.to_string(),
+                validator_address: "cosmosvaloper1".to_string(),
+                amount: Some(new_coin(1000, "uatom")),
+            }
+        );
+
+        let any = proto_into_any(&msg).unwrap();
+        assert_eq!(any.type_url, "/cosmos.staking.v1beta1.MsgDelegate");
+        assert_eq!(MsgDelegate::decode(any.value.as_slice()).unwrap(), msg);
+    }
+
+    #[test]
+    fn undelegate() {
+        let client = client();
+
+        let msg = client
+            .staking_undelegate_msg("cosmosvaloper1", new_coin(500, "uatom"))
+            .unwrap();
+        assert_eq!(
+            msg,
+            MsgUndelegate {
+                delegator_address: client.addr.to_string(),
+                validator_address: "cosmosvaloper1".to_string(),
+                amount: Some(new_coin(500, "uatom")),
+            }
+        );
+        assert_eq!(
+            proto_into_any(&msg).unwrap().type_url,
+            "/cosmos.staking.v1beta1.MsgUndelegate"
+        );
+    }
+
+    #[test]
+    fn redelegate() {
+        let client = client();
+
+        let msg = client
+            .staking_redelegate_msg("cosmosvaloper1", "cosmosvaloper2", new_coin(250, "uatom"))
+            .unwrap();
+        assert_eq!(
+            msg,
+            MsgBeginRedelegate {
+                delegator_address: client.addr.to_string(),
+                validator_src_address: "cosmosvaloper1".to_string(),
+                validator_dst_address: "cosmosvaloper2".to_string(),
+                amount: Some(new_coin(250, "uatom")),
+            }
+        );
+        assert_eq!(
+            proto_into_any(&msg).unwrap().type_url,
+            "/cosmos.staking.v1beta1.MsgBeginRedelegate"
+        );
+    }
+
+    #[test]
+    fn cancel_unbonding_delegation() {
+        let client = client();
+
+        let msg = client
+            .staking_cancel_unbonding_delegation_msg(
+                "cosmosvaloper1",
+                new_coin(100, "uatom"),
+                12345,
+            )
+            .unwrap();
+        assert_eq!(
+            msg,
+            MsgCancelUnbondingDelegation {
+                delegator_address: client.addr.to_string(),
+                validator_address: "cosmosvaloper1".to_string(),
+                amount: Some(new_coin(100, "uatom")),
+                creation_height: 12345,
+            }
+        );
+        assert_eq!(
+            proto_into_any(&msg).unwrap().type_url,
+            "/cosmos.staking.v1beta1.MsgCancelUnbondingDelegation"
+        );
+
+        // the proto field is an i64
+        assert!(client
+            .staking_cancel_unbonding_delegation_msg(
+                "cosmosvaloper1",
+                new_coin(100, "uatom"),
+                u64::MAX,
+            )
+            .is_err());
+    }
+}
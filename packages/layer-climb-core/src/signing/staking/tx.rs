@@ -0,0 +1,75 @@
+use crate::prelude::*;
+
+impl SigningClient {
+    pub async fn staking_delegate(
+        &self,
+        validator_address: impl ToString,
+        amount: layer_climb_proto::Coin,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([proto_into_any(
+                &self.staking_delegate_msg(validator_address, amount)?,
+            )?])
+            .await?;
+
+        Ok(resp)
+    }
+
+    pub async fn staking_undelegate(
+        &self,
+        validator_address: impl ToString,
+        amount: layer_climb_proto::Coin,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([proto_into_any(
+                &self.staking_undelegate_msg(validator_address, amount)?,
+            )?])
+            .await?;
+
+        Ok(resp)
+    }
+
+    pub async fn staking_redelegate(
+        &self,
+        src_validator_address: impl ToString,
+        dst_validator_address: impl ToString,
+        amount: layer_climb_proto::Coin,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([proto_into_any(&self.staking_redelegate_msg(
+                src_validator_address,
+                dst_validator_address,
+                amount,
+            )?)?])
+            .await?;
+
+        Ok(resp)
+    }
+
+    pub async fn staking_cancel_unbonding_delegation(
+        &self,
+        validator_address: impl ToString,
+        amount: layer_climb_proto::Coin,
+        creation_height: u64,
+        tx_builder: Option<TxBuilder<'_>>,
+    ) -> Result<layer_climb_proto::abci::TxResponse> {
+        let resp = tx_builder
+            .unwrap_or_else(|| self.tx_builder())
+            .broadcast([proto_into_any(
+                &self.staking_cancel_unbonding_delegation_msg(
+                    validator_address,
+                    amount,
+                    creation_height,
+                )?,
+            )?])
+            .await?;
+
+        Ok(resp)
+    }
+}
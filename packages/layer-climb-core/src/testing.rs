@@ -0,0 +1,160 @@
+// Fakes for unit tests that need a client but no chain
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use base64::prelude::*;
+use futures::executor::block_on;
+
+use layer_climb_signer::{KeySigner, TxSigner};
+
+use crate::{
+    network::rpc::{AbciQueryError, RpcTransport},
+    prelude::*,
+    signing::middleware,
+    transaction::{SequenceStrategy, SequenceStrategyKind},
+};
+
+pub(crate) const HEIGHT: u64 = 7;
+
+const MNEMONIC: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+type AbciHandler =
+    dyn Fn(&str, &[u8], Option<u64>) -> std::result::Result<Vec<u8>, AbciQueryError> + Send + Sync;
+
+/// Answers every abci query with `handler(path, data, height)`, i.e. the encoded response or an abci error
+/// any other rpc call fails
+pub(crate) struct FakeAbci {
+    handler: Box<AbciHandler>,
+}
+
+impl FakeAbci {
+    fn respond(&self, body: Vec<u8>) -> Result<String> {
+        let body: serde_json::Value = serde_json::from_slice(&body)?;
+        if body["method"] != "abci_query" {
+            bail!("unexpected rpc method {}", body["method"]);
+        }
+
+        let params = &body["params"];
+        let path = params["path"].as_str().context("missing path")?;
+        let data = const_hex::decode(params["data"].as_str().context("missing data")?)?;
+        let height = match params["height"].as_str() {
+            None | Some("0") => None,
+            Some(height) => Some(height.parse()?),
+        };
+
+        let (code, log, codespace, value) = match (self.handler)(path, &data, height) {
+            Ok(value) => (0, String::new(), String::new(), value),
+            Err(err) => (err.code, err.log, err.codespace, Vec::new()),
+        };
+
+        Ok(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": body["id"],
+            "result": {
+                "response": {
+                    "code": code,
+                    "log": log,
+                    "info": "",
+                    "index": "0",
+                    "key": "",
+                    "value": BASE64_STANDARD.encode(value),
+                    "proofOps": null,
+                    "height": height.unwrap_or(HEIGHT).to_string(),
+                    "codespace": codespace
+                }
+            }
+        })
+        .to_string())
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        #[async_trait(?Send)]
+        impl RpcTransport for FakeAbci {
+            async fn post_json_bytes(&self, _url: &str, body: Vec<u8>) -> Result<String> {
+                self.respond(body)
+            }
+        }
+    } else {
+        #[async_trait]
+        impl RpcTransport for FakeAbci {
+            async fn post_json_bytes(&self, _url: &str, body: Vec<u8>) -> Result<String> {
+                self.respond(body)
+            }
+        }
+    }
+}
+
+pub(crate) fn chain_config() -> ChainConfig {
+    ChainConfig {
+        chain_id: ChainId::new("test-1"),
+        rpc_endpoint: Some("http://localhost:26657".to_string()),
+        gas_price: 0.025,
+        gas_denom: "uatom".to_string(),
+        ..Default::default()
+    }
+}
+
+/// A client over rpc, where every abci query goes to `handler`, see `FakeAbci`
+pub(crate) fn query_client(
+    handler: impl Fn(&str, &[u8], Option<u64>) -> std::result::Result<Vec<u8>, AbciQueryError>
+        + Send
+        + Sync
+        + 'static,
+) -> QueryClient {
+    let rpc = Arc::new(FakeAbci {
+        handler: Box::new(handler),
+    });
+
+    block_on(QueryClient::new(
+        chain_config(),
+        Some(Connection {
+            rpc,
+            preferred_mode: Some(ConnectionMode::Rpc),
+        }),
+    ))
+    .unwrap()
+}
+
+/// A signing client on top of `querier`, without querying the account
+pub(crate) fn signing_client(querier: QueryClient) -> SigningClient {
+    let signer = KeySigner::new_mnemonic_str(MNEMONIC, None).unwrap();
+    let addr = block_on(signer.address(&querier.chain_config)).unwrap();
+
+    SigningClient {
+        querier,
+        signer: Arc::new(signer),
+        addr,
+        account_number: 1,
+        middleware_map_body: Arc::new(middleware::default_middleware_map_body()),
+        middleware_map_auth_info: Arc::new(middleware::default_middleware_map_auth_info()),
+        middleware_map_resp: Arc::new(middleware::default_middleware_map_resp()),
+        sequence_strategy: SequenceStrategy::new(SequenceStrategyKind::Query),
+    }
+}
+
+/// An abci error like the sdk returns for a missing item
+pub(crate) fn not_found(log: &str) -> AbciQueryError {
+    AbciQueryError {
+        codespace: "sdk".to_string(),
+        code: 38,
+        log: log.to_string(),
+    }
+}
+
+/// The address of the `n`th account of the test mnemonic, e.g. for a grantee
+pub(crate) fn address(n: u32) -> Address {
+    let signer = KeySigner::new_mnemonic_str(
+        MNEMONIC,
+        Some(&layer_climb_signer::cosmos_hub_derivation(n).unwrap()),
+    )
+    .unwrap();
+
+    // not via `TxSigner::address()`, so it can be used inside a fake handler too
+    let public_key =
+        tendermint::PublicKey::from_raw_secp256k1(&signer.key.public_key().to_bytes()).unwrap();
+
+    chain_config().address_from_pub_key(&public_key).unwrap()
+}
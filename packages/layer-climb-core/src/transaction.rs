@@ -1,12 +1,20 @@
+pub mod amino;
+pub mod msg_registry;
+pub mod offline;
+
 use crate::prelude::*;
 use crate::querier::tx::AnyTxResponse;
-use crate::signing::middleware::{SigningMiddlewareMapBody, SigningMiddlewareMapResp};
+use crate::signing::middleware::{
+    SigningMiddlewareMapAuthInfo, SigningMiddlewareMapBody, SigningMiddlewareMapResp,
+};
 use std::sync::{
-    atomic::{AtomicBool, AtomicU64},
+    atomic::{AtomicBool, AtomicU64, Ordering},
     Arc,
 };
 
+use amino::{AminoMsgRegistry, DEFAULT_AMINO_MSG_REGISTRY};
 use layer_climb_signer::TxSigner;
+use offline::UnsignedTx;
 
 pub struct TxBuilder<'a> {
     pub querier: &'a QueryClient,
@@ -18,6 +26,9 @@ pub struct TxBuilder<'a> {
     /// how many blocks until a tx is considered invalid
     /// if not set, the default is 10 blocks
     pub tx_timeout_blocks: Option<u64>,
+    /// an absolute timeout height, takes precedence over `tx_timeout_blocks`
+    /// and means the current block height doesn't need to be queried (e.g. when building a tx offline)
+    pub timeout_height: Option<u64>,
     /// for manually overriding the sequence number, e.g. parallel transactions (multiple *messages* in a tx do not need this)
     pub sequence_strategy: Option<SequenceStrategy>,
 
@@ -27,9 +38,22 @@ pub struct TxBuilder<'a> {
     pub memo: Option<String>,
 
     /// The gas coin to use. Gas price (in gas_coin.denom) = gas_coin.amount * gas_units
-    /// If not set, it will be derived from querier.chain_config (without hitting the network)
+    /// If not set, it will be derived from `querier.gas_price()` (which only hits the network for a dynamic `GasPriceSource`)
     pub gas_coin: Option<layer_climb_proto::Coin>,
 
+    /// An account with a fee allowance (see `SigningClient::feegrant_grant_*`) for the signer
+    /// which pays the fee instead of the signer
+    pub fee_granter: Option<Address>,
+
+    /// Who pays the fee, if not the signer, written to `Fee.payer`
+    /// The sdk requires the payer to sign the tx as well, but a TxBuilder only adds its own signer,
+    /// so a payer other than the signer is rejected by the chain - use `fee_granter` for that instead
+    pub fee_payer: Option<Address>,
+
+    /// A multiplier on top of the gas price from `querier.gas_price()`, e.g. 1.2 to pay 20% over a fee market's base fee
+    /// Not used if `gas_coin` is set. If not set, the default is 1.0
+    pub gas_price_premium: Option<f32>,
+
     /// The maximum gas units. Gas price (in gas_coin.denom) = gas_coin.amount * gas_units
     /// If not set, it will be derived from running an on-chain simulation multiplied by `gas_multiplier`
     pub gas_units_or_simulate: Option<u64>,
@@ -53,31 +77,55 @@ pub struct TxBuilder<'a> {
     /// If not set, the default is 30 seconds
     pub broadcast_poll_timeout_duration: Option<std::time::Duration>,
 
-    /// Middleware to run before the tx is broadcast
-    pub middleware_map_body: Option<Arc<Vec<SigningMiddlewareMapBody>>>,
+    /// How many times to re-sign and rebroadcast after an "account sequence mismatch" error
+    /// the sequence is resynced from the error each time (not possible with `SequenceStrategyKind::Constant`)
+    /// If not set, the default is 3
+    pub max_sequence_mismatch_retries: Option<u32>,
+
+    /// Middleware to run on the body before the tx is simulated and signed
+    pub middleware_map_body: Option<Arc<Vec<Arc<dyn SigningMiddlewareMapBody>>>>,
+
+    /// Middleware to run on the auth info (i.e. once the fee is known) before the tx is signed
+    pub middleware_map_auth_info: Option<Arc<Vec<Arc<dyn SigningMiddlewareMapAuthInfo>>>>,
 
     /// Middleware to run after the tx is broadcast
-    pub middleware_map_resp: Option<Arc<Vec<SigningMiddlewareMapResp>>>,
+    pub middleware_map_resp: Option<Arc<Vec<Arc<dyn SigningMiddlewareMapResp>>>>,
+
+    /// The sign mode to use, either `Direct` or `LegacyAminoJson`
+    /// If not set, the default is `Direct`
+    pub sign_mode: Option<layer_climb_proto::tx::signing::SignMode>,
+
+    /// Used to convert messages to amino json when signing with `LegacyAminoJson`
+    /// If not set, the default registry is used (bank, wasm, authz, staking and ibc transfer)
+    pub amino_msg_registry: Option<Arc<AminoMsgRegistry>>,
 }
 
 impl<'a> TxBuilder<'a> {
     const DEFAULT_TX_TIMEOUT_BLOCKS: u64 = 10;
     const DEFAULT_GAS_MULTIPLIER: f32 = 1.5;
+    const DEFAULT_GAS_PRICE_PREMIUM: f32 = 1.0;
     const DEFAULT_BROADCAST_MODE: layer_climb_proto::tx::BroadcastMode =
         layer_climb_proto::tx::BroadcastMode::Sync;
     const DEFAULT_BROADCAST_POLL_SLEEP_DURATION: std::time::Duration =
         std::time::Duration::from_secs(1);
     const DEFAULT_BROADCAST_POLL_TIMEOUT_DURATION: std::time::Duration =
         std::time::Duration::from_secs(30);
+    const DEFAULT_MAX_SEQUENCE_MISMATCH_RETRIES: u32 = 3;
+    const DEFAULT_SIGN_MODE: layer_climb_proto::tx::signing::SignMode =
+        layer_climb_proto::tx::signing::SignMode::Direct;
 
     pub fn new(querier: &'a QueryClient, signer: &'a dyn TxSigner) -> Self {
         Self {
             querier,
             signer,
             gas_coin: None,
+            gas_price_premium: None,
+            fee_granter: None,
+            fee_payer: None,
             sender: None,
             memo: None,
             tx_timeout_blocks: None,
+            timeout_height: None,
             sequence_strategy: None,
             gas_units_or_simulate: None,
             gas_simulate_multiplier: None,
@@ -86,8 +134,12 @@ impl<'a> TxBuilder<'a> {
             broadcast_poll: true,
             broadcast_poll_sleep_duration: None,
             broadcast_poll_timeout_duration: None,
+            max_sequence_mismatch_retries: None,
             middleware_map_body: None,
+            middleware_map_auth_info: None,
             middleware_map_resp: None,
+            sign_mode: None,
+            amino_msg_registry: None,
         }
     }
 
@@ -96,6 +148,11 @@ impl<'a> TxBuilder<'a> {
         self
     }
 
+    pub fn set_timeout_height(&mut self, timeout_height: u64) -> &mut Self {
+        self.timeout_height = Some(timeout_height);
+        self
+    }
+
     pub fn set_memo(&mut self, memo: impl Into<String>) -> &mut Self {
         self.memo = Some(memo.into());
         self
@@ -116,6 +173,21 @@ impl<'a> TxBuilder<'a> {
         self
     }
 
+    pub fn set_gas_price_premium(&mut self, gas_price_premium: f32) -> &mut Self {
+        self.gas_price_premium = Some(gas_price_premium);
+        self
+    }
+
+    pub fn set_fee_granter(&mut self, fee_granter: Address) -> &mut Self {
+        self.fee_granter = Some(fee_granter);
+        self
+    }
+
+    pub fn set_fee_payer(&mut self, fee_payer: Address) -> &mut Self {
+        self.fee_payer = Some(fee_payer);
+        self
+    }
+
     pub fn set_gas_units_or_simulate(&mut self, gas_units: Option<u64>) -> &mut Self {
         self.gas_units_or_simulate = gas_units;
         self
@@ -160,22 +232,51 @@ impl<'a> TxBuilder<'a> {
         self
     }
 
+    pub fn set_max_sequence_mismatch_retries(&mut self, max_retries: u32) -> &mut Self {
+        self.max_sequence_mismatch_retries = Some(max_retries);
+        self
+    }
+
     pub fn set_middleware_map_body(
         &mut self,
-        middleware_map_body: Arc<Vec<SigningMiddlewareMapBody>>,
+        middleware_map_body: Arc<Vec<Arc<dyn SigningMiddlewareMapBody>>>,
     ) -> &mut Self {
         self.middleware_map_body = Some(middleware_map_body);
         self
     }
 
+    pub fn set_middleware_map_auth_info(
+        &mut self,
+        middleware_map_auth_info: Arc<Vec<Arc<dyn SigningMiddlewareMapAuthInfo>>>,
+    ) -> &mut Self {
+        self.middleware_map_auth_info = Some(middleware_map_auth_info);
+        self
+    }
+
     pub fn set_middleware_map_resp(
         &mut self,
-        middleware_map_resp: Arc<Vec<SigningMiddlewareMapResp>>,
+        middleware_map_resp: Arc<Vec<Arc<dyn SigningMiddlewareMapResp>>>,
     ) -> &mut Self {
         self.middleware_map_resp = Some(middleware_map_resp);
         self
     }
 
+    pub fn set_sign_mode(
+        &mut self,
+        sign_mode: layer_climb_proto::tx::signing::SignMode,
+    ) -> &mut Self {
+        self.sign_mode = Some(sign_mode);
+        self
+    }
+
+    pub fn set_amino_msg_registry(
+        &mut self,
+        amino_msg_registry: Arc<AminoMsgRegistry>,
+    ) -> &mut Self {
+        self.amino_msg_registry = Some(amino_msg_registry);
+        self
+    }
+
     async fn query_base_account(&self) -> Result<layer_climb_proto::auth::BaseAccount> {
         self.querier
             .base_account(
@@ -206,59 +307,100 @@ impl<'a> TxBuilder<'a> {
         signer_info: layer_climb_proto::tx::SignerInfo,
         account_number: u64,
         // mutable so we can set the timeout_height here
-        tx_body: &mut layer_climb_proto::tx::TxBody,
+        tx_body: &mut layer_climb_proto::tx::TxBodyV053,
     ) -> Result<layer_climb_proto::abci::GasInfo> {
+        self.simulate_body(signer_info, account_number, tx_body)
+            .await?
+            .gas_info
+            .context("unable to get gas from simulation")
+    }
+
+    /// Simulates the messages without broadcasting anything
+    /// the result has everything the real tx would, i.e. events, msg responses, and the fee that would be paid
+    pub async fn simulate(
+        &self,
+        messages: impl IntoIterator<Item = layer_climb_proto::Any>,
+    ) -> Result<SimulationResult> {
+        if self.unordered_ttl().is_some() {
+            self.check_unordered_support().await?;
+        }
+
+        let account_number = self.get_account_number().await?;
+        let mut body = self.tx_body(messages.into_iter().collect()).await?;
+
+        let signer_info = self
+            .signer
+            .signer_info(
+                self.current_sequence().await?,
+                layer_climb_proto::tx::signing::SignMode::Unspecified,
+            )
+            .await?;
+
+        let response = self
+            .simulate_body(signer_info, account_number, &mut body)
+            .await?;
+
+        let gas_info = response
+            .gas_info
+            .clone()
+            .context("unable to get gas from simulation")?;
+
+        let gas_units = match self.gas_units_or_simulate {
+            Some(gas_units) => gas_units,
+            None => self.simulated_gas_units(&gas_info),
+        };
+
+        let mut fee = self.fee(gas_units).await?;
+        self.apply_fee_granter_and_payer(&mut fee);
+
+        let msg_responses = response
+            .result
+            .as_ref()
+            .map(|result| result.msg_responses.clone())
+            .unwrap_or_default();
+
+        Ok(SimulationResult {
+            gas_info,
+            gas_units,
+            fee,
+            msg_responses,
+            response,
+        })
+    }
+
+    async fn simulate_body(
+        &self,
+        signer_info: layer_climb_proto::tx::SignerInfo,
+        account_number: u64,
+        tx_body: &mut layer_climb_proto::tx::TxBodyV053,
+    ) -> Result<layer_climb_proto::tx::SimulateResponse> {
         let fee = FeeCalculation::Simulation {
             chain_config: &self.querier.chain_config,
         }
         .calculate()?;
 
-        let simulate_tx_resp = self
-            .querier
-            .simulate_tx(
-                self.sign_tx(signer_info, account_number, tx_body, fee, true)
-                    .await?,
-            )
+        let simulation_signature = simulation_signature(&signer_info)?;
+        let sign_doc = self
+            .make_sign_doc(signer_info, account_number, tx_body, fee)
             .await?;
 
-        simulate_tx_resp
-            .gas_info
-            .context("unable to get gas from simulation")
+        self.querier
+            .simulate_tx(tx_raw_bytes(&sign_doc, simulation_signature)?)
+            .await
     }
 
     pub async fn current_sequence(&self) -> Result<u64> {
-        let sequence = match &self.sequence_strategy {
-            Some(sequence_strategy) => match sequence_strategy.kind {
-                SequenceStrategyKind::Query => {
-                    let base_account = self.query_base_account().await?;
-                    base_account.sequence
-                }
-                SequenceStrategyKind::QueryAndIncrement => {
-                    if !sequence_strategy
-                        .has_queried
-                        .load(std::sync::atomic::Ordering::SeqCst)
-                    {
-                        let base_account = self.query_base_account().await?;
-                        sequence_strategy
-                            .has_queried
-                            .store(true, std::sync::atomic::Ordering::SeqCst);
-                        sequence_strategy
-                            .value
-                            .store(base_account.sequence, std::sync::atomic::Ordering::SeqCst);
-                        base_account.sequence
-                    } else {
-                        sequence_strategy
-                            .value
-                            .load(std::sync::atomic::Ordering::SeqCst)
-                    }
-                }
-                SequenceStrategyKind::SetAndIncrement(_) => sequence_strategy
-                    .value
-                    .load(std::sync::atomic::Ordering::SeqCst),
-                SequenceStrategyKind::Constant(n) => n,
-            },
+        let sequence = match self
+            .sequence_strategy
+            .as_ref()
+            .and_then(|sequence_strategy| sequence_strategy.known_sequence())
+        {
+            Some(sequence) => sequence,
             None => {
                 let base_account = self.query_base_account().await?;
+                if let Some(sequence_strategy) = self.sequence_strategy.as_ref() {
+                    sequence_strategy.set_queried(base_account.sequence);
+                }
                 base_account.sequence
             }
         };
@@ -279,35 +421,71 @@ impl<'a> TxBuilder<'a> {
         self,
         messages: Vec<layer_climb_proto::Any>,
     ) -> Result<AnyTxResponse> {
-        let account_number = match self.account_number {
-            Some(account_number) => account_number,
-            None => self.query_base_account().await?.account_number,
-        };
-
-        let mut body = layer_climb_proto::tx::TxBody {
-            messages,
-            memo: self.memo.as_deref().unwrap_or("").to_string(),
-            timeout_height: 0, // will be set later so we don't get delayed by other async calls before we send
-            extension_options: Default::default(),
-            non_critical_extension_options: Default::default(),
-        };
+        let max_retries = self
+            .max_sequence_mismatch_retries
+            .unwrap_or(Self::DEFAULT_MAX_SEQUENCE_MISMATCH_RETRIES);
+
+        let mut retries = 0;
+
+        loop {
+            // the sequence mismatch can come from either simulation or broadcasting
+            match self.sign_and_broadcast(messages.clone()).await {
+                Err(err) if retries < max_retries => {
+                    let Some(expected) = sequence_mismatch(&err) else {
+                        return Err(err);
+                    };
+
+                    if !self.resync_sequence(expected) {
+                        return Err(err);
+                    }
 
-        if let Some(middleware) = self.middleware_map_body.as_ref() {
-            for middleware in middleware.iter() {
-                body = match middleware.map_body(body).await {
-                    Ok(req) => req,
-                    Err(e) => return Err(e),
+                    retries += 1;
+                    tracing::warn!(
+                        "account sequence mismatch, retrying with sequence {expected} (attempt {retries}/{max_retries})"
+                    );
                 }
+                res => return res,
             }
         }
+    }
+
+    async fn sign_and_broadcast(
+        &self,
+        messages: Vec<layer_climb_proto::Any>,
+    ) -> Result<AnyTxResponse> {
+        let sign_doc = self.sign_doc(messages).await?;
+        let signature = self.sign(&sign_doc).await?;
+
+        self.broadcast_signed(&sign_doc, signature).await
+    }
+
+    // returns false if the sequence can't be fixed by retrying
+    fn resync_sequence(&self, expected: u64) -> bool {
+        match self.sequence_strategy.as_ref() {
+            Some(sequence_strategy) => sequence_strategy.resync(expected),
+            None => true,
+        }
+    }
+
+    /// Builds the final `SignDoc` for these messages without signing it
+    /// i.e. gas is simulated (if needed), and the fee, sequence and timeout are all fixed
+    ///
+    /// Useful when the signature is collected some other way, e.g. from each member of a `MultisigSigner`
+    /// and then broadcast via `broadcast_signed`
+    pub async fn sign_doc(
+        &self,
+        messages: Vec<layer_climb_proto::Any>,
+    ) -> Result<layer_climb_proto::tx::SignDoc> {
+        if self.unordered_ttl().is_some() {
+            self.check_unordered_support().await?;
+        }
+
+        let account_number = self.get_account_number().await?;
+        let mut body = self.tx_body(messages).await?;
 
         let gas_units = match self.gas_units_or_simulate {
             Some(gas_units) => gas_units,
             None => {
-                let gas_multiplier = self
-                    .gas_simulate_multiplier
-                    .unwrap_or(Self::DEFAULT_GAS_MULTIPLIER);
-
                 let signer_info = self
                     .signer
                     .signer_info(
@@ -320,34 +498,87 @@ impl<'a> TxBuilder<'a> {
                     .simulate_gas(signer_info, account_number, &mut body)
                     .await?;
 
-                (gas_info.gas_used as f32 * gas_multiplier).ceil() as u64
+                self.simulated_gas_units(&gas_info)
             }
         };
 
-        let fee = match self.gas_coin.clone() {
-            Some(gas_coin) => FeeCalculation::RealCoin {
-                gas_coin,
-                gas_units,
-            }
-            .calculate()?,
-            None => FeeCalculation::RealNetwork {
-                chain_config: &self.querier.chain_config,
-                gas_units,
-            }
-            .calculate()?,
-        };
+        let fee = self.fee(gas_units).await?;
 
         let signer_info = self
             .signer
             .signer_info(
                 self.current_sequence().await?,
-                layer_climb_proto::tx::signing::SignMode::Direct,
+                self.sign_mode.unwrap_or(Self::DEFAULT_SIGN_MODE),
             )
             .await?;
 
-        let tx_bytes = self
-            .sign_tx(signer_info, account_number, &mut body, fee, false)
-            .await?;
+        self.make_sign_doc(signer_info, account_number, &mut body, fee)
+            .await
+    }
+
+    /// Signs a `SignDoc` (typically from `sign_doc()`) according to the builder's sign mode
+    /// for `LegacyAminoJson`, the equivalent `StdSignDoc` is what actually gets signed
+    pub async fn sign(&self, sign_doc: &layer_climb_proto::tx::SignDoc) -> Result<Vec<u8>> {
+        let registry = match self.amino_msg_registry.as_ref() {
+            Some(registry) => registry.as_ref(),
+            None => &DEFAULT_AMINO_MSG_REGISTRY,
+        };
+
+        sign_with_mode(
+            self.signer,
+            sign_doc,
+            self.sign_mode.unwrap_or(Self::DEFAULT_SIGN_MODE),
+            registry,
+        )
+        .await
+    }
+
+    /// Builds the tx without signing or broadcasting it, e.g. to be signed on another host
+    /// see `offline` for the full workflow
+    pub async fn build_unsigned(
+        &self,
+        messages: impl IntoIterator<Item = layer_climb_proto::Any>,
+    ) -> Result<UnsignedTx> {
+        Ok(self.sign_doc(messages.into_iter().collect()).await?.into())
+    }
+
+    /// Broadcasts a `SignDoc` (typically from `sign_doc()`) along with its signature
+    pub async fn broadcast_signed(
+        &self,
+        sign_doc: &layer_climb_proto::tx::SignDoc,
+        signature: Vec<u8>,
+    ) -> Result<AnyTxResponse> {
+        let mut tx_response = match self.broadcast_signed_unmapped(sign_doc, signature).await {
+            Ok(tx_response) => tx_response,
+            Err(err) => {
+                if let Some(middleware) = self.middleware_map_resp.as_ref() {
+                    for middleware in middleware.iter() {
+                        middleware.on_error(&err).await;
+                    }
+                }
+                return Err(err);
+            }
+        };
+
+        if let Some(middleware) = self.middleware_map_resp.as_ref() {
+            for middleware in middleware.iter() {
+                tx_response = match middleware.map_resp(tx_response).await {
+                    Ok(req) => req,
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        Ok(tx_response)
+    }
+
+    // everything up to the response middleware
+    async fn broadcast_signed_unmapped(
+        &self,
+        sign_doc: &layer_climb_proto::tx::SignDoc,
+        signature: Vec<u8>,
+    ) -> Result<AnyTxResponse> {
+        let tx_bytes = tx_raw_bytes(sign_doc, signature)?;
         let broadcast_mode = self.broadcast_mode.unwrap_or(Self::DEFAULT_BROADCAST_MODE);
 
         let tx_response = self
@@ -356,15 +587,15 @@ impl<'a> TxBuilder<'a> {
             .await?;
 
         if tx_response.code() != 0 {
-            bail!(
-                "tx failed with code: {}, codespace: {}, raw_log: {}",
-                tx_response.code(),
-                tx_response.codespace(),
-                tx_response.raw_log()
-            );
+            return Err(ClimbError::from(TxError::new(TxStage::CheckTx, &tx_response)).into());
+        }
+
+        // passing CheckTx means the sequence is used up, even if the tx later fails in DeliverTx
+        if let Some(sequence_strategy) = self.sequence_strategy.as_ref() {
+            sequence_strategy.increment();
         }
 
-        let mut tx_response = if self.broadcast_poll {
+        let tx_response = if self.broadcast_poll {
             let sleep_duration = self
                 .broadcast_poll_sleep_duration
                 .unwrap_or(Self::DEFAULT_BROADCAST_POLL_SLEEP_DURATION);
@@ -383,87 +614,253 @@ impl<'a> TxBuilder<'a> {
         };
 
         if tx_response.code() != 0 {
-            bail!(
-                "tx failed with code: {}, codespace: {}, raw_log: {}",
-                tx_response.code(),
-                tx_response.codespace(),
-                tx_response.raw_log()
-            );
+            return Err(ClimbError::from(TxError::new(TxStage::DeliverTx, &tx_response)).into());
         }
 
-        // TODO not sure about this... should increase even if failed?
-        if let Some(sequence) = self.sequence_strategy {
-            match sequence.kind {
-                SequenceStrategyKind::QueryAndIncrement => {
-                    sequence
-                        .value
-                        .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                }
-                SequenceStrategyKind::SetAndIncrement(_) => {
-                    sequence
-                        .value
-                        .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                }
-                _ => {}
-            }
+        Ok(tx_response)
+    }
+
+    fn unordered_ttl(&self) -> Option<std::time::Duration> {
+        match self
+            .sequence_strategy
+            .as_ref()
+            .map(|strategy| &strategy.kind)
+        {
+            Some(SequenceStrategyKind::Unordered(ttl)) => Some(*ttl),
+            _ => None,
         }
+    }
 
-        if let Some(middleware) = self.middleware_map_resp.as_ref() {
+    // only checked once per SequenceStrategy
+    async fn check_unordered_support(&self) -> Result<()> {
+        let Some(sequence_strategy) = self.sequence_strategy.as_ref() else {
+            return Ok(());
+        };
+
+        if sequence_strategy
+            .has_queried
+            .load(std::sync::atomic::Ordering::SeqCst)
+        {
+            return Ok(());
+        }
+
+        if !self.querier.supports_unordered_txs().await? {
+            bail!("unordered txs are not supported by this node (requires cosmos-sdk 0.53+)");
+        }
+
+        sequence_strategy
+            .has_queried
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    async fn get_account_number(&self) -> Result<u64> {
+        match self.account_number {
+            Some(account_number) => Ok(account_number),
+            None => Ok(self.query_base_account().await?.account_number),
+        }
+    }
+
+    async fn tx_body(
+        &self,
+        messages: Vec<layer_climb_proto::Any>,
+    ) -> Result<layer_climb_proto::tx::TxBodyV053> {
+        let mut body = layer_climb_proto::tx::TxBodyV053 {
+            messages,
+            memo: self.memo.as_deref().unwrap_or("").to_string(),
+            // timeouts will be set later so we don't get delayed by other async calls before we send
+            timeout_height: 0,
+            unordered: false,
+            timeout_timestamp: None,
+            extension_options: Default::default(),
+            non_critical_extension_options: Default::default(),
+        };
+
+        if let Some(middleware) = self.middleware_map_body.as_ref() {
             for middleware in middleware.iter() {
-                tx_response = match middleware.map_resp(tx_response).await {
+                body = match middleware.map_body(body).await {
                     Ok(req) => req,
                     Err(e) => return Err(e),
                 }
             }
         }
 
-        Ok(tx_response)
+        Ok(body)
+    }
+
+    fn simulated_gas_units(&self, gas_info: &layer_climb_proto::abci::GasInfo) -> u64 {
+        let gas_multiplier = self
+            .gas_simulate_multiplier
+            .unwrap_or(Self::DEFAULT_GAS_MULTIPLIER);
+
+        (gas_info.gas_used as f32 * gas_multiplier).ceil() as u64
+    }
+
+    async fn fee(&self, gas_units: u64) -> Result<layer_climb_proto::tx::Fee> {
+        match self.gas_coin.clone() {
+            Some(gas_coin) => FeeCalculation::RealCoin {
+                gas_coin,
+                gas_units,
+            }
+            .calculate(),
+            None => FeeCalculation::RealNetwork {
+                chain_config: &self.querier.chain_config,
+                gas_price: self.querier.gas_price().await?
+                    * self
+                        .gas_price_premium
+                        .unwrap_or(Self::DEFAULT_GAS_PRICE_PREMIUM),
+                gas_units,
+            }
+            .calculate(),
+        }
+    }
+
+    fn apply_fee_granter_and_payer(&self, fee: &mut layer_climb_proto::tx::Fee) {
+        if let Some(fee_granter) = self.fee_granter.as_ref() {
+            fee.granter = fee_granter.to_string();
+        }
+        if let Some(fee_payer) = self.fee_payer.as_ref() {
+            fee.payer = fee_payer.to_string();
+        }
     }
 
-    async fn sign_tx(
+    async fn make_sign_doc(
         &self,
         signer_info: layer_climb_proto::tx::SignerInfo,
         account_number: u64,
         // mutable so we can set the timeout_height here
-        body: &mut layer_climb_proto::tx::TxBody,
-        fee: layer_climb_proto::tx::Fee,
-        simulate_only: bool,
-    ) -> Result<Vec<u8>> {
+        body: &mut layer_climb_proto::tx::TxBodyV053,
+        mut fee: layer_climb_proto::tx::Fee,
+    ) -> Result<layer_climb_proto::tx::SignDoc> {
+        // set here so that simulation also runs with the granter, since the ante handler checks the allowance
+        self.apply_fee_granter_and_payer(&mut fee);
+
         #[allow(deprecated)]
-        let auth_info = layer_climb_proto::tx::AuthInfo {
+        let mut auth_info = layer_climb_proto::tx::AuthInfo {
             signer_infos: vec![signer_info],
             fee: Some(fee),
             tip: None,
         };
 
-        let block_height = self.querier.block_height().await?;
+        match self.unordered_ttl() {
+            // unordered txs expire by block time instead of height
+            Some(ttl) => {
+                let block_time = self
+                    .querier
+                    .block_header(None)
+                    .await?
+                    .time()
+                    .context("block header has no time")?;
+
+                set_unordered(body, self.timeout_height, block_time, ttl)?;
+            }
+            None => {
+                body.timeout_height = match self.timeout_height {
+                    Some(timeout_height) => timeout_height,
+                    None => {
+                        let block_height = self.querier.block_height().await?;
+
+                        let tx_timeout_blocks = self
+                            .tx_timeout_blocks
+                            .unwrap_or(Self::DEFAULT_TX_TIMEOUT_BLOCKS);
 
-        let tx_timeout_blocks = self
-            .tx_timeout_blocks
-            .unwrap_or(Self::DEFAULT_TX_TIMEOUT_BLOCKS);
+                        // latest possible time we can grab the current block height
+                        block_height + tx_timeout_blocks
+                    }
+                };
+            }
+        }
 
-        // latest possible time we can grab the current block height
-        body.timeout_height = block_height + tx_timeout_blocks;
+        if let Some(middleware) = self.middleware_map_auth_info.as_ref() {
+            for middleware in middleware.iter() {
+                auth_info = middleware.map_auth_info(body, auth_info).await?;
+            }
+        }
 
-        let sign_doc = layer_climb_proto::tx::SignDoc {
+        Ok(layer_climb_proto::tx::SignDoc {
             body_bytes: proto_into_bytes(body)?,
             auth_info_bytes: proto_into_bytes(&auth_info)?,
             chain_id: self.querier.chain_config.chain_id.to_string(),
             account_number,
-        };
+        })
+    }
+}
 
-        let signature = match simulate_only {
-            true => Vec::new(),
-            false => self.signer.sign(&sign_doc).await?,
-        };
+async fn sign_with_mode(
+    signer: &dyn TxSigner,
+    sign_doc: &layer_climb_proto::tx::SignDoc,
+    sign_mode: layer_climb_proto::tx::signing::SignMode,
+    registry: &AminoMsgRegistry,
+) -> Result<Vec<u8>> {
+    match sign_mode {
+        layer_climb_proto::tx::signing::SignMode::Direct => signer.sign(sign_doc).await,
+        layer_climb_proto::tx::signing::SignMode::LegacyAminoJson => {
+            signer.sign_amino(&registry.std_sign_doc(sign_doc)?).await
+        }
+        sign_mode => bail!("unsupported sign mode: {}", sign_mode.as_str_name()),
+    }
+}
 
-        let tx_raw = layer_climb_proto::tx::TxRaw {
-            body_bytes: sign_doc.body_bytes.clone(),
-            auth_info_bytes: sign_doc.auth_info_bytes.clone(),
-            signatures: vec![signature],
-        };
+// the mismatch can come from simulating or broadcasting, either way it's a typed TxError
+fn sequence_mismatch(err: &anyhow::Error) -> Option<u64> {
+    match ClimbError::from_anyhow(err)?.tx_error()?.kind() {
+        TxErrorKind::SequenceMismatch { expected } => expected,
+        _ => None,
+    }
+}
 
-        proto_into_bytes(&tx_raw)
+// the chain checks the timeout against the block time, so it's relative to the latest block rather than the local clock
+fn set_unordered(
+    body: &mut layer_climb_proto::tx::TxBodyV053,
+    timeout_height: Option<u64>,
+    block_time: layer_climb_proto::Timestamp,
+    ttl: std::time::Duration,
+) -> Result<()> {
+    body.unordered = true;
+    body.timeout_height = timeout_height.unwrap_or_default();
+    body.timeout_timestamp = Some(add_duration(block_time, ttl)?);
+
+    Ok(())
+}
+
+fn add_duration(
+    timestamp: layer_climb_proto::Timestamp,
+    duration: std::time::Duration,
+) -> Result<layer_climb_proto::Timestamp> {
+    let nanos = timestamp.nanos as u64 + duration.subsec_nanos() as u64;
+
+    Ok(layer_climb_proto::Timestamp {
+        seconds: timestamp.seconds
+            + i64::try_from(duration.as_secs())?
+            + i64::try_from(nanos / 1_000_000_000)?,
+        nanos: (nanos % 1_000_000_000).try_into()?,
+    })
+}
+
+fn tx_raw_bytes(sign_doc: &layer_climb_proto::tx::SignDoc, signature: Vec<u8>) -> Result<Vec<u8>> {
+    let tx_raw = layer_climb_proto::tx::TxRaw {
+        body_bytes: sign_doc.body_bytes.clone(),
+        auth_info_bytes: sign_doc.auth_info_bytes.clone(),
+        signatures: vec![signature],
+    };
+
+    proto_into_bytes(&tx_raw)
+}
+
+// signatures aren't verified in simulation, but a multisig still needs one (empty) signature per signer
+fn simulation_signature(signer_info: &layer_climb_proto::tx::SignerInfo) -> Result<Vec<u8>> {
+    match signer_info
+        .mode_info
+        .as_ref()
+        .and_then(|mode| mode.sum.as_ref())
+    {
+        Some(layer_climb_proto::tx::mode_info::Sum::Multi(multi)) => {
+            proto_into_bytes(&layer_climb_proto::crypto::multisig::MultiSignature {
+                signatures: vec![Vec::new(); multi.mode_infos.len()],
+            })
+        }
+        _ => Ok(Vec::new()),
     }
 }
 
@@ -482,23 +879,122 @@ impl SequenceStrategy {
                 SequenceStrategyKind::QueryAndIncrement => 0, // will be ignored
                 SequenceStrategyKind::SetAndIncrement(n) => n,
                 SequenceStrategyKind::Constant(n) => n,
+                SequenceStrategyKind::Unordered(_) => 0, // will be ignored
             })),
             kind,
             has_queried: Arc::new(AtomicBool::new(false)),
         }
     }
+
+    /// The sequence to use without querying the account, if there is one
+    pub fn known_sequence(&self) -> Option<u64> {
+        match self.kind {
+            SequenceStrategyKind::Query => None,
+            SequenceStrategyKind::QueryAndIncrement => self
+                .has_queried
+                .load(Ordering::SeqCst)
+                .then(|| self.value.load(Ordering::SeqCst)),
+            SequenceStrategyKind::SetAndIncrement(_) => Some(self.value.load(Ordering::SeqCst)),
+            SequenceStrategyKind::Constant(n) => Some(n),
+            // not used for replay protection
+            SequenceStrategyKind::Unordered(_) => Some(0),
+        }
+    }
+
+    // the queried sequence is only kept for QueryAndIncrement, Query always asks again
+    fn set_queried(&self, sequence: u64) {
+        if let SequenceStrategyKind::QueryAndIncrement = self.kind {
+            self.value.store(sequence, Ordering::SeqCst);
+            self.has_queried.store(true, Ordering::SeqCst);
+        }
+    }
+
+    fn increment(&self) {
+        match self.kind {
+            SequenceStrategyKind::QueryAndIncrement | SequenceStrategyKind::SetAndIncrement(_) => {
+                self.value.fetch_add(1, Ordering::SeqCst);
+            }
+            _ => {}
+        }
+    }
+
+    // returns false if the sequence can't be fixed by retrying
+    fn resync(&self, expected: u64) -> bool {
+        match self.kind {
+            SequenceStrategyKind::QueryAndIncrement | SequenceStrategyKind::SetAndIncrement(_) => {
+                self.value.store(expected, Ordering::SeqCst);
+                self.has_queried.store(true, Ordering::SeqCst);
+                true
+            }
+            // will be queried again
+            SequenceStrategyKind::Query => true,
+            SequenceStrategyKind::Constant(_) | SequenceStrategyKind::Unordered(_) => false,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub enum SequenceStrategyKind {
     /// Always query
     Query,
-    /// Query the first time, and then increment each successful tx
+    /// Query the first time, and then increment each tx that passes CheckTx (resynced on sequence mismatch)
     QueryAndIncrement,
-    /// Set to this the first time, and then increment each successful tx
+    /// Set to this the first time, and then increment each tx that passes CheckTx (resynced on sequence mismatch)
     SetAndIncrement(u64),
     /// Set to this each time
     Constant(u64),
+    /// Unordered txs (cosmos-sdk 0.53+), i.e. no sequence at all, so they can be broadcast in parallel
+    /// the tx expires this long after the latest block time (the chain enforces a max, typically 10 minutes)
+    /// whether the node supports it is checked the first time it's used (by sdk version only, see `QueryClient::supports_unordered_txs`)
+    Unordered(std::time::Duration),
+}
+
+/// The result of `TxBuilder::simulate`
+#[derive(Clone, Debug)]
+pub struct SimulationResult {
+    pub gas_info: layer_climb_proto::abci::GasInfo,
+    /// the gas limit the tx would be sent with, i.e. after `gas_simulate_multiplier`
+    pub gas_units: u64,
+    /// the fee the tx would pay
+    pub fee: layer_climb_proto::tx::Fee,
+    pub msg_responses: Vec<layer_climb_proto::Any>,
+    /// the full response from the node
+    pub response: layer_climb_proto::tx::SimulateResponse,
+}
+
+impl SimulationResult {
+    /// The events the tx would emit
+    pub fn events(&self) -> CosmosTxEvents<'static> {
+        let events: Vec<cosmwasm_std::Event> = self
+            .response
+            .result
+            .iter()
+            .flat_map(|result| result.events.iter())
+            .map(|event| crate::events::Event::Tendermint(event).into())
+            .collect();
+
+        events.into()
+    }
+
+    /// The response of the message at `index`, decoded as `T`
+    /// e.g. `msg_response::<layer_climb_proto::wasm::MsgExecuteContractResponse>(0)?.data`
+    pub fn msg_response<T: layer_climb_proto::Name + Default>(&self, index: usize) -> Result<T> {
+        let msg_response = self
+            .msg_responses
+            .get(index)
+            .with_context(|| format!("no msg response at index {index}"))?;
+
+        if msg_response.type_url != T::type_url() {
+            bail!(
+                "msg response at index {index} is a {}, not a {}",
+                msg_response.type_url,
+                T::type_url()
+            );
+        }
+
+        T::decode(msg_response.value.as_slice())
+            .with_context(|| format!("couldn't decode msg response at index {index}"))
+    }
 }
 
 pub enum FeeCalculation<'a> {
@@ -507,6 +1003,8 @@ pub enum FeeCalculation<'a> {
     },
     RealNetwork {
         chain_config: &'a ChainConfig,
+        /// typically from `QueryClient::gas_price()`, in `chain_config.gas_denom`
+        gas_price: f32,
         gas_units: u64,
     },
     RealCoin {
@@ -521,9 +1019,10 @@ impl FeeCalculation<'_> {
             Self::Simulation { chain_config } => (new_coin(0, &chain_config.gas_denom), 0),
             Self::RealNetwork {
                 chain_config,
+                gas_price,
                 gas_units,
             } => {
-                let amount = (chain_config.gas_price * *gas_units as f32).ceil() as u128;
+                let amount = (gas_price * *gas_units as f32).ceil() as u128;
                 (new_coin(amount, &chain_config.gas_denom), *gas_units)
             }
             Self::RealCoin {
@@ -540,3 +1039,153 @@ impl FeeCalculation<'_> {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn simulation_msg_response() {
+        let execute_response = layer_climb_proto::wasm::MsgExecuteContractResponse {
+            data: b"{}".to_vec(),
+        };
+
+        let simulation = SimulationResult {
+            gas_info: Default::default(),
+            gas_units: 0,
+            fee: Default::default(),
+            msg_responses: vec![proto_into_any(&execute_response).unwrap()],
+            response: Default::default(),
+        };
+
+        assert_eq!(
+            simulation
+                .msg_response::<layer_climb_proto::wasm::MsgExecuteContractResponse>(0)
+                .unwrap(),
+            execute_response
+        );
+        assert!(simulation
+            .msg_response::<layer_climb_proto::wasm::MsgInstantiateContractResponse>(0)
+            .is_err());
+        assert!(simulation
+            .msg_response::<layer_climb_proto::wasm::MsgExecuteContractResponse>(1)
+            .is_err());
+    }
+
+    #[test]
+    fn multisig_simulation_signature() {
+        let single = layer_climb_proto::tx::ModeInfo {
+            sum: Some(layer_climb_proto::tx::mode_info::Sum::Single(
+                layer_climb_proto::tx::mode_info::Single {
+                    mode: layer_climb_proto::tx::signing::SignMode::Direct.into(),
+                },
+            )),
+        };
+
+        let multisig = layer_climb_proto::tx::SignerInfo {
+            public_key: None,
+            mode_info: Some(layer_climb_proto::tx::ModeInfo {
+                sum: Some(layer_climb_proto::tx::mode_info::Sum::Multi(
+                    layer_climb_proto::tx::mode_info::Multi {
+                        bitarray: Some(layer_climb_proto::crypto::multisig::CompactBitArray {
+                            extra_bits_stored: 3,
+                            elems: vec![0b1010_0000],
+                        }),
+                        mode_infos: vec![single.clone(), single.clone()],
+                    },
+                )),
+            }),
+            sequence: 0,
+        };
+
+        // one empty signature per signing member
+        assert_eq!(
+            layer_climb_proto::crypto::multisig::MultiSignature::decode(
+                simulation_signature(&multisig).unwrap().as_slice()
+            )
+            .unwrap()
+            .signatures,
+            vec![Vec::<u8>::new(); 2]
+        );
+
+        let single = layer_climb_proto::tx::SignerInfo {
+            mode_info: Some(single),
+            ..multisig
+        };
+        assert!(simulation_signature(&single).unwrap().is_empty());
+    }
+
+    #[test]
+    fn unordered_timeout_timestamp() {
+        let mut body = layer_climb_proto::tx::TxBodyV053::default();
+        let block_time = layer_climb_proto::Timestamp {
+            seconds: 1_700_000_000,
+            nanos: 900_000_000,
+        };
+
+        set_unordered(
+            &mut body,
+            None,
+            block_time,
+            std::time::Duration::from_millis(600_250),
+        )
+        .unwrap();
+
+        assert!(body.unordered);
+        assert_eq!(body.timeout_height, 0);
+        assert_eq!(
+            body.timeout_timestamp,
+            Some(layer_climb_proto::Timestamp {
+                seconds: 1_700_000_601,
+                nanos: 150_000_000,
+            })
+        );
+
+        // an explicit timeout height still applies on top
+        set_unordered(&mut body, Some(100), block_time, std::time::Duration::ZERO).unwrap();
+        assert_eq!(body.timeout_height, 100);
+        assert_eq!(body.timeout_timestamp, Some(block_time));
+    }
+
+    #[test]
+    fn sequence_strategy() {
+        let query = SequenceStrategy::new(SequenceStrategyKind::Query);
+        assert_eq!(query.known_sequence(), None);
+        query.set_queried(5);
+        assert_eq!(query.known_sequence(), None);
+        assert!(query.resync(7));
+        assert_eq!(query.known_sequence(), None);
+
+        let query_and_increment = SequenceStrategy::new(SequenceStrategyKind::QueryAndIncrement);
+        assert_eq!(query_and_increment.known_sequence(), None);
+        query_and_increment.set_queried(5);
+        assert_eq!(query_and_increment.known_sequence(), Some(5));
+        query_and_increment.increment();
+        assert_eq!(query_and_increment.known_sequence(), Some(6));
+        assert!(query_and_increment.resync(9));
+        assert_eq!(query_and_increment.known_sequence(), Some(9));
+
+        let set_and_increment = SequenceStrategy::new(SequenceStrategyKind::SetAndIncrement(3));
+        assert_eq!(set_and_increment.known_sequence(), Some(3));
+        set_and_increment.increment();
+        assert_eq!(set_and_increment.known_sequence(), Some(4));
+        assert!(set_and_increment.resync(10));
+        assert_eq!(set_and_increment.known_sequence(), Some(10));
+
+        let constant = SequenceStrategy::new(SequenceStrategyKind::Constant(3));
+        constant.increment();
+        assert_eq!(constant.known_sequence(), Some(3));
+        assert!(!constant.resync(10));
+        assert_eq!(constant.known_sequence(), Some(3));
+
+        // unordered txs always sign with sequence 0, and a mismatch can't be fixed by retrying
+        let unordered = SequenceStrategy::new(SequenceStrategyKind::Unordered(
+            std::time::Duration::from_secs(60),
+        ));
+        assert_eq!(unordered.known_sequence(), Some(0));
+        unordered.increment();
+        assert_eq!(unordered.known_sequence(), Some(0));
+        assert!(!unordered.resync(10));
+        assert_eq!(unordered.known_sequence(), Some(0));
+    }
+}
@@ -0,0 +1,2453 @@
+// SIGN_MODE_LEGACY_AMINO_JSON support
+// the json shapes follow the cosmos-sdk's legacy amino codec, i.e. the same thing cosmjs' AminoTypes produce
+// for these messages, the protobuf json encoding only differs in how nested `Any`s are represented,
+// that empty fields are kept and that enums are names, so the same converters are used for both (see `MsgJsonFormat`)
+
+use std::{collections::HashMap, sync::LazyLock};
+
+use base64::prelude::*;
+use layer_climb_signer::{AminoCoin, AminoMsg, StdFee, StdSignDoc};
+use serde_json::{Map, Value};
+
+use crate::prelude::*;
+
+/// Converts a protobuf message into the `value` of its amino json form
+/// gets the registry and format too, for messages which wrap other messages (e.g. authz MsgExec)
+pub type AminoMsgConverter =
+    fn(&AminoMsgRegistry, MsgJsonFormat, &layer_climb_proto::Any) -> Result<Value>;
+
+/// The reverse of `MsgJsonFormat::Proto`, i.e. reads a message from the sdk's protobuf json (without the `@type`)
+/// and gives its protobuf encoding
+pub type ProtoJsonParser = fn(&AminoMsgRegistry, &Value) -> Result<Vec<u8>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MsgJsonFormat {
+    /// `{"type": "cosmos-sdk/MsgSend", "value": {...}}`
+    Amino,
+    /// `{"@type": "/cosmos.bank.v1beta1.MsgSend", ...}`, as used in the cosmos-sdk's tx json files
+    Proto,
+}
+
+pub static DEFAULT_AMINO_MSG_REGISTRY: LazyLock<AminoMsgRegistry> =
+    LazyLock::new(AminoMsgRegistry::default);
+
+/// Maps protobuf type urls to their amino json representation
+/// the default registry covers the messages climb itself constructs,
+/// more can be added via `register()` (and `register_parser()` for reading them back from protobuf json)
+#[derive(Clone)]
+pub struct AminoMsgRegistry {
+    converters: HashMap<String, (String, AminoMsgConverter)>,
+    parsers: HashMap<String, ProtoJsonParser>,
+}
+
+impl AminoMsgRegistry {
+    /// An empty registry, see `default()` for one with the built-in messages
+    pub fn new() -> Self {
+        Self {
+            converters: HashMap::new(),
+            parsers: HashMap::new(),
+        }
+    }
+
+    pub fn register(
+        &mut self,
+        type_url: impl ToString,
+        amino_type: impl ToString,
+        converter: AminoMsgConverter,
+    ) -> &mut Self {
+        self.converters
+            .insert(type_url.to_string(), (amino_type.to_string(), converter));
+        self
+    }
+
+    pub fn register_parser(
+        &mut self,
+        type_url: impl ToString,
+        parser: ProtoJsonParser,
+    ) -> &mut Self {
+        self.parsers.insert(type_url.to_string(), parser);
+        self
+    }
+
+    pub fn to_amino(&self, msg: &layer_climb_proto::Any) -> Result<AminoMsg> {
+        let (amino_type, converter) = self.converter(msg)?;
+
+        Ok(AminoMsg {
+            kind: amino_type.clone(),
+            value: converter(self, MsgJsonFormat::Amino, msg)?,
+        })
+    }
+
+    pub fn to_proto_json(&self, msg: &layer_climb_proto::Any) -> Result<Value> {
+        let (_, converter) = self.converter(msg)?;
+
+        let mut value = converter(self, MsgJsonFormat::Proto, msg)?;
+        value
+            .as_object_mut()
+            .context("expected a json object")?
+            .insert("@type".to_string(), Value::String(msg.type_url.clone()));
+
+        Ok(value)
+    }
+
+    /// Reads a message back from `to_proto_json()`, i.e. `{"@type": "/cosmos.bank.v1beta1.MsgSend", ...}`
+    pub fn from_proto_json(&self, json: &Value) -> Result<layer_climb_proto::Any> {
+        let type_url = json
+            .get("@type")
+            .and_then(Value::as_str)
+            .context("missing @type in protobuf json")?;
+
+        let parser = self
+            .parsers
+            .get(type_url)
+            .with_context(|| format!("no json parser for {type_url}"))?;
+
+        Ok(layer_climb_proto::Any {
+            type_url: type_url.to_string(),
+            value: parser(self, json)
+                .with_context(|| format!("couldn't read {type_url} from protobuf json"))?,
+        })
+    }
+
+    pub fn to_json(&self, format: MsgJsonFormat, msg: &layer_climb_proto::Any) -> Result<Value> {
+        match format {
+            MsgJsonFormat::Amino => Ok(serde_json::to_value(self.to_amino(msg)?)?),
+            MsgJsonFormat::Proto => self.to_proto_json(msg),
+        }
+    }
+
+    fn converter(&self, msg: &layer_climb_proto::Any) -> Result<&(String, AminoMsgConverter)> {
+        self.converters
+            .get(&msg.type_url)
+            .with_context(|| format!("no json conversion for {}", msg.type_url))
+    }
+
+    /// Builds the amino json sign doc that's equivalent to this (direct mode) sign doc
+    pub fn std_sign_doc(&self, sign_doc: &layer_climb_proto::tx::SignDoc) -> Result<StdSignDoc> {
+        let body = layer_climb_proto::tx::TxBodyV053::decode(sign_doc.body_bytes.as_slice())?;
+        let auth_info =
+            layer_climb_proto::tx::AuthInfo::decode(sign_doc.auth_info_bytes.as_slice())?;
+
+        if !body.extension_options.is_empty() || !body.non_critical_extension_options.is_empty() {
+            bail!("extension options are not supported in amino json signing");
+        }
+
+        let sequence = auth_info
+            .signer_infos
+            .first()
+            .context("missing signer info")?
+            .sequence;
+
+        let fee = auth_info.fee.as_ref().context("missing fee")?;
+
+        let msgs = body
+            .messages
+            .iter()
+            .map(|msg| self.to_amino(msg))
+            .collect::<Result<Vec<_>>>()?;
+
+        StdSignDoc::new(
+            &sign_doc.chain_id,
+            sign_doc.account_number,
+            sequence,
+            StdFee::from(fee),
+            msgs,
+            &body.memo,
+        )
+        .with_timeout_height(body.timeout_height)
+        .with_unordered(body.unordered, body.timeout_timestamp)
+    }
+}
+
+impl Default for AminoMsgRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+
+        registry
+            // bank
+            .register(
+                "/cosmos.bank.v1beta1.MsgSend",
+                "cosmos-sdk/MsgSend",
+                bank_msg_send,
+            )
+            .register(
+                "/cosmos.bank.v1beta1.MsgMultiSend",
+                "cosmos-sdk/MsgMultiSend",
+                bank_msg_multi_send,
+            )
+            // wasm
+            .register(
+                "/cosmwasm.wasm.v1.MsgStoreCode",
+                "wasm/MsgStoreCode",
+                wasm_msg_store_code,
+            )
+            .register(
+                "/cosmwasm.wasm.v1.MsgInstantiateContract",
+                "wasm/MsgInstantiateContract",
+                wasm_msg_instantiate_contract,
+            )
+            .register(
+                "/cosmwasm.wasm.v1.MsgInstantiateContract2",
+                "wasm/MsgInstantiateContract2",
+                wasm_msg_instantiate_contract2,
+            )
+            .register(
+                "/cosmwasm.wasm.v1.MsgExecuteContract",
+                "wasm/MsgExecuteContract",
+                wasm_msg_execute_contract,
+            )
+            .register(
+                "/cosmwasm.wasm.v1.MsgMigrateContract",
+                "wasm/MsgMigrateContract",
+                wasm_msg_migrate_contract,
+            )
+            // authz
+            .register(
+                "/cosmos.authz.v1beta1.GenericAuthorization",
+                "cosmos-sdk/GenericAuthorization",
+                authz_generic_authorization,
+            )
+            .register(
+                "/cosmos.bank.v1beta1.SendAuthorization",
+                "cosmos-sdk/SendAuthorization",
+                bank_send_authorization,
+            )
+            .register(
+                "/cosmos.authz.v1beta1.MsgGrant",
+                "cosmos-sdk/MsgGrant",
+                authz_msg_grant,
+            )
+            .register(
+                "/cosmos.authz.v1beta1.MsgRevoke",
+                "cosmos-sdk/MsgRevoke",
+                authz_msg_revoke,
+            )
+            .register(
+                "/cosmos.authz.v1beta1.MsgExec",
+                "cosmos-sdk/MsgExec",
+                authz_msg_exec,
+            )
+            // staking
+            .register(
+                "/cosmos.staking.v1beta1.MsgDelegate",
+                "cosmos-sdk/MsgDelegate",
+                staking_msg_delegate,
+            )
+            .register(
+                "/cosmos.staking.v1beta1.MsgUndelegate",
+                "cosmos-sdk/MsgUndelegate",
+                staking_msg_undelegate,
+            )
+            .register(
+                "/cosmos.staking.v1beta1.MsgBeginRedelegate",
+                "cosmos-sdk/MsgBeginRedelegate",
+                staking_msg_begin_redelegate,
+            )
+            .register(
+                "/cosmos.staking.v1beta1.MsgCancelUnbondingDelegation",
+                "cosmos-sdk/MsgCancelUnbondingDelegation",
+                staking_msg_cancel_unbonding_delegation,
+            )
+            // distribution
+            .register(
+                "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward",
+                "cosmos-sdk/MsgWithdrawDelegationReward",
+                distribution_msg_withdraw_delegator_reward,
+            )
+            .register(
+                "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress",
+                "cosmos-sdk/MsgModifyWithdrawAddress",
+                distribution_msg_set_withdraw_address,
+            )
+            .register(
+                "/cosmos.distribution.v1beta1.MsgWithdrawValidatorCommission",
+                "cosmos-sdk/MsgWithdrawValCommission",
+                distribution_msg_withdraw_validator_commission,
+            )
+            // gov
+            .register(
+                "/cosmos.gov.v1beta1.MsgVote",
+                "cosmos-sdk/MsgVote",
+                gov_msg_vote_v1beta1,
+            )
+            .register(
+                "/cosmos.gov.v1.MsgVote",
+                "cosmos-sdk/v1/MsgVote",
+                gov_msg_vote_v1,
+            )
+            .register(
+                "/cosmos.gov.v1beta1.MsgDeposit",
+                "cosmos-sdk/MsgDeposit",
+                gov_msg_deposit_v1beta1,
+            )
+            .register(
+                "/cosmos.gov.v1.MsgDeposit",
+                "cosmos-sdk/v1/MsgDeposit",
+                gov_msg_deposit_v1,
+            )
+            .register(
+                "/cosmos.gov.v1beta1.MsgVoteWeighted",
+                "cosmos-sdk/MsgVoteWeighted",
+                gov_msg_vote_weighted_v1beta1,
+            )
+            .register(
+                "/cosmos.gov.v1.MsgVoteWeighted",
+                "cosmos-sdk/v1/MsgVoteWeighted",
+                gov_msg_vote_weighted_v1,
+            )
+            .register(
+                "/cosmos.gov.v1beta1.TextProposal",
+                "cosmos-sdk/TextProposal",
+                gov_text_proposal,
+            )
+            .register(
+                "/cosmos.gov.v1beta1.MsgSubmitProposal",
+                "cosmos-sdk/MsgSubmitProposal",
+                gov_msg_submit_proposal_v1beta1,
+            )
+            .register(
+                "/cosmos.gov.v1.MsgSubmitProposal",
+                "cosmos-sdk/v1/MsgSubmitProposal",
+                gov_msg_submit_proposal_v1,
+            )
+            // ibc (only transfer, core ibc messages are not amino-signable)
+            .register(
+                "/ibc.applications.transfer.v1.MsgTransfer",
+                "cosmos-sdk/MsgTransfer",
+                ibc_msg_transfer,
+            );
+
+        registry
+            // bank
+            .register_parser("/cosmos.bank.v1beta1.MsgSend", bank_msg_send_parser)
+            .register_parser(
+                "/cosmos.bank.v1beta1.MsgMultiSend",
+                bank_msg_multi_send_parser,
+            )
+            // wasm
+            .register_parser("/cosmwasm.wasm.v1.MsgStoreCode", wasm_msg_store_code_parser)
+            .register_parser(
+                "/cosmwasm.wasm.v1.MsgInstantiateContract",
+                wasm_msg_instantiate_contract_parser,
+            )
+            .register_parser(
+                "/cosmwasm.wasm.v1.MsgInstantiateContract2",
+                wasm_msg_instantiate_contract2_parser,
+            )
+            .register_parser(
+                "/cosmwasm.wasm.v1.MsgExecuteContract",
+                wasm_msg_execute_contract_parser,
+            )
+            .register_parser(
+                "/cosmwasm.wasm.v1.MsgMigrateContract",
+                wasm_msg_migrate_contract_parser,
+            )
+            // authz
+            .register_parser(
+                "/cosmos.authz.v1beta1.GenericAuthorization",
+                authz_generic_authorization_parser,
+            )
+            .register_parser(
+                "/cosmos.bank.v1beta1.SendAuthorization",
+                bank_send_authorization_parser,
+            )
+            .register_parser("/cosmos.authz.v1beta1.MsgGrant", authz_msg_grant_parser)
+            .register_parser("/cosmos.authz.v1beta1.MsgRevoke", authz_msg_revoke_parser)
+            .register_parser("/cosmos.authz.v1beta1.MsgExec", authz_msg_exec_parser)
+            // staking
+            .register_parser(
+                "/cosmos.staking.v1beta1.MsgDelegate",
+                staking_msg_delegate_parser,
+            )
+            .register_parser(
+                "/cosmos.staking.v1beta1.MsgUndelegate",
+                staking_msg_undelegate_parser,
+            )
+            .register_parser(
+                "/cosmos.staking.v1beta1.MsgBeginRedelegate",
+                staking_msg_begin_redelegate_parser,
+            )
+            .register_parser(
+                "/cosmos.staking.v1beta1.MsgCancelUnbondingDelegation",
+                staking_msg_cancel_unbonding_delegation_parser,
+            )
+            // distribution
+            .register_parser(
+                "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward",
+                distribution_msg_withdraw_delegator_reward_parser,
+            )
+            .register_parser(
+                "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress",
+                distribution_msg_set_withdraw_address_parser,
+            )
+            .register_parser(
+                "/cosmos.distribution.v1beta1.MsgWithdrawValidatorCommission",
+                distribution_msg_withdraw_validator_commission_parser,
+            )
+            // gov
+            .register_parser("/cosmos.gov.v1beta1.MsgVote", gov_msg_vote_v1beta1_parser)
+            .register_parser("/cosmos.gov.v1.MsgVote", gov_msg_vote_v1_parser)
+            .register_parser(
+                "/cosmos.gov.v1beta1.MsgDeposit",
+                gov_msg_deposit_v1beta1_parser,
+            )
+            .register_parser("/cosmos.gov.v1.MsgDeposit", gov_msg_deposit_v1_parser)
+            .register_parser(
+                "/cosmos.gov.v1beta1.MsgVoteWeighted",
+                gov_msg_vote_weighted_v1beta1_parser,
+            )
+            .register_parser(
+                "/cosmos.gov.v1.MsgVoteWeighted",
+                gov_msg_vote_weighted_v1_parser,
+            )
+            .register_parser("/cosmos.gov.v1beta1.TextProposal", gov_text_proposal_parser)
+            .register_parser(
+                "/cosmos.gov.v1beta1.MsgSubmitProposal",
+                gov_msg_submit_proposal_v1beta1_parser,
+            )
+            .register_parser(
+                "/cosmos.gov.v1.MsgSubmitProposal",
+                gov_msg_submit_proposal_v1_parser,
+            )
+            // ibc
+            .register_parser(
+                "/ibc.applications.transfer.v1.MsgTransfer",
+                ibc_msg_transfer_parser,
+            );
+
+        registry
+    }
+}
+
+fn decode<M: Message + Default>(msg: &layer_climb_proto::Any) -> Result<M> {
+    M::decode(msg.value.as_slice())
+        .with_context(|| format!("couldn't decode {} for amino json", msg.type_url))
+}
+
+// amino json leaves out empty values, unless explicitly told not to
+// the sdk's protobuf json always includes them (`null` for missing messages), and has enums as strings
+struct AminoJson {
+    fields: Map<String, Value>,
+    format: MsgJsonFormat,
+}
+
+impl AminoJson {
+    fn new(format: MsgJsonFormat) -> Self {
+        Self {
+            fields: Map::new(),
+            format,
+        }
+    }
+
+    fn value(mut self, key: &str, value: Value) -> Self {
+        self.fields.insert(key.to_string(), value);
+        self
+    }
+
+    fn value_or_empty(self, key: &str, value: Value, is_empty: bool) -> Self {
+        match (is_empty, self.format) {
+            (true, MsgJsonFormat::Amino) => self,
+            _ => self.value(key, value),
+        }
+    }
+
+    fn optional(self, key: &str, value: Option<Value>) -> Self {
+        let is_empty = value.is_none();
+        self.value_or_empty(key, value.unwrap_or(Value::Null), is_empty)
+    }
+
+    fn string(self, key: &str, value: &str) -> Self {
+        self.value_or_empty(key, Value::String(value.to_string()), value.is_empty())
+    }
+
+    // 64-bit numbers are strings in both formats
+    fn uint(self, key: &str, value: u64) -> Self {
+        self.value_or_empty(key, Value::String(value.to_string()), value == 0)
+    }
+
+    fn bool(self, key: &str, value: bool) -> Self {
+        self.value_or_empty(key, Value::Bool(value), !value)
+    }
+
+    fn bytes(self, key: &str, value: &[u8]) -> Self {
+        self.value_or_empty(
+            key,
+            Value::String(BASE64_STANDARD.encode(value)),
+            value.is_empty(),
+        )
+    }
+
+    // enums are numbers in amino json (always included), and their names in protobuf json
+    fn enumeration(self, key: &str, value: i32, name: Option<&str>) -> Self {
+        let json = match (self.format, name) {
+            (MsgJsonFormat::Proto, Some(name)) => Value::String(name.to_string()),
+            _ => Value::from(value),
+        };
+        self.value(key, json)
+    }
+
+    // coin lists are always included, even if empty
+    fn coins(self, key: &str, coins: &[layer_climb_proto::Coin]) -> Self {
+        let coins = coins.iter().map(coin_json).collect();
+        self.value(key, Value::Array(coins))
+    }
+
+    fn coin(self, key: &str, coin: Option<&layer_climb_proto::Coin>) -> Self {
+        self.optional(key, coin.map(coin_json))
+    }
+
+    // contract messages are embedded as json, not bytes
+    fn contract_msg(self, key: &str, msg: &[u8]) -> Result<Self> {
+        let msg: Value =
+            serde_json::from_slice(msg).context("contract msg is not valid json for amino")?;
+        Ok(self.value(key, msg))
+    }
+
+    fn build(self) -> Value {
+        Value::Object(self.fields)
+    }
+}
+
+fn coin_json(coin: &layer_climb_proto::Coin) -> Value {
+    serde_json::to_value(AminoCoin::from(coin)).unwrap_or_default()
+}
+
+fn bank_msg_send(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::bank::MsgSend = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("from_address", &msg.from_address)
+        .string("to_address", &msg.to_address)
+        .coins("amount", &msg.amount)
+        .build())
+}
+
+fn bank_msg_multi_send(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::bank::MsgMultiSend = decode(msg)?;
+
+    let inputs = msg
+        .inputs
+        .iter()
+        .map(|input| {
+            AminoJson::new(format)
+                .string("address", &input.address)
+                .coins("coins", &input.coins)
+                .build()
+        })
+        .collect();
+
+    let outputs = msg
+        .outputs
+        .iter()
+        .map(|output| {
+            AminoJson::new(format)
+                .string("address", &output.address)
+                .coins("coins", &output.coins)
+                .build()
+        })
+        .collect();
+
+    Ok(AminoJson::new(format)
+        .value("inputs", Value::Array(inputs))
+        .value("outputs", Value::Array(outputs))
+        .build())
+}
+
+fn wasm_access_config(
+    format: MsgJsonFormat,
+    config: &layer_climb_proto::wasm::AccessConfig,
+) -> Value {
+    let permission = layer_climb_proto::wasm::AccessType::try_from(config.permission)
+        .map(|permission| permission.as_str_name())
+        .unwrap_or("ACCESS_TYPE_UNSPECIFIED");
+
+    AminoJson::new(format)
+        .value("permission", Value::String(permission.to_string()))
+        .value(
+            "addresses",
+            Value::Array(
+                config
+                    .addresses
+                    .iter()
+                    .map(|addr| Value::String(addr.clone()))
+                    .collect(),
+            ),
+        )
+        .build()
+}
+
+fn wasm_msg_store_code(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::wasm::MsgStoreCode = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("sender", &msg.sender)
+        .bytes("wasm_byte_code", &msg.wasm_byte_code)
+        .optional(
+            "instantiate_permission",
+            msg.instantiate_permission
+                .as_ref()
+                .map(|permission| wasm_access_config(format, permission)),
+        )
+        .build())
+}
+
+fn wasm_msg_instantiate_contract(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::wasm::MsgInstantiateContract = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("sender", &msg.sender)
+        .string("admin", &msg.admin)
+        .uint("code_id", msg.code_id)
+        .string("label", &msg.label)
+        .contract_msg("msg", &msg.msg)?
+        .coins("funds", &msg.funds)
+        .build())
+}
+
+fn wasm_msg_instantiate_contract2(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::wasm::MsgInstantiateContract2 = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("sender", &msg.sender)
+        .string("admin", &msg.admin)
+        .uint("code_id", msg.code_id)
+        .string("label", &msg.label)
+        .contract_msg("msg", &msg.msg)?
+        .coins("funds", &msg.funds)
+        .bytes("salt", &msg.salt)
+        .bool("fix_msg", msg.fix_msg)
+        .build())
+}
+
+fn wasm_msg_execute_contract(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::wasm::MsgExecuteContract = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("sender", &msg.sender)
+        .string("contract", &msg.contract)
+        .contract_msg("msg", &msg.msg)?
+        .coins("funds", &msg.funds)
+        .build())
+}
+
+fn wasm_msg_migrate_contract(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::wasm::MsgMigrateContract = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("sender", &msg.sender)
+        .string("contract", &msg.contract)
+        .uint("code_id", msg.code_id)
+        .contract_msg("msg", &msg.msg)?
+        .build())
+}
+
+fn authz_generic_authorization(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let authorization: layer_climb_proto::authz::GenericAuthorization = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("msg", &authorization.msg)
+        .build())
+}
+
+fn bank_send_authorization(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let authorization: layer_climb_proto::bank::SendAuthorization = decode(msg)?;
+
+    let allow_list = authorization
+        .allow_list
+        .iter()
+        .map(|addr| Value::String(addr.clone()))
+        .collect();
+
+    Ok(AminoJson::new(format)
+        .coins("spend_limit", &authorization.spend_limit)
+        .value_or_empty(
+            "allow_list",
+            Value::Array(allow_list),
+            authorization.allow_list.is_empty(),
+        )
+        .build())
+}
+
+fn authz_msg_grant(
+    registry: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::authz::MsgGrant = decode(msg)?;
+
+    let mut grant = AminoJson::new(format);
+
+    if let Some(msg_grant) = msg.grant.as_ref() {
+        let authorization = msg_grant
+            .authorization
+            .as_ref()
+            .map(|authorization| registry.to_json(format, authorization))
+            .transpose()?;
+
+        let expiration = msg_grant
+            .expiration
+            .map(|expiration| {
+                anyhow::Ok(Value::String(
+                    tendermint::Time::from_unix_timestamp(
+                        expiration.seconds,
+                        expiration.nanos.try_into()?,
+                    )?
+                    .to_rfc3339(),
+                ))
+            })
+            .transpose()?;
+
+        grant = grant
+            .optional("authorization", authorization)
+            .optional("expiration", expiration);
+    }
+
+    Ok(AminoJson::new(format)
+        .string("granter", &msg.granter)
+        .string("grantee", &msg.grantee)
+        .value("grant", grant.build())
+        .build())
+}
+
+fn authz_msg_revoke(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::authz::MsgRevoke = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("granter", &msg.granter)
+        .string("grantee", &msg.grantee)
+        .string("msg_type_url", &msg.msg_type_url)
+        .build())
+}
+
+fn authz_msg_exec(
+    registry: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::authz::MsgExec = decode(msg)?;
+
+    let msgs = msg
+        .msgs
+        .iter()
+        .map(|msg| registry.to_json(format, msg))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(AminoJson::new(format)
+        .string("grantee", &msg.grantee)
+        .value("msgs", Value::Array(msgs))
+        .build())
+}
+
+fn staking_msg_delegate(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::staking::MsgDelegate = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("delegator_address", &msg.delegator_address)
+        .string("validator_address", &msg.validator_address)
+        .coin("amount", msg.amount.as_ref())
+        .build())
+}
+
+fn staking_msg_undelegate(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::staking::MsgUndelegate = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("delegator_address", &msg.delegator_address)
+        .string("validator_address", &msg.validator_address)
+        .coin("amount", msg.amount.as_ref())
+        .build())
+}
+
+fn staking_msg_begin_redelegate(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::staking::MsgBeginRedelegate = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("delegator_address", &msg.delegator_address)
+        .string("validator_src_address", &msg.validator_src_address)
+        .string("validator_dst_address", &msg.validator_dst_address)
+        .coin("amount", msg.amount.as_ref())
+        .build())
+}
+
+fn staking_msg_cancel_unbonding_delegation(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::staking::MsgCancelUnbondingDelegation = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("delegator_address", &msg.delegator_address)
+        .string("validator_address", &msg.validator_address)
+        .coin("amount", msg.amount.as_ref())
+        .uint("creation_height", msg.creation_height.try_into()?)
+        .build())
+}
+
+fn distribution_msg_withdraw_delegator_reward(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::distribution::MsgWithdrawDelegatorReward = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("delegator_address", &msg.delegator_address)
+        .string("validator_address", &msg.validator_address)
+        .build())
+}
+
+fn distribution_msg_set_withdraw_address(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::distribution::MsgSetWithdrawAddress = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("delegator_address", &msg.delegator_address)
+        .string("withdraw_address", &msg.withdraw_address)
+        .build())
+}
+
+fn distribution_msg_withdraw_validator_commission(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::distribution::MsgWithdrawValidatorCommission = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("validator_address", &msg.validator_address)
+        .build())
+}
+
+fn gov_msg_vote_v1beta1(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::gov::v1beta1::MsgVote = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .uint("proposal_id", msg.proposal_id)
+        .string("voter", &msg.voter)
+        .enumeration("option", msg.option, vote_option_v1beta1(msg.option))
+        .build())
+}
+
+fn gov_msg_vote_v1(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::gov::v1::MsgVote = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .uint("proposal_id", msg.proposal_id)
+        .string("voter", &msg.voter)
+        .enumeration("option", msg.option, vote_option_v1(msg.option))
+        .string("metadata", &msg.metadata)
+        .build())
+}
+
+fn gov_msg_deposit_v1beta1(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::gov::v1beta1::MsgDeposit = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .uint("proposal_id", msg.proposal_id)
+        .string("depositor", &msg.depositor)
+        .coins("amount", &msg.amount)
+        .build())
+}
+
+fn gov_msg_deposit_v1(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::gov::v1::MsgDeposit = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .uint("proposal_id", msg.proposal_id)
+        .string("depositor", &msg.depositor)
+        .coins("amount", &msg.amount)
+        .build())
+}
+
+// v1beta1 weights are sdk.Dec atomics (18 decimal places), amino json prints them in full, e.g. "0.500000000000000000"
+fn legacy_dec_json(atomics: &str) -> Result<String> {
+    const ONE: u128 = 1_000_000_000_000_000_000;
+
+    let atomics: u128 = atomics
+        .parse()
+        .with_context(|| format!("invalid decimal {atomics}"))?;
+
+    Ok(format!("{}.{:018}", atomics / ONE, atomics % ONE))
+}
+
+fn vote_option_v1beta1(option: i32) -> Option<&'static str> {
+    layer_climb_proto::gov::v1beta1::VoteOption::try_from(option)
+        .ok()
+        .map(|option| option.as_str_name())
+}
+
+fn vote_option_v1(option: i32) -> Option<&'static str> {
+    layer_climb_proto::gov::v1::VoteOption::try_from(option)
+        .ok()
+        .map(|option| option.as_str_name())
+}
+
+fn gov_msg_vote_weighted_v1beta1(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::gov::v1beta1::MsgVoteWeighted = decode(msg)?;
+
+    let options = msg
+        .options
+        .iter()
+        .map(|option| {
+            Ok(AminoJson::new(format)
+                .enumeration("option", option.option, vote_option_v1beta1(option.option))
+                .value("weight", Value::String(legacy_dec_json(&option.weight)?))
+                .build())
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(AminoJson::new(format)
+        .uint("proposal_id", msg.proposal_id)
+        .string("voter", &msg.voter)
+        .value("options", Value::Array(options))
+        .build())
+}
+
+fn gov_msg_vote_weighted_v1(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::gov::v1::MsgVoteWeighted = decode(msg)?;
+
+    let options = msg
+        .options
+        .iter()
+        .map(|option| {
+            AminoJson::new(format)
+                .enumeration("option", option.option, vote_option_v1(option.option))
+                .string("weight", &option.weight)
+                .build()
+        })
+        .collect();
+
+    Ok(AminoJson::new(format)
+        .uint("proposal_id", msg.proposal_id)
+        .string("voter", &msg.voter)
+        .value("options", Value::Array(options))
+        .string("metadata", &msg.metadata)
+        .build())
+}
+
+fn gov_text_proposal(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let proposal: layer_climb_proto::gov::v1beta1::TextProposal = decode(msg)?;
+
+    Ok(AminoJson::new(format)
+        .string("title", &proposal.title)
+        .string("description", &proposal.description)
+        .build())
+}
+
+fn gov_msg_submit_proposal_v1beta1(
+    registry: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::gov::v1beta1::MsgSubmitProposal = decode(msg)?;
+
+    let content = msg
+        .content
+        .as_ref()
+        .map(|content| registry.to_json(format, content))
+        .transpose()?;
+
+    Ok(AminoJson::new(format)
+        .optional("content", content)
+        .coins("initial_deposit", &msg.initial_deposit)
+        .string("proposer", &msg.proposer)
+        .build())
+}
+
+fn gov_msg_submit_proposal_v1(
+    registry: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::gov::v1::MsgSubmitProposal = decode(msg)?;
+
+    let messages = msg
+        .messages
+        .iter()
+        .map(|msg| registry.to_json(format, msg))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(AminoJson::new(format)
+        .value_or_empty("messages", Value::Array(messages), msg.messages.is_empty())
+        .coins("initial_deposit", &msg.initial_deposit)
+        .string("proposer", &msg.proposer)
+        .string("metadata", &msg.metadata)
+        .string("title", &msg.title)
+        .string("summary", &msg.summary)
+        .bool("expedited", msg.expedited)
+        .build())
+}
+
+fn ibc_msg_transfer(
+    _: &AminoMsgRegistry,
+    format: MsgJsonFormat,
+    msg: &layer_climb_proto::Any,
+) -> Result<Value> {
+    let msg: layer_climb_proto::ibc::transfer::MsgTransfer = decode(msg)?;
+
+    let token = msg.token.as_ref().map(|token| layer_climb_proto::Coin {
+        denom: token.denom.clone(),
+        amount: token.amount.clone(),
+    });
+
+    // timeout_height is always present, even if its fields are not
+    let timeout_height = msg.timeout_height.unwrap_or_default();
+    let timeout_height = AminoJson::new(format)
+        .uint("revision_number", timeout_height.revision_number)
+        .uint("revision_height", timeout_height.revision_height);
+
+    Ok(AminoJson::new(format)
+        .string("source_port", &msg.source_port)
+        .string("source_channel", &msg.source_channel)
+        .coin("token", token.as_ref())
+        .string("sender", &msg.sender)
+        .string("receiver", &msg.receiver)
+        .value("timeout_height", timeout_height.build())
+        .uint("timeout_timestamp", msg.timeout_timestamp)
+        .string("memo", &msg.memo)
+        .build())
+}
+
+// the rest reads messages back from the sdk's protobuf json, i.e. the reverse of `MsgJsonFormat::Proto`
+
+// missing and `null` fields are their protobuf defaults
+#[derive(Clone, Copy)]
+pub(crate) struct ProtoJson<'a>(&'a Map<String, Value>);
+
+impl<'a> ProtoJson<'a> {
+    pub(crate) fn new(json: &'a Value) -> Result<Self> {
+        json.as_object().map(Self).context("expected a json object")
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<&'a Value> {
+        self.0.get(key).filter(|value| !value.is_null())
+    }
+
+    pub(crate) fn string(&self, key: &str) -> Result<String> {
+        match self.get(key) {
+            None => Ok(String::new()),
+            Some(value) => Ok(value
+                .as_str()
+                .with_context(|| format!("expected {key} to be a string"))?
+                .to_string()),
+        }
+    }
+
+    // 64-bit numbers are strings, but numbers are accepted too
+    pub(crate) fn uint(&self, key: &str) -> Result<u64> {
+        match self.get(key) {
+            None => Ok(0),
+            Some(Value::String(value)) => value
+                .parse()
+                .with_context(|| format!("expected {key} to be a number")),
+            Some(value) => value
+                .as_u64()
+                .with_context(|| format!("expected {key} to be a number")),
+        }
+    }
+
+    pub(crate) fn int(&self, key: &str) -> Result<i64> {
+        match self.get(key) {
+            None => Ok(0),
+            Some(Value::String(value)) => value
+                .parse()
+                .with_context(|| format!("expected {key} to be a number")),
+            Some(value) => value
+                .as_i64()
+                .with_context(|| format!("expected {key} to be a number")),
+        }
+    }
+
+    pub(crate) fn bool(&self, key: &str) -> Result<bool> {
+        match self.get(key) {
+            None => Ok(false),
+            Some(value) => value
+                .as_bool()
+                .with_context(|| format!("expected {key} to be a bool")),
+        }
+    }
+
+    pub(crate) fn bytes(&self, key: &str) -> Result<Vec<u8>> {
+        Ok(BASE64_STANDARD.decode(self.string(key)?)?)
+    }
+
+    pub(crate) fn strings(&self, key: &str) -> Result<Vec<String>> {
+        self.array(key)?
+            .iter()
+            .map(|value| {
+                value
+                    .as_str()
+                    .map(str::to_string)
+                    .with_context(|| format!("expected {key} to be a list of strings"))
+            })
+            .collect()
+    }
+
+    pub(crate) fn array(&self, key: &str) -> Result<&'a [Value]> {
+        match self.get(key) {
+            None => Ok(&[]),
+            Some(value) => Ok(value
+                .as_array()
+                .with_context(|| format!("expected {key} to be a list"))?),
+        }
+    }
+
+    pub(crate) fn object(&self, key: &str) -> Result<Option<ProtoJson<'a>>> {
+        self.get(key).map(ProtoJson::new).transpose()
+    }
+
+    pub(crate) fn objects(&self, key: &str) -> Result<Vec<ProtoJson<'a>>> {
+        self.array(key)?.iter().map(ProtoJson::new).collect()
+    }
+
+    pub(crate) fn coins(&self, key: &str) -> Result<Vec<layer_climb_proto::Coin>> {
+        self.objects(key)?.iter().map(coin_from_json).collect()
+    }
+
+    pub(crate) fn coin(&self, key: &str) -> Result<Option<layer_climb_proto::Coin>> {
+        self.object(key)?.as_ref().map(coin_from_json).transpose()
+    }
+
+    pub(crate) fn any(
+        &self,
+        registry: &AminoMsgRegistry,
+        key: &str,
+    ) -> Result<Option<layer_climb_proto::Any>> {
+        self.get(key)
+            .map(|value| registry.from_proto_json(value))
+            .transpose()
+    }
+
+    pub(crate) fn anys(
+        &self,
+        registry: &AminoMsgRegistry,
+        key: &str,
+    ) -> Result<Vec<layer_climb_proto::Any>> {
+        self.array(key)?
+            .iter()
+            .map(|value| registry.from_proto_json(value))
+            .collect()
+    }
+
+    // contract messages are embedded as json, not bytes
+    pub(crate) fn contract_msg(&self, key: &str) -> Result<Vec<u8>> {
+        match self.get(key) {
+            None => Ok(Vec::new()),
+            Some(value) => Ok(serde_json::to_vec(value)?),
+        }
+    }
+
+    // enums are their names, but numbers are accepted too
+    pub(crate) fn enumeration(
+        &self,
+        key: &str,
+        from_str_name: fn(&str) -> Option<i32>,
+    ) -> Result<i32> {
+        match self.get(key) {
+            None => Ok(0),
+            Some(Value::String(name)) => {
+                from_str_name(name).with_context(|| format!("unknown {key} {name}"))
+            }
+            Some(value) => Ok(value
+                .as_i64()
+                .with_context(|| format!("expected {key} to be an enum"))?
+                .try_into()?),
+        }
+    }
+
+    pub(crate) fn timestamp(&self, key: &str) -> Result<Option<layer_climb_proto::Timestamp>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(_) => Ok(Some(
+                tendermint::Time::parse_from_rfc3339(&self.string(key)?)?.into(),
+            )),
+        }
+    }
+}
+
+fn coin_from_json(json: &ProtoJson) -> Result<layer_climb_proto::Coin> {
+    Ok(layer_climb_proto::Coin {
+        denom: json.string("denom")?,
+        amount: json.string("amount")?,
+    })
+}
+
+fn bank_msg_send_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::bank::MsgSend {
+        from_address: json.string("from_address")?,
+        to_address: json.string("to_address")?,
+        amount: json.coins("amount")?,
+    }
+    .encode_to_vec())
+}
+
+fn bank_msg_multi_send_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::bank::MsgMultiSend {
+        inputs: json
+            .objects("inputs")?
+            .iter()
+            .map(|input| {
+                Ok(layer_climb_proto::bank::Input {
+                    address: input.string("address")?,
+                    coins: input.coins("coins")?,
+                })
+            })
+            .collect::<Result<_>>()?,
+        outputs: json
+            .objects("outputs")?
+            .iter()
+            .map(|output| {
+                Ok(layer_climb_proto::bank::Output {
+                    address: output.string("address")?,
+                    coins: output.coins("coins")?,
+                })
+            })
+            .collect::<Result<_>>()?,
+    }
+    .encode_to_vec())
+}
+
+fn wasm_msg_store_code_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    let instantiate_permission = json
+        .object("instantiate_permission")?
+        .map(|permission| {
+            anyhow::Ok(layer_climb_proto::wasm::AccessConfig {
+                permission: permission.enumeration("permission", |name| {
+                    layer_climb_proto::wasm::AccessType::from_str_name(name).map(Into::into)
+                })?,
+                addresses: permission.strings("addresses")?,
+            })
+        })
+        .transpose()?;
+
+    Ok(layer_climb_proto::wasm::MsgStoreCode {
+        sender: json.string("sender")?,
+        wasm_byte_code: json.bytes("wasm_byte_code")?,
+        instantiate_permission,
+    }
+    .encode_to_vec())
+}
+
+fn wasm_msg_instantiate_contract_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::wasm::MsgInstantiateContract {
+        sender: json.string("sender")?,
+        admin: json.string("admin")?,
+        code_id: json.uint("code_id")?,
+        label: json.string("label")?,
+        msg: json.contract_msg("msg")?,
+        funds: json.coins("funds")?,
+    }
+    .encode_to_vec())
+}
+
+fn wasm_msg_instantiate_contract2_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::wasm::MsgInstantiateContract2 {
+        sender: json.string("sender")?,
+        admin: json.string("admin")?,
+        code_id: json.uint("code_id")?,
+        label: json.string("label")?,
+        msg: json.contract_msg("msg")?,
+        funds: json.coins("funds")?,
+        salt: json.bytes("salt")?,
+        fix_msg: json.bool("fix_msg")?,
+    }
+    .encode_to_vec())
+}
+
+fn wasm_msg_execute_contract_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::wasm::MsgExecuteContract {
+        sender: json.string("sender")?,
+        contract: json.string("contract")?,
+        msg: json.contract_msg("msg")?,
+        funds: json.coins("funds")?,
+    }
+    .encode_to_vec())
+}
+
+fn wasm_msg_migrate_contract_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::wasm::MsgMigrateContract {
+        sender: json.string("sender")?,
+        contract: json.string("contract")?,
+        code_id: json.uint("code_id")?,
+        msg: json.contract_msg("msg")?,
+    }
+    .encode_to_vec())
+}
+
+fn authz_generic_authorization_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::authz::GenericAuthorization {
+        msg: json.string("msg")?,
+    }
+    .encode_to_vec())
+}
+
+fn bank_send_authorization_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::bank::SendAuthorization {
+        spend_limit: json.coins("spend_limit")?,
+        allow_list: json.strings("allow_list")?,
+    }
+    .encode_to_vec())
+}
+
+fn authz_msg_grant_parser(registry: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    let grant = json
+        .object("grant")?
+        .map(|grant| {
+            anyhow::Ok(layer_climb_proto::authz::Grant {
+                authorization: grant.any(registry, "authorization")?,
+                expiration: grant.timestamp("expiration")?,
+            })
+        })
+        .transpose()?;
+
+    Ok(layer_climb_proto::authz::MsgGrant {
+        granter: json.string("granter")?,
+        grantee: json.string("grantee")?,
+        grant,
+    }
+    .encode_to_vec())
+}
+
+fn authz_msg_revoke_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::authz::MsgRevoke {
+        granter: json.string("granter")?,
+        grantee: json.string("grantee")?,
+        msg_type_url: json.string("msg_type_url")?,
+    }
+    .encode_to_vec())
+}
+
+fn authz_msg_exec_parser(registry: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::authz::MsgExec {
+        grantee: json.string("grantee")?,
+        msgs: json.anys(registry, "msgs")?,
+    }
+    .encode_to_vec())
+}
+
+fn staking_msg_delegate_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::staking::MsgDelegate {
+        delegator_address: json.string("delegator_address")?,
+        validator_address: json.string("validator_address")?,
+        amount: json.coin("amount")?,
+    }
+    .encode_to_vec())
+}
+
+fn staking_msg_undelegate_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::staking::MsgUndelegate {
+        delegator_address: json.string("delegator_address")?,
+        validator_address: json.string("validator_address")?,
+        amount: json.coin("amount")?,
+    }
+    .encode_to_vec())
+}
+
+fn staking_msg_begin_redelegate_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::staking::MsgBeginRedelegate {
+        delegator_address: json.string("delegator_address")?,
+        validator_src_address: json.string("validator_src_address")?,
+        validator_dst_address: json.string("validator_dst_address")?,
+        amount: json.coin("amount")?,
+    }
+    .encode_to_vec())
+}
+
+fn staking_msg_cancel_unbonding_delegation_parser(
+    _: &AminoMsgRegistry,
+    json: &Value,
+) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::staking::MsgCancelUnbondingDelegation {
+        delegator_address: json.string("delegator_address")?,
+        validator_address: json.string("validator_address")?,
+        amount: json.coin("amount")?,
+        creation_height: json.int("creation_height")?,
+    }
+    .encode_to_vec())
+}
+
+fn distribution_msg_withdraw_delegator_reward_parser(
+    _: &AminoMsgRegistry,
+    json: &Value,
+) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(
+        layer_climb_proto::distribution::MsgWithdrawDelegatorReward {
+            delegator_address: json.string("delegator_address")?,
+            validator_address: json.string("validator_address")?,
+        }
+        .encode_to_vec(),
+    )
+}
+
+fn distribution_msg_set_withdraw_address_parser(
+    _: &AminoMsgRegistry,
+    json: &Value,
+) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::distribution::MsgSetWithdrawAddress {
+        delegator_address: json.string("delegator_address")?,
+        withdraw_address: json.string("withdraw_address")?,
+    }
+    .encode_to_vec())
+}
+
+fn distribution_msg_withdraw_validator_commission_parser(
+    _: &AminoMsgRegistry,
+    json: &Value,
+) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(
+        layer_climb_proto::distribution::MsgWithdrawValidatorCommission {
+            validator_address: json.string("validator_address")?,
+        }
+        .encode_to_vec(),
+    )
+}
+
+fn vote_option_v1beta1_from_name(name: &str) -> Option<i32> {
+    layer_climb_proto::gov::v1beta1::VoteOption::from_str_name(name).map(Into::into)
+}
+
+fn vote_option_v1_from_name(name: &str) -> Option<i32> {
+    layer_climb_proto::gov::v1::VoteOption::from_str_name(name).map(Into::into)
+}
+
+fn gov_msg_vote_v1beta1_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::gov::v1beta1::MsgVote {
+        proposal_id: json.uint("proposal_id")?,
+        voter: json.string("voter")?,
+        option: json.enumeration("option", vote_option_v1beta1_from_name)?,
+    }
+    .encode_to_vec())
+}
+
+fn gov_msg_vote_v1_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::gov::v1::MsgVote {
+        proposal_id: json.uint("proposal_id")?,
+        voter: json.string("voter")?,
+        option: json.enumeration("option", vote_option_v1_from_name)?,
+        metadata: json.string("metadata")?,
+    }
+    .encode_to_vec())
+}
+
+fn gov_msg_deposit_v1beta1_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::gov::v1beta1::MsgDeposit {
+        proposal_id: json.uint("proposal_id")?,
+        depositor: json.string("depositor")?,
+        amount: json.coins("amount")?,
+    }
+    .encode_to_vec())
+}
+
+fn gov_msg_deposit_v1_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::gov::v1::MsgDeposit {
+        proposal_id: json.uint("proposal_id")?,
+        depositor: json.string("depositor")?,
+        amount: json.coins("amount")?,
+    }
+    .encode_to_vec())
+}
+
+// the reverse of `legacy_dec_json`, e.g. "0.500000000000000000" is "500000000000000000" in protobuf
+fn legacy_dec_atomics(json: &str) -> Result<String> {
+    const ONE: u128 = 1_000_000_000_000_000_000;
+
+    let (whole, fraction) = json.split_once('.').unwrap_or((json, ""));
+    if fraction.len() > 18 {
+        bail!("invalid decimal {json}");
+    }
+
+    let whole: u128 = whole
+        .parse()
+        .with_context(|| format!("invalid decimal {json}"))?;
+    let fraction: u128 = match fraction.is_empty() {
+        true => 0,
+        false => format!("{fraction:0<18}")
+            .parse()
+            .with_context(|| format!("invalid decimal {json}"))?,
+    };
+
+    Ok((whole * ONE + fraction).to_string())
+}
+
+fn gov_msg_vote_weighted_v1beta1_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::gov::v1beta1::MsgVoteWeighted {
+        proposal_id: json.uint("proposal_id")?,
+        voter: json.string("voter")?,
+        options: json
+            .objects("options")?
+            .iter()
+            .map(|option| {
+                Ok(layer_climb_proto::gov::v1beta1::WeightedVoteOption {
+                    option: option.enumeration("option", vote_option_v1beta1_from_name)?,
+                    weight: legacy_dec_atomics(&option.string("weight")?)?,
+                })
+            })
+            .collect::<Result<_>>()?,
+    }
+    .encode_to_vec())
+}
+
+fn gov_msg_vote_weighted_v1_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::gov::v1::MsgVoteWeighted {
+        proposal_id: json.uint("proposal_id")?,
+        voter: json.string("voter")?,
+        options: json
+            .objects("options")?
+            .iter()
+            .map(|option| {
+                Ok(layer_climb_proto::gov::v1::WeightedVoteOption {
+                    option: option.enumeration("option", vote_option_v1_from_name)?,
+                    weight: option.string("weight")?,
+                })
+            })
+            .collect::<Result<_>>()?,
+        metadata: json.string("metadata")?,
+    }
+    .encode_to_vec())
+}
+
+fn gov_text_proposal_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::gov::v1beta1::TextProposal {
+        title: json.string("title")?,
+        description: json.string("description")?,
+    }
+    .encode_to_vec())
+}
+
+fn gov_msg_submit_proposal_v1beta1_parser(
+    registry: &AminoMsgRegistry,
+    json: &Value,
+) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::gov::v1beta1::MsgSubmitProposal {
+        content: json.any(registry, "content")?,
+        initial_deposit: json.coins("initial_deposit")?,
+        proposer: json.string("proposer")?,
+    }
+    .encode_to_vec())
+}
+
+fn gov_msg_submit_proposal_v1_parser(registry: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    Ok(layer_climb_proto::gov::v1::MsgSubmitProposal {
+        messages: json.anys(registry, "messages")?,
+        initial_deposit: json.coins("initial_deposit")?,
+        proposer: json.string("proposer")?,
+        metadata: json.string("metadata")?,
+        title: json.string("title")?,
+        summary: json.string("summary")?,
+        expedited: json.bool("expedited")?,
+    }
+    .encode_to_vec())
+}
+
+fn ibc_msg_transfer_parser(_: &AminoMsgRegistry, json: &Value) -> Result<Vec<u8>> {
+    let json = ProtoJson::new(json)?;
+
+    let timeout_height = json
+        .object("timeout_height")?
+        .map(|height| {
+            anyhow::Ok(layer_climb_proto::ibc::client::Height {
+                revision_number: height.uint("revision_number")?,
+                revision_height: height.uint("revision_height")?,
+            })
+        })
+        .transpose()?;
+
+    Ok(layer_climb_proto::ibc::transfer::MsgTransfer {
+        source_port: json.string("source_port")?,
+        source_channel: json.string("source_channel")?,
+        // ibc has its own copy of Coin
+        token: json
+            .coin("token")?
+            .map(|token| Message::decode(token.encode_to_vec().as_slice()))
+            .transpose()?,
+        sender: json.string("sender")?,
+        receiver: json.string("receiver")?,
+        timeout_height,
+        timeout_timestamp: json.uint("timeout_timestamp")?,
+        memo: json.string("memo")?,
+    }
+    .encode_to_vec())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DELEGATOR: &str = "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu";
+    const VALIDATOR: &str = "cosmosvaloper1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5ntyjsd";
+    const VALIDATOR_DST: &str = "cosmosvaloper1pgqsmf08ruam4k8fqa97h8gy3gylkznwh6jz3g";
+    const GRANTEE: &str = "cosmos1pgqsmf08ruam4k8fqa97h8gy3gylkznwsnf7ex";
+    const CONTRACT: &str = "cosmos14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9s4hmalr";
+
+    fn coin(amount: u128) -> layer_climb_proto::Coin {
+        layer_climb_proto::Coin {
+            denom: "ustake".to_string(),
+            amount: amount.to_string(),
+        }
+    }
+
+    fn fee() -> layer_climb_proto::tx::Fee {
+        layer_climb_proto::tx::Fee {
+            amount: vec![coin(5000)],
+            gas_limit: 200000,
+            ..Default::default()
+        }
+    }
+
+    fn sign_doc(
+        body: &layer_climb_proto::tx::TxBodyV053,
+        fee: layer_climb_proto::tx::Fee,
+    ) -> layer_climb_proto::tx::SignDoc {
+        let auth_info = layer_climb_proto::tx::AuthInfo {
+            signer_infos: vec![layer_climb_proto::tx::SignerInfo {
+                sequence: 3,
+                ..Default::default()
+            }],
+            fee: Some(fee),
+            ..Default::default()
+        };
+
+        layer_climb_proto::tx::SignDoc {
+            body_bytes: body.encode_to_vec(),
+            auth_info_bytes: auth_info.encode_to_vec(),
+            chain_id: "test-1".to_string(),
+            account_number: 7,
+        }
+    }
+
+    fn sign_bytes(sign_doc: &layer_climb_proto::tx::SignDoc) -> String {
+        let sign_bytes = DEFAULT_AMINO_MSG_REGISTRY
+            .std_sign_doc(sign_doc)
+            .unwrap()
+            .to_sign_bytes()
+            .unwrap();
+
+        String::from_utf8(sign_bytes).unwrap()
+    }
+
+    // the sign bytes of a tx with just this message, the expected msg json is what cosmjs produces for it
+    fn assert_sign_bytes<T: layer_climb_proto::Name>(msg: &T, expected_msg: &str) {
+        let body = layer_climb_proto::tx::TxBodyV053 {
+            messages: vec![proto_into_any(msg).unwrap()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            sign_bytes(&sign_doc(&body, fee())),
+            format!(
+                r#"{{"account_number":"7","chain_id":"test-1","fee":{{"amount":[{{"amount":"5000","denom":"ustake"}}],"gas":"200000"}},"memo":"","msgs":[{expected_msg}],"sequence":"3"}}"#
+            )
+        );
+    }
+
+    // the ibc protos have their own copies of the common types
+    fn convert<T: Message + Default>(msg: &impl Message) -> T {
+        T::decode(msg.encode_to_vec().as_slice()).unwrap()
+    }
+
+    fn bank_send() -> layer_climb_proto::bank::MsgSend {
+        layer_climb_proto::bank::MsgSend {
+            from_address: DELEGATOR.to_string(),
+            to_address: GRANTEE.to_string(),
+            amount: vec![coin(1000)],
+        }
+    }
+
+    #[test]
+    fn sign_doc_envelope() {
+        let body = layer_climb_proto::tx::TxBodyV053 {
+            messages: vec![proto_into_any(&bank_send()).unwrap()],
+            memo: "<script> & co".to_string(),
+            timeout_height: 100,
+            ..Default::default()
+        };
+        let fee = layer_climb_proto::tx::Fee {
+            granter: GRANTEE.to_string(),
+            ..fee()
+        };
+
+        // go's encoding/json escapes html characters
+        assert_eq!(
+            sign_bytes(&sign_doc(&body, fee)),
+            format!(
+                r#"{{"account_number":"7","chain_id":"test-1","fee":{{"amount":[{{"amount":"5000","denom":"ustake"}}],"gas":"200000","granter":"{GRANTEE}"}},"memo":"\u003cscript\u003e \u0026 co","msgs":[{{"type":"cosmos-sdk/MsgSend","value":{{"amount":[{{"amount":"1000","denom":"ustake"}}],"from_address":"{DELEGATOR}","to_address":"{GRANTEE}"}}}}],"sequence":"3","timeout_height":"100"}}"#
+            )
+        );
+    }
+
+    #[test]
+    fn sign_doc_unordered() {
+        let body = layer_climb_proto::tx::TxBodyV053 {
+            messages: vec![proto_into_any(&bank_send()).unwrap()],
+            unordered: true,
+            timeout_timestamp: Some(layer_climb_proto::Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 150_000_000,
+            }),
+            ..Default::default()
+        };
+
+        // same as go's time.RFC3339Nano, i.e. trailing zeros trimmed
+        assert_eq!(
+            sign_bytes(&sign_doc(&body, fee())),
+            format!(
+                r#"{{"account_number":"7","chain_id":"test-1","fee":{{"amount":[{{"amount":"5000","denom":"ustake"}}],"gas":"200000"}},"memo":"","msgs":[{{"type":"cosmos-sdk/MsgSend","value":{{"amount":[{{"amount":"1000","denom":"ustake"}}],"from_address":"{DELEGATOR}","to_address":"{GRANTEE}"}}}}],"sequence":"3","timeout_timestamp":"2023-11-14T22:13:20.15Z","unordered":true}}"#
+            )
+        );
+
+        let whole_seconds = layer_climb_proto::tx::TxBodyV053 {
+            timeout_timestamp: Some(layer_climb_proto::Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 0,
+            }),
+            ..body
+        };
+        assert!(sign_bytes(&sign_doc(&whole_seconds, fee()))
+            .ends_with(r#""timeout_timestamp":"2023-11-14T22:13:20Z","unordered":true}"#));
+    }
+
+    #[test]
+    fn sign_doc_unsupported() {
+        let unregistered = layer_climb_proto::tx::TxBodyV053 {
+            messages: vec![layer_climb_proto::Any {
+                type_url: "/chain.custom.v1.MsgCustom".to_string(),
+                value: vec![],
+            }],
+            ..Default::default()
+        };
+        assert!(DEFAULT_AMINO_MSG_REGISTRY
+            .std_sign_doc(&sign_doc(&unregistered, fee()))
+            .is_err());
+    }
+
+    #[test]
+    fn bank_msg_send() {
+        assert_sign_bytes(
+            &bank_send(),
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgSend","value":{{"amount":[{{"amount":"1000","denom":"ustake"}}],"from_address":"{DELEGATOR}","to_address":"{GRANTEE}"}}}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn bank_msg_multi_send() {
+        assert_sign_bytes(
+            &layer_climb_proto::bank::MsgMultiSend {
+                inputs: vec![layer_climb_proto::bank::Input {
+                    address: DELEGATOR.to_string(),
+                    coins: vec![coin(3000)],
+                }],
+                outputs: vec![
+                    layer_climb_proto::bank::Output {
+                        address: GRANTEE.to_string(),
+                        coins: vec![coin(1000)],
+                    },
+                    layer_climb_proto::bank::Output {
+                        address: CONTRACT.to_string(),
+                        coins: vec![coin(2000)],
+                    },
+                ],
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgMultiSend","value":{{"inputs":[{{"address":"{DELEGATOR}","coins":[{{"amount":"3000","denom":"ustake"}}]}}],"outputs":[{{"address":"{GRANTEE}","coins":[{{"amount":"1000","denom":"ustake"}}]}},{{"address":"{CONTRACT}","coins":[{{"amount":"2000","denom":"ustake"}}]}}]}}}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn wasm_msgs() {
+        assert_sign_bytes(
+            &layer_climb_proto::wasm::MsgStoreCode {
+                sender: DELEGATOR.to_string(),
+                wasm_byte_code: vec![0, 97, 115, 109],
+                instantiate_permission: None,
+            },
+            &format!(
+                r#"{{"type":"wasm/MsgStoreCode","value":{{"sender":"{DELEGATOR}","wasm_byte_code":"AGFzbQ=="}}}}"#
+            ),
+        );
+
+        assert_sign_bytes(
+            &layer_climb_proto::wasm::MsgInstantiateContract {
+                sender: DELEGATOR.to_string(),
+                admin: String::new(),
+                code_id: 1,
+                label: "counter".to_string(),
+                msg: br#"{"count":0}"#.to_vec(),
+                funds: vec![],
+            },
+            &format!(
+                r#"{{"type":"wasm/MsgInstantiateContract","value":{{"code_id":"1","funds":[],"label":"counter","msg":{{"count":0}},"sender":"{DELEGATOR}"}}}}"#
+            ),
+        );
+
+        assert_sign_bytes(
+            &layer_climb_proto::wasm::MsgInstantiateContract2 {
+                sender: DELEGATOR.to_string(),
+                admin: DELEGATOR.to_string(),
+                code_id: 1,
+                label: "counter".to_string(),
+                msg: br#"{"count":0}"#.to_vec(),
+                funds: vec![coin(1)],
+                salt: b"salt".to_vec(),
+                fix_msg: false,
+            },
+            &format!(
+                r#"{{"type":"wasm/MsgInstantiateContract2","value":{{"admin":"{DELEGATOR}","code_id":"1","funds":[{{"amount":"1","denom":"ustake"}}],"label":"counter","msg":{{"count":0}},"salt":"c2FsdA==","sender":"{DELEGATOR}"}}}}"#
+            ),
+        );
+
+        assert_sign_bytes(
+            &layer_climb_proto::wasm::MsgExecuteContract {
+                sender: DELEGATOR.to_string(),
+                contract: CONTRACT.to_string(),
+                msg: br#"{"increment":{"by":2}}"#.to_vec(),
+                funds: vec![],
+            },
+            &format!(
+                r#"{{"type":"wasm/MsgExecuteContract","value":{{"contract":"{CONTRACT}","funds":[],"msg":{{"increment":{{"by":2}}}},"sender":"{DELEGATOR}"}}}}"#
+            ),
+        );
+
+        assert_sign_bytes(
+            &layer_climb_proto::wasm::MsgMigrateContract {
+                sender: DELEGATOR.to_string(),
+                contract: CONTRACT.to_string(),
+                code_id: 2,
+                msg: b"{}".to_vec(),
+            },
+            &format!(
+                r#"{{"type":"wasm/MsgMigrateContract","value":{{"code_id":"2","contract":"{CONTRACT}","msg":{{}},"sender":"{DELEGATOR}"}}}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn authz_msgs() {
+        let authorization = proto_into_any(&layer_climb_proto::authz::GenericAuthorization {
+            msg: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+        })
+        .unwrap();
+
+        assert_sign_bytes(
+            &layer_climb_proto::authz::MsgGrant {
+                granter: DELEGATOR.to_string(),
+                grantee: GRANTEE.to_string(),
+                grant: Some(layer_climb_proto::authz::Grant {
+                    authorization: Some(authorization),
+                    expiration: Some(layer_climb_proto::Timestamp {
+                        seconds: 1893456000,
+                        nanos: 0,
+                    }),
+                }),
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgGrant","value":{{"grant":{{"authorization":{{"type":"cosmos-sdk/GenericAuthorization","value":{{"msg":"/cosmos.bank.v1beta1.MsgSend"}}}},"expiration":"2030-01-01T00:00:00Z"}},"grantee":"{GRANTEE}","granter":"{DELEGATOR}"}}}}"#
+            ),
+        );
+
+        assert_sign_bytes(
+            &layer_climb_proto::authz::MsgRevoke {
+                granter: DELEGATOR.to_string(),
+                grantee: GRANTEE.to_string(),
+                msg_type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgRevoke","value":{{"grantee":"{GRANTEE}","granter":"{DELEGATOR}","msg_type_url":"/cosmos.bank.v1beta1.MsgSend"}}}}"#
+            ),
+        );
+
+        assert_sign_bytes(
+            &layer_climb_proto::authz::MsgExec {
+                grantee: GRANTEE.to_string(),
+                msgs: vec![proto_into_any(&bank_send()).unwrap()],
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgExec","value":{{"grantee":"{GRANTEE}","msgs":[{{"type":"cosmos-sdk/MsgSend","value":{{"amount":[{{"amount":"1000","denom":"ustake"}}],"from_address":"{DELEGATOR}","to_address":"{GRANTEE}"}}}}]}}}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn authz_exec_proto_json() {
+        let exec = proto_into_any(&layer_climb_proto::authz::MsgExec {
+            grantee: GRANTEE.to_string(),
+            msgs: vec![proto_into_any(&bank_send()).unwrap()],
+        })
+        .unwrap();
+
+        assert_eq!(
+            DEFAULT_AMINO_MSG_REGISTRY.to_proto_json(&exec).unwrap(),
+            serde_json::json!({
+                "@type": "/cosmos.authz.v1beta1.MsgExec",
+                "grantee": GRANTEE,
+                "msgs": [{
+                    "@type": "/cosmos.bank.v1beta1.MsgSend",
+                    "from_address": DELEGATOR,
+                    "to_address": GRANTEE,
+                    "amount": [{"denom": "ustake", "amount": "1000"}],
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn proto_json_defaults_and_enums() {
+        let vote = proto_into_any(&layer_climb_proto::gov::v1beta1::MsgVote {
+            proposal_id: 0,
+            voter: DELEGATOR.to_string(),
+            option: layer_climb_proto::gov::v1beta1::VoteOption::NoWithVeto.into(),
+        })
+        .unwrap();
+
+        assert_eq!(
+            DEFAULT_AMINO_MSG_REGISTRY.to_proto_json(&vote).unwrap(),
+            serde_json::json!({
+                "@type": "/cosmos.gov.v1beta1.MsgVote",
+                "proposal_id": "0",
+                "voter": DELEGATOR,
+                "option": "VOTE_OPTION_NO_WITH_VETO",
+            })
+        );
+        // amino json is unchanged, i.e. numeric enums and no empty fields
+        assert_eq!(
+            DEFAULT_AMINO_MSG_REGISTRY.to_amino(&vote).unwrap().value,
+            serde_json::json!({"voter": DELEGATOR, "option": 4})
+        );
+
+        let store_code = proto_into_any(&layer_climb_proto::wasm::MsgStoreCode {
+            sender: DELEGATOR.to_string(),
+            wasm_byte_code: Vec::new(),
+            instantiate_permission: None,
+        })
+        .unwrap();
+
+        assert_eq!(
+            DEFAULT_AMINO_MSG_REGISTRY
+                .to_proto_json(&store_code)
+                .unwrap(),
+            serde_json::json!({
+                "@type": "/cosmwasm.wasm.v1.MsgStoreCode",
+                "sender": DELEGATOR,
+                "wasm_byte_code": "",
+                "instantiate_permission": null,
+            })
+        );
+    }
+
+    #[test]
+    fn proto_json_round_trip() {
+        let msgs = vec![
+            proto_into_any(&layer_climb_proto::authz::MsgExec {
+                grantee: GRANTEE.to_string(),
+                msgs: vec![proto_into_any(&bank_send()).unwrap()],
+            })
+            .unwrap(),
+            proto_into_any(&layer_climb_proto::authz::MsgGrant {
+                granter: DELEGATOR.to_string(),
+                grantee: GRANTEE.to_string(),
+                grant: Some(layer_climb_proto::authz::Grant {
+                    authorization: Some(
+                        proto_into_any(&layer_climb_proto::bank::SendAuthorization {
+                            spend_limit: vec![coin(1000)],
+                            allow_list: vec![GRANTEE.to_string()],
+                        })
+                        .unwrap(),
+                    ),
+                    expiration: Some(layer_climb_proto::Timestamp {
+                        seconds: 1_700_000_000,
+                        nanos: 500_000_000,
+                    }),
+                }),
+            })
+            .unwrap(),
+            proto_into_any(&layer_climb_proto::wasm::MsgStoreCode {
+                sender: DELEGATOR.to_string(),
+                wasm_byte_code: vec![0, 97, 115, 109],
+                instantiate_permission: Some(layer_climb_proto::wasm::AccessConfig {
+                    permission: layer_climb_proto::wasm::AccessType::AnyOfAddresses.into(),
+                    addresses: vec![GRANTEE.to_string()],
+                }),
+            })
+            .unwrap(),
+            proto_into_any(&layer_climb_proto::wasm::MsgInstantiateContract2 {
+                sender: DELEGATOR.to_string(),
+                admin: String::new(),
+                code_id: 1,
+                label: "label".to_string(),
+                msg: br#"{"b":1,"a":[true,null]}"#.to_vec(),
+                funds: vec![coin(1)],
+                salt: vec![1, 2, 3],
+                fix_msg: true,
+            })
+            .unwrap(),
+            proto_into_any(&layer_climb_proto::staking::MsgCancelUnbondingDelegation {
+                delegator_address: DELEGATOR.to_string(),
+                validator_address: VALIDATOR.to_string(),
+                amount: Some(coin(1000)),
+                creation_height: 123,
+            })
+            .unwrap(),
+            proto_into_any(&layer_climb_proto::gov::v1beta1::MsgVoteWeighted {
+                proposal_id: 5,
+                voter: DELEGATOR.to_string(),
+                options: vec![
+                    layer_climb_proto::gov::v1beta1::WeightedVoteOption {
+                        option: 1,
+                        weight: "700000000000000000".to_string(),
+                    },
+                    layer_climb_proto::gov::v1beta1::WeightedVoteOption {
+                        option: 3,
+                        weight: "1000000000000000000".to_string(),
+                    },
+                ],
+            })
+            .unwrap(),
+            proto_into_any(&layer_climb_proto::gov::v1beta1::MsgSubmitProposal {
+                content: Some(
+                    proto_into_any(&layer_climb_proto::gov::v1beta1::TextProposal {
+                        title: "Title".to_string(),
+                        description: "Description".to_string(),
+                    })
+                    .unwrap(),
+                ),
+                initial_deposit: Vec::new(),
+                proposer: DELEGATOR.to_string(),
+            })
+            .unwrap(),
+            proto_into_any(&layer_climb_proto::ibc::transfer::MsgTransfer {
+                source_port: "transfer".to_string(),
+                source_channel: "channel-0".to_string(),
+                token: Some(convert(&coin(1000))),
+                sender: DELEGATOR.to_string(),
+                receiver: "osmo1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5pa4g6x".to_string(),
+                timeout_height: Some(layer_climb_proto::ibc::client::Height {
+                    revision_number: 1,
+                    revision_height: 0,
+                }),
+                timeout_timestamp: 1700000000000000000,
+                memo: String::new(),
+            })
+            .unwrap(),
+        ];
+
+        for msg in msgs {
+            let json = DEFAULT_AMINO_MSG_REGISTRY.to_proto_json(&msg).unwrap();
+            // i.e. through a file
+            let json: Value = serde_json::from_str(&json.to_string()).unwrap();
+
+            assert_eq!(
+                DEFAULT_AMINO_MSG_REGISTRY.from_proto_json(&json).unwrap(),
+                msg,
+                "{}",
+                msg.type_url
+            );
+        }
+    }
+
+    #[test]
+    fn legacy_dec() {
+        assert_eq!(
+            legacy_dec_json("500000000000000000").unwrap(),
+            "0.500000000000000000"
+        );
+        assert_eq!(
+            legacy_dec_atomics("0.500000000000000000").unwrap(),
+            "500000000000000000"
+        );
+        assert_eq!(legacy_dec_atomics("0.5").unwrap(), "500000000000000000");
+        assert_eq!(legacy_dec_atomics("1").unwrap(), "1000000000000000000");
+        assert!(legacy_dec_atomics("0.1234567890123456789").is_err());
+        assert!(legacy_dec_atomics("half").is_err());
+    }
+
+    #[test]
+    fn ibc_msg_transfer() {
+        assert_sign_bytes(
+            &layer_climb_proto::ibc::transfer::MsgTransfer {
+                source_port: "transfer".to_string(),
+                source_channel: "channel-0".to_string(),
+                token: Some(convert(&coin(1000))),
+                sender: DELEGATOR.to_string(),
+                receiver: "osmo1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5pa4g6x".to_string(),
+                timeout_height: Some(layer_climb_proto::ibc::client::Height {
+                    revision_number: 1,
+                    revision_height: 0,
+                }),
+                timeout_timestamp: 1700000000000000000,
+                memo: String::new(),
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgTransfer","value":{{"receiver":"osmo1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5pa4g6x","sender":"{DELEGATOR}","source_channel":"channel-0","source_port":"transfer","timeout_height":{{"revision_number":"1"}},"timeout_timestamp":"1700000000000000000","token":{{"amount":"1000","denom":"ustake"}}}}}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn staking_delegate() {
+        assert_sign_bytes(
+            &layer_climb_proto::staking::MsgDelegate {
+                delegator_address: DELEGATOR.to_string(),
+                validator_address: VALIDATOR.to_string(),
+                amount: Some(coin(1000)),
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgDelegate","value":{{"amount":{{"amount":"1000","denom":"ustake"}},"delegator_address":"{DELEGATOR}","validator_address":"{VALIDATOR}"}}}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn staking_undelegate() {
+        assert_sign_bytes(
+            &layer_climb_proto::staking::MsgUndelegate {
+                delegator_address: DELEGATOR.to_string(),
+                validator_address: VALIDATOR.to_string(),
+                amount: Some(coin(1000)),
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgUndelegate","value":{{"amount":{{"amount":"1000","denom":"ustake"}},"delegator_address":"{DELEGATOR}","validator_address":"{VALIDATOR}"}}}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn staking_begin_redelegate() {
+        assert_sign_bytes(
+            &layer_climb_proto::staking::MsgBeginRedelegate {
+                delegator_address: DELEGATOR.to_string(),
+                validator_src_address: VALIDATOR.to_string(),
+                validator_dst_address: VALIDATOR_DST.to_string(),
+                amount: Some(coin(1000)),
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgBeginRedelegate","value":{{"amount":{{"amount":"1000","denom":"ustake"}},"delegator_address":"{DELEGATOR}","validator_dst_address":"{VALIDATOR_DST}","validator_src_address":"{VALIDATOR}"}}}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn staking_cancel_unbonding_delegation() {
+        assert_sign_bytes(
+            &layer_climb_proto::staking::MsgCancelUnbondingDelegation {
+                delegator_address: DELEGATOR.to_string(),
+                validator_address: VALIDATOR.to_string(),
+                amount: Some(coin(1000)),
+                creation_height: 12345,
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgCancelUnbondingDelegation","value":{{"amount":{{"amount":"1000","denom":"ustake"}},"creation_height":"12345","delegator_address":"{DELEGATOR}","validator_address":"{VALIDATOR}"}}}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn distribution_withdraw_delegator_reward() {
+        assert_sign_bytes(
+            &layer_climb_proto::distribution::MsgWithdrawDelegatorReward {
+                delegator_address: DELEGATOR.to_string(),
+                validator_address: VALIDATOR.to_string(),
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgWithdrawDelegationReward","value":{{"delegator_address":"{DELEGATOR}","validator_address":"{VALIDATOR}"}}}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn distribution_set_withdraw_address() {
+        assert_sign_bytes(
+            &layer_climb_proto::distribution::MsgSetWithdrawAddress {
+                delegator_address: DELEGATOR.to_string(),
+                withdraw_address: DELEGATOR.to_string(),
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgModifyWithdrawAddress","value":{{"delegator_address":"{DELEGATOR}","withdraw_address":"{DELEGATOR}"}}}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn distribution_withdraw_validator_commission() {
+        assert_sign_bytes(
+            &layer_climb_proto::distribution::MsgWithdrawValidatorCommission {
+                validator_address: VALIDATOR.to_string(),
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgWithdrawValCommission","value":{{"validator_address":"{VALIDATOR}"}}}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn gov_vote() {
+        assert_sign_bytes(
+            &layer_climb_proto::gov::v1beta1::MsgVote {
+                proposal_id: 5,
+                voter: DELEGATOR.to_string(),
+                option: layer_climb_proto::gov::v1beta1::VoteOption::Yes as i32,
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgVote","value":{{"option":1,"proposal_id":"5","voter":"{DELEGATOR}"}}}}"#
+            ),
+        );
+
+        assert_sign_bytes(
+            &layer_climb_proto::gov::v1::MsgVote {
+                proposal_id: 5,
+                voter: DELEGATOR.to_string(),
+                option: layer_climb_proto::gov::v1::VoteOption::NoWithVeto as i32,
+                metadata: "because".to_string(),
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/v1/MsgVote","value":{{"metadata":"because","option":4,"proposal_id":"5","voter":"{DELEGATOR}"}}}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn gov_vote_weighted() {
+        assert_sign_bytes(
+            &layer_climb_proto::gov::v1beta1::MsgVoteWeighted {
+                proposal_id: 5,
+                voter: DELEGATOR.to_string(),
+                options: vec![
+                    layer_climb_proto::gov::v1beta1::WeightedVoteOption {
+                        option: 1,
+                        weight: "700000000000000000".to_string(),
+                    },
+                    layer_climb_proto::gov::v1beta1::WeightedVoteOption {
+                        option: 3,
+                        weight: "300000000000000000".to_string(),
+                    },
+                ],
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgVoteWeighted","value":{{"options":[{{"option":1,"weight":"0.700000000000000000"}},{{"option":3,"weight":"0.300000000000000000"}}],"proposal_id":"5","voter":"{DELEGATOR}"}}}}"#
+            ),
+        );
+
+        assert_sign_bytes(
+            &layer_climb_proto::gov::v1::MsgVoteWeighted {
+                proposal_id: 5,
+                voter: DELEGATOR.to_string(),
+                options: vec![layer_climb_proto::gov::v1::WeightedVoteOption {
+                    option: 1,
+                    weight: "1.000000000000000000".to_string(),
+                }],
+                metadata: String::new(),
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/v1/MsgVoteWeighted","value":{{"options":[{{"option":1,"weight":"1.000000000000000000"}}],"proposal_id":"5","voter":"{DELEGATOR}"}}}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn gov_deposit() {
+        assert_sign_bytes(
+            &layer_climb_proto::gov::v1beta1::MsgDeposit {
+                proposal_id: 5,
+                depositor: DELEGATOR.to_string(),
+                amount: vec![coin(1000)],
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgDeposit","value":{{"amount":[{{"amount":"1000","denom":"ustake"}}],"depositor":"{DELEGATOR}","proposal_id":"5"}}}}"#
+            ),
+        );
+
+        assert_sign_bytes(
+            &layer_climb_proto::gov::v1::MsgDeposit {
+                proposal_id: 5,
+                depositor: DELEGATOR.to_string(),
+                amount: vec![coin(1000)],
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/v1/MsgDeposit","value":{{"amount":[{{"amount":"1000","denom":"ustake"}}],"depositor":"{DELEGATOR}","proposal_id":"5"}}}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn gov_submit_proposal() {
+        let content = proto_into_any(&layer_climb_proto::gov::v1beta1::TextProposal {
+            title: "Title".to_string(),
+            description: "Description".to_string(),
+        })
+        .unwrap();
+
+        assert_sign_bytes(
+            &layer_climb_proto::gov::v1beta1::MsgSubmitProposal {
+                content: Some(content),
+                initial_deposit: vec![coin(1000)],
+                proposer: DELEGATOR.to_string(),
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/MsgSubmitProposal","value":{{"content":{{"type":"cosmos-sdk/TextProposal","value":{{"description":"Description","title":"Title"}}}},"initial_deposit":[{{"amount":"1000","denom":"ustake"}}],"proposer":"{DELEGATOR}"}}}}"#
+            ),
+        );
+
+        let send = proto_into_any(&layer_climb_proto::bank::MsgSend {
+            from_address: DELEGATOR.to_string(),
+            to_address: DELEGATOR.to_string(),
+            amount: vec![coin(1)],
+        })
+        .unwrap();
+
+        assert_sign_bytes(
+            &layer_climb_proto::gov::v1::MsgSubmitProposal {
+                messages: vec![send],
+                initial_deposit: vec![],
+                proposer: DELEGATOR.to_string(),
+                metadata: String::new(),
+                title: "Title".to_string(),
+                summary: "Summary".to_string(),
+                expedited: true,
+            },
+            &format!(
+                r#"{{"type":"cosmos-sdk/v1/MsgSubmitProposal","value":{{"expedited":true,"initial_deposit":[],"messages":[{{"type":"cosmos-sdk/MsgSend","value":{{"amount":[{{"amount":"1","denom":"ustake"}}],"from_address":"{DELEGATOR}","to_address":"{DELEGATOR}"}}}}],"proposer":"{DELEGATOR}","summary":"Summary","title":"Title"}}}}"#
+            ),
+        );
+    }
+}
@@ -0,0 +1,276 @@
+// Decoding of the `Any` messages in a tx into typed values, e.g. for `QueryClient::block_txs()`
+// messages that aren't registered, or fail to decode, are kept as-is in `TxMsg::Unknown`
+
+use std::{collections::HashMap, sync::Arc, sync::LazyLock};
+
+use layer_climb_proto::Name;
+
+use crate::prelude::*;
+
+/// Decodes the protobuf message into its typed form
+/// gets the registry too, for messages which wrap other messages (e.g. authz MsgExec)
+pub type MsgDecoder = fn(&MsgRegistry, &layer_climb_proto::Any) -> Result<TxMsg>;
+
+pub static DEFAULT_MSG_REGISTRY: LazyLock<MsgRegistry> = LazyLock::new(MsgRegistry::default);
+
+/// A tx message, decoded via a `MsgRegistry`
+#[derive(Clone, Debug)]
+pub enum TxMsg {
+    BankSend(layer_climb_proto::bank::MsgSend),
+    BankMultiSend(layer_climb_proto::bank::MsgMultiSend),
+    WasmStoreCode(layer_climb_proto::wasm::MsgStoreCode),
+    WasmInstantiate(layer_climb_proto::wasm::MsgInstantiateContract),
+    WasmInstantiate2(layer_climb_proto::wasm::MsgInstantiateContract2),
+    WasmExecute(layer_climb_proto::wasm::MsgExecuteContract),
+    WasmMigrate(layer_climb_proto::wasm::MsgMigrateContract),
+    WasmUpdateAdmin(layer_climb_proto::wasm::MsgUpdateAdmin),
+    WasmClearAdmin(layer_climb_proto::wasm::MsgClearAdmin),
+    AuthzGrant(layer_climb_proto::authz::MsgGrant),
+    AuthzRevoke(layer_climb_proto::authz::MsgRevoke),
+    /// the wrapped messages are decoded with the same registry
+    AuthzExec {
+        grantee: String,
+        msgs: Vec<TxMsg>,
+    },
+    IbcTransfer(layer_climb_proto::ibc::transfer::MsgTransfer),
+    IbcUpdateClient(layer_climb_proto::ibc::client::MsgUpdateClient),
+    IbcRecvPacket(Box<layer_climb_proto::ibc::channel::MsgRecvPacket>),
+    IbcAcknowledgement(Box<layer_climb_proto::ibc::channel::MsgAcknowledgement>),
+    IbcTimeout(Box<layer_climb_proto::ibc::channel::MsgTimeout>),
+    StakingDelegate(layer_climb_proto::staking::MsgDelegate),
+    StakingUndelegate(layer_climb_proto::staking::MsgUndelegate),
+    StakingRedelegate(layer_climb_proto::staking::MsgBeginRedelegate),
+    StakingCancelUnbondingDelegation(layer_climb_proto::staking::MsgCancelUnbondingDelegation),
+    /// for messages registered outside of climb, see `TxMsg::custom()`
+    Custom {
+        type_url: String,
+        value: Arc<dyn std::any::Any + Send + Sync>,
+    },
+    /// not in the registry, or its decoder failed
+    Unknown(layer_climb_proto::Any),
+}
+
+impl TxMsg {
+    /// For decoders of messages climb doesn't know about
+    pub fn custom<T: std::any::Any + Send + Sync>(type_url: impl ToString, value: T) -> Self {
+        Self::Custom {
+            type_url: type_url.to_string(),
+            value: Arc::new(value),
+        }
+    }
+
+    /// The value of a `TxMsg::Custom`, if it's a `T`
+    pub fn as_custom<T: std::any::Any>(&self) -> Option<&T> {
+        match self {
+            Self::Custom { value, .. } => value.downcast_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn type_url(&self) -> String {
+        match self {
+            Self::BankSend(_) => layer_climb_proto::bank::MsgSend::type_url(),
+            Self::BankMultiSend(_) => layer_climb_proto::bank::MsgMultiSend::type_url(),
+            Self::WasmStoreCode(_) => layer_climb_proto::wasm::MsgStoreCode::type_url(),
+            Self::WasmInstantiate(_) => layer_climb_proto::wasm::MsgInstantiateContract::type_url(),
+            Self::WasmInstantiate2(_) => {
+                layer_climb_proto::wasm::MsgInstantiateContract2::type_url()
+            }
+            Self::WasmExecute(_) => layer_climb_proto::wasm::MsgExecuteContract::type_url(),
+            Self::WasmMigrate(_) => layer_climb_proto::wasm::MsgMigrateContract::type_url(),
+            Self::WasmUpdateAdmin(_) => layer_climb_proto::wasm::MsgUpdateAdmin::type_url(),
+            Self::WasmClearAdmin(_) => layer_climb_proto::wasm::MsgClearAdmin::type_url(),
+            Self::AuthzGrant(_) => layer_climb_proto::authz::MsgGrant::type_url(),
+            Self::AuthzRevoke(_) => layer_climb_proto::authz::MsgRevoke::type_url(),
+            Self::AuthzExec { .. } => layer_climb_proto::authz::MsgExec::type_url(),
+            Self::IbcTransfer(_) => layer_climb_proto::ibc::transfer::MsgTransfer::type_url(),
+            Self::IbcUpdateClient(_) => layer_climb_proto::ibc::client::MsgUpdateClient::type_url(),
+            Self::IbcRecvPacket(_) => layer_climb_proto::ibc::channel::MsgRecvPacket::type_url(),
+            Self::IbcAcknowledgement(_) => {
+                layer_climb_proto::ibc::channel::MsgAcknowledgement::type_url()
+            }
+            Self::IbcTimeout(_) => layer_climb_proto::ibc::channel::MsgTimeout::type_url(),
+            Self::StakingDelegate(_) => layer_climb_proto::staking::MsgDelegate::type_url(),
+            Self::StakingUndelegate(_) => layer_climb_proto::staking::MsgUndelegate::type_url(),
+            Self::StakingRedelegate(_) => {
+                layer_climb_proto::staking::MsgBeginRedelegate::type_url()
+            }
+            Self::StakingCancelUnbondingDelegation(_) => {
+                layer_climb_proto::staking::MsgCancelUnbondingDelegation::type_url()
+            }
+            Self::Custom { type_url, .. } => type_url.clone(),
+            Self::Unknown(any) => any.type_url.clone(),
+        }
+    }
+}
+
+/// Maps protobuf type urls to decoders for them
+/// the default registry covers the bank, wasm, authz, ibc and staking messages,
+/// more can be added via `register()`
+#[derive(Clone)]
+pub struct MsgRegistry {
+    decoders: HashMap<String, MsgDecoder>,
+}
+
+impl MsgRegistry {
+    /// An empty registry, see `default()` for one with the built-in messages
+    pub fn new() -> Self {
+        Self {
+            decoders: HashMap::new(),
+        }
+    }
+
+    pub fn register(&mut self, type_url: impl ToString, decoder: MsgDecoder) -> &mut Self {
+        self.decoders.insert(type_url.to_string(), decoder);
+        self
+    }
+
+    pub fn contains(&self, type_url: &str) -> bool {
+        self.decoders.contains_key(type_url)
+    }
+
+    /// Unregistered messages, and registered ones that fail to decode, become `TxMsg::Unknown`
+    /// so one odd message doesn't hide the rest of the tx
+    pub fn decode(&self, msg: &layer_climb_proto::Any) -> TxMsg {
+        let Some(decoder) = self.decoders.get(&msg.type_url) else {
+            return TxMsg::Unknown(msg.clone());
+        };
+
+        match decoder(self, msg) {
+            Ok(decoded) => decoded,
+            Err(err) => {
+                tracing::debug!("couldn't decode {}: {err:?}", msg.type_url);
+                TxMsg::Unknown(msg.clone())
+            }
+        }
+    }
+
+    pub fn decode_all(&self, msgs: &[layer_climb_proto::Any]) -> Vec<TxMsg> {
+        msgs.iter().map(|msg| self.decode(msg)).collect()
+    }
+}
+
+impl Default for MsgRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+
+        registry
+            // bank
+            .register("/cosmos.bank.v1beta1.MsgSend", |_, msg| {
+                Ok(TxMsg::BankSend(decode(msg)?))
+            })
+            .register("/cosmos.bank.v1beta1.MsgMultiSend", |_, msg| {
+                Ok(TxMsg::BankMultiSend(decode(msg)?))
+            })
+            // wasm
+            .register("/cosmwasm.wasm.v1.MsgStoreCode", |_, msg| {
+                Ok(TxMsg::WasmStoreCode(decode(msg)?))
+            })
+            .register("/cosmwasm.wasm.v1.MsgInstantiateContract", |_, msg| {
+                Ok(TxMsg::WasmInstantiate(decode(msg)?))
+            })
+            .register("/cosmwasm.wasm.v1.MsgInstantiateContract2", |_, msg| {
+                Ok(TxMsg::WasmInstantiate2(decode(msg)?))
+            })
+            .register("/cosmwasm.wasm.v1.MsgExecuteContract", |_, msg| {
+                Ok(TxMsg::WasmExecute(decode(msg)?))
+            })
+            .register("/cosmwasm.wasm.v1.MsgMigrateContract", |_, msg| {
+                Ok(TxMsg::WasmMigrate(decode(msg)?))
+            })
+            .register("/cosmwasm.wasm.v1.MsgUpdateAdmin", |_, msg| {
+                Ok(TxMsg::WasmUpdateAdmin(decode(msg)?))
+            })
+            .register("/cosmwasm.wasm.v1.MsgClearAdmin", |_, msg| {
+                Ok(TxMsg::WasmClearAdmin(decode(msg)?))
+            })
+            // authz
+            .register("/cosmos.authz.v1beta1.MsgGrant", |_, msg| {
+                Ok(TxMsg::AuthzGrant(decode(msg)?))
+            })
+            .register("/cosmos.authz.v1beta1.MsgRevoke", |_, msg| {
+                Ok(TxMsg::AuthzRevoke(decode(msg)?))
+            })
+            .register("/cosmos.authz.v1beta1.MsgExec", authz_exec)
+            // ibc
+            .register("/ibc.applications.transfer.v1.MsgTransfer", |_, msg| {
+                Ok(TxMsg::IbcTransfer(decode(msg)?))
+            })
+            .register("/ibc.core.client.v1.MsgUpdateClient", |_, msg| {
+                Ok(TxMsg::IbcUpdateClient(decode(msg)?))
+            })
+            .register("/ibc.core.channel.v1.MsgRecvPacket", |_, msg| {
+                Ok(TxMsg::IbcRecvPacket(Box::new(decode(msg)?)))
+            })
+            .register("/ibc.core.channel.v1.MsgAcknowledgement", |_, msg| {
+                Ok(TxMsg::IbcAcknowledgement(Box::new(decode(msg)?)))
+            })
+            .register("/ibc.core.channel.v1.MsgTimeout", |_, msg| {
+                Ok(TxMsg::IbcTimeout(Box::new(decode(msg)?)))
+            })
+            // staking
+            .register("/cosmos.staking.v1beta1.MsgDelegate", |_, msg| {
+                Ok(TxMsg::StakingDelegate(decode(msg)?))
+            })
+            .register("/cosmos.staking.v1beta1.MsgUndelegate", |_, msg| {
+                Ok(TxMsg::StakingUndelegate(decode(msg)?))
+            })
+            .register("/cosmos.staking.v1beta1.MsgBeginRedelegate", |_, msg| {
+                Ok(TxMsg::StakingRedelegate(decode(msg)?))
+            })
+            .register(
+                "/cosmos.staking.v1beta1.MsgCancelUnbondingDelegation",
+                |_, msg| Ok(TxMsg::StakingCancelUnbondingDelegation(decode(msg)?)),
+            );
+
+        registry
+    }
+}
+
+fn authz_exec(registry: &MsgRegistry, msg: &layer_climb_proto::Any) -> Result<TxMsg> {
+    let msg: layer_climb_proto::authz::MsgExec = decode(msg)?;
+
+    Ok(TxMsg::AuthzExec {
+        grantee: msg.grantee,
+        msgs: registry.decode_all(&msg.msgs),
+    })
+}
+
+fn decode<M: Message + Default>(msg: &layer_climb_proto::Any) -> Result<M> {
+    Ok(M::decode(msg.value.as_slice())?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode_falls_back_to_unknown() {
+        let registry = MsgRegistry::default();
+
+        let send = layer_climb_proto::bank::MsgSend {
+            from_address: "a".to_string(),
+            to_address: "b".to_string(),
+            amount: vec![],
+        };
+        let send = layer_climb_proto::Any {
+            type_url: layer_climb_proto::bank::MsgSend::type_url(),
+            value: send.encode_to_vec(),
+        };
+        assert!(matches!(registry.decode(&send), TxMsg::BankSend(msg) if msg.to_address == "b"));
+
+        let garbage = layer_climb_proto::Any {
+            type_url: layer_climb_proto::bank::MsgSend::type_url(),
+            value: vec![0xff, 0xff, 0xff],
+        };
+        let unregistered = layer_climb_proto::Any {
+            type_url: "/chain.custom.v1.MsgCustom".to_string(),
+            value: vec![1, 2, 3],
+        };
+
+        let msgs = registry.decode_all(&[garbage.clone(), unregistered.clone(), send]);
+        assert!(matches!(&msgs[0], TxMsg::Unknown(any) if *any == garbage));
+        assert!(matches!(&msgs[1], TxMsg::Unknown(any) if *any == unregistered));
+        assert!(matches!(&msgs[2], TxMsg::BankSend(_)));
+    }
+}
@@ -0,0 +1,856 @@
+// generate-only / offline signing
+// i.e. build the tx on one host, sign it on another (possibly air-gapped) host, and broadcast it later
+//
+// 1. `TxBuilder::build_unsigned()` fixes the fee, sequence and timeout, and gives an `UnsignedTx`
+// 2. `UnsignedTx::to_json()`/`to_bytes()` exports it, `from_json()`/`from_bytes()` imports it on the signing host
+// 3. `UnsignedTx::sign()` signs it with any `TxSigner`, giving a `SignedTx`
+// 4. `SignedTx::to_bytes()` can then be broadcast with `QueryClient::broadcast_tx_bytes()`
+//
+// both also have `to_sdk_json()`, which is the cosmos-sdk's tx json file format
+// for use with `tx sign` (unsigned) and `tx broadcast` (signed)
+// and `SignedTx::from_sdk_json()` reads back what `tx sign` gives
+
+use base64::prelude::*;
+use layer_climb_signer::TxSigner;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::amino::{AminoMsgRegistry, ProtoJson, DEFAULT_AMINO_MSG_REGISTRY};
+use crate::prelude::*;
+
+/// A tx that's ready to be signed, i.e. everything in the `SignDoc` is fixed
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UnsignedTx {
+    pub chain_id: String,
+    #[serde(with = "string_u64")]
+    pub account_number: u64,
+    #[serde(with = "base64_bytes")]
+    pub body_bytes: Vec<u8>,
+    #[serde(with = "base64_bytes")]
+    pub auth_info_bytes: Vec<u8>,
+}
+
+impl UnsignedTx {
+    pub fn sign_doc(&self) -> layer_climb_proto::tx::SignDoc {
+        layer_climb_proto::tx::SignDoc {
+            body_bytes: self.body_bytes.clone(),
+            auth_info_bytes: self.auth_info_bytes.clone(),
+            chain_id: self.chain_id.clone(),
+            account_number: self.account_number,
+        }
+    }
+
+    pub fn body(&self) -> Result<layer_climb_proto::tx::TxBodyV053> {
+        Ok(layer_climb_proto::tx::TxBodyV053::decode(
+            self.body_bytes.as_slice(),
+        )?)
+    }
+
+    pub fn auth_info(&self) -> Result<layer_climb_proto::tx::AuthInfo> {
+        Ok(layer_climb_proto::tx::AuthInfo::decode(
+            self.auth_info_bytes.as_slice(),
+        )?)
+    }
+
+    /// The sign mode that the tx was built for
+    pub fn sign_mode(&self) -> Result<layer_climb_proto::tx::signing::SignMode> {
+        let auth_info = self.auth_info()?;
+        let signer_info = auth_info
+            .signer_infos
+            .first()
+            .context("missing signer info")?;
+
+        signer_info_sign_mode(signer_info)
+    }
+
+    /// The protobuf-encoded `SignDoc`
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        proto_into_bytes(&self.sign_doc())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Ok(layer_climb_proto::tx::SignDoc::decode(bytes)?.into())
+    }
+
+    /// Unlike `to_sdk_json()`, this keeps everything needed to sign the tx, and can be read back with `from_json()`
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// The cosmos-sdk's "generate-only" tx json, i.e. what `tx sign` expects
+    /// the sdk fills in the signer info itself, so `tx sign` needs the same `--account-number` and `--sequence`
+    pub fn to_sdk_json(&self, registry: Option<&AminoMsgRegistry>) -> Result<Value> {
+        let registry = registry.unwrap_or(&DEFAULT_AMINO_MSG_REGISTRY);
+        let mut auth_info = self.auth_info()?;
+        auth_info.signer_infos.clear();
+
+        Ok(json!({
+            "body": tx_body_json(registry, &self.body()?)?,
+            "auth_info": auth_info_json(&auth_info)?,
+            "signatures": [],
+        }))
+    }
+
+    pub async fn sign(&self, signer: &dyn TxSigner) -> Result<SignedTx> {
+        self.sign_with_registry(signer, &DEFAULT_AMINO_MSG_REGISTRY)
+            .await
+    }
+
+    /// Same as `sign()`, but with a custom registry for amino json signing
+    pub async fn sign_with_registry(
+        &self,
+        signer: &dyn TxSigner,
+        registry: &AminoMsgRegistry,
+    ) -> Result<SignedTx> {
+        let sign_doc = self.sign_doc();
+        let signature =
+            super::sign_with_mode(signer, &sign_doc, self.sign_mode()?, registry).await?;
+
+        Ok(SignedTx::new(&sign_doc, signature))
+    }
+}
+
+impl From<layer_climb_proto::tx::SignDoc> for UnsignedTx {
+    fn from(sign_doc: layer_climb_proto::tx::SignDoc) -> Self {
+        Self {
+            chain_id: sign_doc.chain_id,
+            account_number: sign_doc.account_number,
+            body_bytes: sign_doc.body_bytes,
+            auth_info_bytes: sign_doc.auth_info_bytes,
+        }
+    }
+}
+
+/// A signed tx, ready to be broadcast
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SignedTx {
+    #[serde(with = "base64_bytes")]
+    pub body_bytes: Vec<u8>,
+    #[serde(with = "base64_bytes")]
+    pub auth_info_bytes: Vec<u8>,
+    #[serde(with = "base64_bytes_vec")]
+    pub signatures: Vec<Vec<u8>>,
+}
+
+impl SignedTx {
+    pub fn new(sign_doc: &layer_climb_proto::tx::SignDoc, signature: Vec<u8>) -> Self {
+        Self {
+            body_bytes: sign_doc.body_bytes.clone(),
+            auth_info_bytes: sign_doc.auth_info_bytes.clone(),
+            signatures: vec![signature],
+        }
+    }
+
+    pub fn tx_raw(&self) -> layer_climb_proto::tx::TxRaw {
+        layer_climb_proto::tx::TxRaw {
+            body_bytes: self.body_bytes.clone(),
+            auth_info_bytes: self.auth_info_bytes.clone(),
+            signatures: self.signatures.clone(),
+        }
+    }
+
+    /// The protobuf-encoded `TxRaw`, i.e. what gets passed to `QueryClient::broadcast_tx_bytes()`
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        proto_into_bytes(&self.tx_raw())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let tx_raw = layer_climb_proto::tx::TxRaw::decode(bytes)?;
+
+        Ok(Self {
+            body_bytes: tx_raw.body_bytes,
+            auth_info_bytes: tx_raw.auth_info_bytes,
+            signatures: tx_raw.signatures,
+        })
+    }
+
+    /// The hash the tx will have on chain, e.g. for `QueryClient::poll_until_tx_ready()`
+    pub fn tx_hash(&self) -> Result<String> {
+        use tendermint::crypto::Sha256;
+
+        let hash = tendermint::crypto::default::Sha256::digest(self.to_bytes()?);
+        Ok(const_hex::encode_upper(hash))
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// The cosmos-sdk's signed tx json, i.e. what `tx broadcast` expects
+    pub fn to_sdk_json(&self, registry: Option<&AminoMsgRegistry>) -> Result<Value> {
+        let registry = registry.unwrap_or(&DEFAULT_AMINO_MSG_REGISTRY);
+        let body = layer_climb_proto::tx::TxBodyV053::decode(self.body_bytes.as_slice())?;
+        let auth_info = layer_climb_proto::tx::AuthInfo::decode(self.auth_info_bytes.as_slice())?;
+
+        Ok(json!({
+            "body": tx_body_json(registry, &body)?,
+            "auth_info": auth_info_json(&auth_info)?,
+            "signatures": self
+                .signatures
+                .iter()
+                .map(|signature| BASE64_STANDARD.encode(signature))
+                .collect::<Vec<_>>(),
+        }))
+    }
+
+    /// Reads the cosmos-sdk's signed tx json, e.g. the output of `tx sign`
+    /// the tx is re-encoded from the json, so every message must have a parser in the registry
+    pub fn from_sdk_json(json: &Value, registry: Option<&AminoMsgRegistry>) -> Result<Self> {
+        let registry = registry.unwrap_or(&DEFAULT_AMINO_MSG_REGISTRY);
+        let json = ProtoJson::new(json)?;
+
+        let body = tx_body_from_json(registry, &json.object("body")?.context("missing body")?)?;
+        let auth_info =
+            auth_info_from_json(&json.object("auth_info")?.context("missing auth_info")?)?;
+
+        Ok(Self {
+            body_bytes: proto_into_bytes(&body)?,
+            auth_info_bytes: proto_into_bytes(&auth_info)?,
+            signatures: json
+                .strings("signatures")?
+                .iter()
+                .map(|signature| Ok(BASE64_STANDARD.decode(signature)?))
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+fn signer_info_sign_mode(
+    signer_info: &layer_climb_proto::tx::SignerInfo,
+) -> Result<layer_climb_proto::tx::signing::SignMode> {
+    // a multisig is signed by each member in the same mode
+    let mode = match signer_info
+        .mode_info
+        .as_ref()
+        .and_then(|mode_info| mode_info.sum.as_ref())
+        .context("missing mode info")?
+    {
+        layer_climb_proto::tx::mode_info::Sum::Single(single) => single.mode,
+        layer_climb_proto::tx::mode_info::Sum::Multi(multi) => {
+            match multi
+                .mode_infos
+                .first()
+                .and_then(|mode_info| mode_info.sum.as_ref())
+            {
+                Some(layer_climb_proto::tx::mode_info::Sum::Single(single)) => single.mode,
+                _ => bail!("unsupported multisig mode info"),
+            }
+        }
+    };
+
+    Ok(layer_climb_proto::tx::signing::SignMode::try_from(mode)?)
+}
+
+// the rest is the cosmos-sdk's protobuf json encoding of a Tx
+
+fn tx_body_json(
+    registry: &AminoMsgRegistry,
+    body: &layer_climb_proto::tx::TxBodyV053,
+) -> Result<Value> {
+    if !body.extension_options.is_empty() || !body.non_critical_extension_options.is_empty() {
+        bail!("extension options are not supported in tx json");
+    }
+
+    let mut json = json!({
+        "messages": body
+            .messages
+            .iter()
+            .map(|msg| registry.to_proto_json(msg))
+            .collect::<Result<Vec<_>>>()?,
+        "memo": body.memo,
+        "timeout_height": body.timeout_height.to_string(),
+        "extension_options": [],
+        "non_critical_extension_options": [],
+    });
+
+    // only included when set, since older sdk versions reject unknown fields
+    if body.unordered {
+        json["unordered"] = Value::Bool(true);
+    }
+    if let Some(timeout_timestamp) = body.timeout_timestamp {
+        let timeout_timestamp = tendermint::Time::from_unix_timestamp(
+            timeout_timestamp.seconds,
+            timeout_timestamp.nanos.try_into()?,
+        )?;
+        json["timeout_timestamp"] = Value::String(timeout_timestamp.to_rfc3339());
+    }
+
+    Ok(json)
+}
+
+fn auth_info_json(auth_info: &layer_climb_proto::tx::AuthInfo) -> Result<Value> {
+    let fee = auth_info.fee.as_ref().context("missing fee")?;
+
+    Ok(json!({
+        "signer_infos": auth_info
+            .signer_infos
+            .iter()
+            .map(signer_info_json)
+            .collect::<Result<Vec<_>>>()?,
+        "fee": {
+            "amount": fee
+                .amount
+                .iter()
+                .map(|coin| json!({"denom": coin.denom, "amount": coin.amount}))
+                .collect::<Vec<_>>(),
+            "gas_limit": fee.gas_limit.to_string(),
+            "payer": fee.payer,
+            "granter": fee.granter,
+        },
+        "tip": null,
+    }))
+}
+
+fn signer_info_json(signer_info: &layer_climb_proto::tx::SignerInfo) -> Result<Value> {
+    Ok(json!({
+        "public_key": signer_info.public_key.as_ref().map(public_key_json).transpose()?,
+        "mode_info": mode_info_json(signer_info.mode_info.as_ref().context("missing mode info")?)?,
+        "sequence": signer_info.sequence.to_string(),
+    }))
+}
+
+fn public_key_json(public_key: &layer_climb_proto::Any) -> Result<Value> {
+    match public_key.type_url.as_str() {
+        "/cosmos.crypto.secp256k1.PubKey" => {
+            let key =
+                layer_climb_proto::crypto::secp256k1::PubKey::decode(public_key.value.as_slice())?;
+            Ok(json!({"@type": public_key.type_url, "key": BASE64_STANDARD.encode(key.key)}))
+        }
+        "/cosmos.crypto.ed25519.PubKey" => {
+            let key =
+                layer_climb_proto::crypto::ed25519::PubKey::decode(public_key.value.as_slice())?;
+            Ok(json!({"@type": public_key.type_url, "key": BASE64_STANDARD.encode(key.key)}))
+        }
+        "/cosmos.crypto.multisig.LegacyAminoPubKey" => {
+            let key = layer_climb_proto::crypto::multisig::LegacyAminoPubKey::decode(
+                public_key.value.as_slice(),
+            )?;
+            Ok(json!({
+                "@type": public_key.type_url,
+                "threshold": key.threshold,
+                "public_keys": key
+                    .public_keys
+                    .iter()
+                    .map(public_key_json)
+                    .collect::<Result<Vec<_>>>()?,
+            }))
+        }
+        type_url => bail!("unsupported public key type {type_url}"),
+    }
+}
+
+fn mode_info_json(mode_info: &layer_climb_proto::tx::ModeInfo) -> Result<Value> {
+    match mode_info.sum.as_ref().context("missing mode info")? {
+        layer_climb_proto::tx::mode_info::Sum::Single(single) => {
+            let mode = layer_climb_proto::tx::signing::SignMode::try_from(single.mode)?;
+            Ok(json!({"single": {"mode": mode.as_str_name()}}))
+        }
+        layer_climb_proto::tx::mode_info::Sum::Multi(multi) => {
+            let bitarray = multi
+                .bitarray
+                .as_ref()
+                .context("missing multisig bitarray")?;
+            Ok(json!({
+                "multi": {
+                    "bitarray": {
+                        "extra_bits_stored": bitarray.extra_bits_stored,
+                        "elems": BASE64_STANDARD.encode(&bitarray.elems),
+                    },
+                    "mode_infos": multi
+                        .mode_infos
+                        .iter()
+                        .map(mode_info_json)
+                        .collect::<Result<Vec<_>>>()?,
+                }
+            }))
+        }
+    }
+}
+
+// and reading it back
+
+fn tx_body_from_json(
+    registry: &AminoMsgRegistry,
+    json: &ProtoJson,
+) -> Result<layer_climb_proto::tx::TxBodyV053> {
+    if !json.array("extension_options")?.is_empty()
+        || !json.array("non_critical_extension_options")?.is_empty()
+    {
+        bail!("extension options are not supported in tx json");
+    }
+
+    Ok(layer_climb_proto::tx::TxBodyV053 {
+        messages: json.anys(registry, "messages")?,
+        memo: json.string("memo")?,
+        timeout_height: json.uint("timeout_height")?,
+        unordered: json.bool("unordered")?,
+        timeout_timestamp: json.timestamp("timeout_timestamp")?,
+        extension_options: Vec::new(),
+        non_critical_extension_options: Vec::new(),
+    })
+}
+
+fn auth_info_from_json(json: &ProtoJson) -> Result<layer_climb_proto::tx::AuthInfo> {
+    if json.get("tip").is_some() {
+        bail!("tips are not supported in tx json");
+    }
+
+    let fee = json.object("fee")?.context("missing fee")?;
+
+    #[allow(deprecated)]
+    Ok(layer_climb_proto::tx::AuthInfo {
+        signer_infos: json
+            .objects("signer_infos")?
+            .iter()
+            .map(signer_info_from_json)
+            .collect::<Result<_>>()?,
+        fee: Some(layer_climb_proto::tx::Fee {
+            amount: fee.coins("amount")?,
+            gas_limit: fee.uint("gas_limit")?,
+            payer: fee.string("payer")?,
+            granter: fee.string("granter")?,
+        }),
+        tip: None,
+    })
+}
+
+fn signer_info_from_json(json: &ProtoJson) -> Result<layer_climb_proto::tx::SignerInfo> {
+    Ok(layer_climb_proto::tx::SignerInfo {
+        public_key: json
+            .object("public_key")?
+            .as_ref()
+            .map(public_key_from_json)
+            .transpose()?,
+        mode_info: Some(mode_info_from_json(
+            &json.object("mode_info")?.context("missing mode info")?,
+        )?),
+        sequence: json.uint("sequence")?,
+    })
+}
+
+fn public_key_from_json(json: &ProtoJson) -> Result<layer_climb_proto::Any> {
+    let type_url = json.string("@type")?;
+
+    let value = match type_url.as_str() {
+        "/cosmos.crypto.secp256k1.PubKey" => layer_climb_proto::crypto::secp256k1::PubKey {
+            key: json.bytes("key")?,
+        }
+        .encode_to_vec(),
+        "/cosmos.crypto.ed25519.PubKey" => layer_climb_proto::crypto::ed25519::PubKey {
+            key: json.bytes("key")?,
+        }
+        .encode_to_vec(),
+        "/cosmos.crypto.multisig.LegacyAminoPubKey" => {
+            layer_climb_proto::crypto::multisig::LegacyAminoPubKey {
+                threshold: json.uint("threshold")?.try_into()?,
+                public_keys: json
+                    .objects("public_keys")?
+                    .iter()
+                    .map(public_key_from_json)
+                    .collect::<Result<_>>()?,
+            }
+            .encode_to_vec()
+        }
+        type_url => bail!("unsupported public key type {type_url}"),
+    };
+
+    Ok(layer_climb_proto::Any { type_url, value })
+}
+
+fn mode_info_from_json(json: &ProtoJson) -> Result<layer_climb_proto::tx::ModeInfo> {
+    let sum = match (json.object("single")?, json.object("multi")?) {
+        (Some(single), None) => layer_climb_proto::tx::mode_info::Sum::Single(
+            layer_climb_proto::tx::mode_info::Single {
+                mode: single.enumeration("mode", |name| {
+                    layer_climb_proto::tx::signing::SignMode::from_str_name(name).map(Into::into)
+                })?,
+            },
+        ),
+        (None, Some(multi)) => {
+            let bitarray = multi
+                .object("bitarray")?
+                .context("missing multisig bitarray")?;
+
+            layer_climb_proto::tx::mode_info::Sum::Multi(layer_climb_proto::tx::mode_info::Multi {
+                bitarray: Some(layer_climb_proto::crypto::multisig::CompactBitArray {
+                    extra_bits_stored: bitarray.uint("extra_bits_stored")?.try_into()?,
+                    elems: bitarray.bytes("elems")?,
+                }),
+                mode_infos: multi
+                    .objects("mode_infos")?
+                    .iter()
+                    .map(mode_info_from_json)
+                    .collect::<Result<_>>()?,
+            })
+        }
+        _ => bail!("expected exactly one of single or multi mode info"),
+    };
+
+    Ok(layer_climb_proto::tx::ModeInfo { sum: Some(sum) })
+}
+
+mod string_u64 {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+mod base64_bytes {
+    use base64::prelude::*;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&BASE64_STANDARD.encode(value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        BASE64_STANDARD
+            .decode(String::deserialize(deserializer)?)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+mod base64_bytes_vec {
+    use base64::prelude::*;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(values: &[Vec<u8>], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(|value| BASE64_STANDARD.encode(value)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Vec<u8>>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|value| {
+                BASE64_STANDARD
+                    .decode(value)
+                    .map_err(serde::de::Error::custom)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SENDER: &str = "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu";
+    const RECIPIENT: &str = "cosmos1pgqsmf08ruam4k8fqa97h8gy3gylkznwsnf7ex";
+    const PUBKEY: &str = "AgECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8g";
+    const SIGNATURE: &str =
+        "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gISIjJCUmJygpKissLS4vMDEyMzQ1Njc4OTo7PD0+Pw==";
+
+    // what `simd tx sign` (cosmos-sdk 0.50) writes for a bank send and a gov vote
+    fn sdk_signed_json() -> Value {
+        serde_json::from_str(&format!(
+            r#"{{
+                "body": {{
+                    "messages": [
+                        {{
+                            "@type": "/cosmos.bank.v1beta1.MsgSend",
+                            "from_address": "{SENDER}",
+                            "to_address": "{RECIPIENT}",
+                            "amount": [{{"denom": "stake", "amount": "1000"}}]
+                        }},
+                        {{
+                            "@type": "/cosmos.gov.v1.MsgVote",
+                            "proposal_id": "1",
+                            "voter": "{SENDER}",
+                            "option": "VOTE_OPTION_YES",
+                            "metadata": ""
+                        }}
+                    ],
+                    "memo": "",
+                    "timeout_height": "0",
+                    "extension_options": [],
+                    "non_critical_extension_options": []
+                }},
+                "auth_info": {{
+                    "signer_infos": [
+                        {{
+                            "public_key": {{
+                                "@type": "/cosmos.crypto.secp256k1.PubKey",
+                                "key": "{PUBKEY}"
+                            }},
+                            "mode_info": {{"single": {{"mode": "SIGN_MODE_DIRECT"}}}},
+                            "sequence": "3"
+                        }}
+                    ],
+                    "fee": {{
+                        "amount": [{{"denom": "stake", "amount": "5000"}}],
+                        "gas_limit": "200000",
+                        "payer": "",
+                        "granter": ""
+                    }},
+                    "tip": null
+                }},
+                "signatures": ["{SIGNATURE}"]
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    fn sign_doc() -> layer_climb_proto::tx::SignDoc {
+        let body = layer_climb_proto::tx::TxBodyV053 {
+            messages: vec![
+                proto_into_any(&layer_climb_proto::bank::MsgSend {
+                    from_address: SENDER.to_string(),
+                    to_address: RECIPIENT.to_string(),
+                    amount: vec![layer_climb_proto::Coin {
+                        denom: "stake".to_string(),
+                        amount: "1000".to_string(),
+                    }],
+                })
+                .unwrap(),
+                proto_into_any(&layer_climb_proto::gov::v1::MsgVote {
+                    proposal_id: 1,
+                    voter: SENDER.to_string(),
+                    option: layer_climb_proto::gov::v1::VoteOption::Yes.into(),
+                    metadata: String::new(),
+                })
+                .unwrap(),
+            ],
+            ..Default::default()
+        };
+
+        #[allow(deprecated)]
+        let auth_info = layer_climb_proto::tx::AuthInfo {
+            signer_infos: vec![layer_climb_proto::tx::SignerInfo {
+                public_key: Some(
+                    proto_into_any(&layer_climb_proto::crypto::secp256k1::PubKey {
+                        key: BASE64_STANDARD.decode(PUBKEY).unwrap(),
+                    })
+                    .unwrap(),
+                ),
+                mode_info: Some(layer_climb_proto::tx::ModeInfo {
+                    sum: Some(layer_climb_proto::tx::mode_info::Sum::Single(
+                        layer_climb_proto::tx::mode_info::Single {
+                            mode: layer_climb_proto::tx::signing::SignMode::Direct.into(),
+                        },
+                    )),
+                }),
+                sequence: 3,
+            }],
+            fee: Some(layer_climb_proto::tx::Fee {
+                amount: vec![layer_climb_proto::Coin {
+                    denom: "stake".to_string(),
+                    amount: "5000".to_string(),
+                }],
+                gas_limit: 200_000,
+                payer: String::new(),
+                granter: String::new(),
+            }),
+            tip: None,
+        };
+
+        layer_climb_proto::tx::SignDoc {
+            body_bytes: proto_into_bytes(&body).unwrap(),
+            auth_info_bytes: proto_into_bytes(&auth_info).unwrap(),
+            chain_id: "test-1".to_string(),
+            account_number: 7,
+        }
+    }
+
+    fn signed_tx() -> SignedTx {
+        SignedTx::new(&sign_doc(), BASE64_STANDARD.decode(SIGNATURE).unwrap())
+    }
+
+    #[test]
+    fn unsigned_round_trip() {
+        let unsigned = UnsignedTx::from(sign_doc());
+
+        assert_eq!(
+            UnsignedTx::from_bytes(&unsigned.to_bytes().unwrap()).unwrap(),
+            unsigned
+        );
+        assert_eq!(
+            UnsignedTx::from_json(&unsigned.to_json().unwrap()).unwrap(),
+            unsigned
+        );
+        assert_eq!(
+            unsigned.sign_mode().unwrap(),
+            layer_climb_proto::tx::signing::SignMode::Direct
+        );
+    }
+
+    #[test]
+    fn signed_round_trip() {
+        let signed = signed_tx();
+        let bytes = signed.to_bytes().unwrap();
+
+        assert_eq!(SignedTx::from_bytes(&bytes).unwrap(), signed);
+        assert_eq!(
+            SignedTx::from_json(&signed.to_json().unwrap()).unwrap(),
+            signed
+        );
+
+        // the hash is of the exact bytes that get broadcast
+        let hash = SignedTx::from_bytes(&bytes).unwrap().tx_hash().unwrap();
+        assert_eq!(
+            hash,
+            "693D66E27F859EDE12C2D2737A1179B7D8BA188256702453D8808C7396159E34"
+        );
+        assert_eq!(signed.tx_hash().unwrap(), hash);
+    }
+
+    #[test]
+    fn sdk_json_signed() {
+        let signed = signed_tx();
+
+        assert_eq!(signed.to_sdk_json(None).unwrap(), sdk_signed_json());
+        assert_eq!(
+            SignedTx::from_sdk_json(&sdk_signed_json(), None).unwrap(),
+            signed
+        );
+    }
+
+    #[test]
+    fn sdk_json_unsigned() {
+        // `tx sign` fills in the signer info itself
+        let mut expected = sdk_signed_json();
+        expected["auth_info"]["signer_infos"] = serde_json::json!([]);
+        expected["signatures"] = serde_json::json!([]);
+
+        assert_eq!(
+            UnsignedTx::from(sign_doc()).to_sdk_json(None).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn sdk_json_keeps_contract_msg_bytes() {
+        // not in sorted key order, which must survive the trip through json
+        let msg = br#"{"transfer":{"recipient":"cosmos1recipient","amount":"1"}}"#.to_vec();
+
+        let body = layer_climb_proto::tx::TxBodyV053 {
+            messages: vec![
+                proto_into_any(&layer_climb_proto::wasm::MsgExecuteContract {
+                    sender: SENDER.to_string(),
+                    contract: RECIPIENT.to_string(),
+                    msg: msg.clone(),
+                    funds: Vec::new(),
+                })
+                .unwrap(),
+            ],
+            unordered: true,
+            timeout_timestamp: Some(layer_climb_proto::Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 0,
+            }),
+            ..Default::default()
+        };
+        let signed = SignedTx {
+            body_bytes: proto_into_bytes(&body).unwrap(),
+            ..signed_tx()
+        };
+
+        let json = signed.to_sdk_json(None).unwrap();
+        assert_eq!(
+            json["body"]["messages"][0]["msg"],
+            serde_json::json!({"transfer": {"recipient": "cosmos1recipient", "amount": "1"}})
+        );
+        assert_eq!(json["body"]["unordered"], Value::Bool(true));
+        assert_eq!(
+            json["body"]["timeout_timestamp"],
+            Value::String("2023-11-14T22:13:20Z".to_string())
+        );
+
+        // the json is what gets written to and read from disk
+        let json: Value = serde_json::from_str(&json.to_string()).unwrap();
+        let read = SignedTx::from_sdk_json(&json, None).unwrap();
+        assert_eq!(read, signed);
+        assert_eq!(read.tx_hash().unwrap(), signed.tx_hash().unwrap());
+    }
+
+    #[test]
+    fn sdk_json_multisig() {
+        let multisig_key = layer_climb_proto::crypto::multisig::LegacyAminoPubKey {
+            threshold: 2,
+            public_keys: vec![
+                proto_into_any(&layer_climb_proto::crypto::secp256k1::PubKey {
+                    key: BASE64_STANDARD.decode(PUBKEY).unwrap(),
+                })
+                .unwrap(),
+                proto_into_any(&layer_climb_proto::crypto::secp256k1::PubKey {
+                    key: BASE64_STANDARD
+                        .decode("AyEiIyQlJicoKSorLC0uLzAxMjM0NTY3ODk6Ozw9Pj9A")
+                        .unwrap(),
+                })
+                .unwrap(),
+            ],
+        };
+        let single = layer_climb_proto::tx::ModeInfo {
+            sum: Some(layer_climb_proto::tx::mode_info::Sum::Single(
+                layer_climb_proto::tx::mode_info::Single {
+                    mode: layer_climb_proto::tx::signing::SignMode::LegacyAminoJson.into(),
+                },
+            )),
+        };
+
+        let mut auth_info =
+            layer_climb_proto::tx::AuthInfo::decode(sign_doc().auth_info_bytes.as_slice()).unwrap();
+        auth_info.signer_infos[0].public_key = Some(proto_into_any(&multisig_key).unwrap());
+        auth_info.signer_infos[0].mode_info = Some(layer_climb_proto::tx::ModeInfo {
+            sum: Some(layer_climb_proto::tx::mode_info::Sum::Multi(
+                layer_climb_proto::tx::mode_info::Multi {
+                    bitarray: Some(layer_climb_proto::crypto::multisig::CompactBitArray {
+                        extra_bits_stored: 2,
+                        elems: vec![0b1100_0000],
+                    }),
+                    mode_infos: vec![single.clone(), single],
+                },
+            )),
+        });
+
+        let signed = SignedTx {
+            auth_info_bytes: proto_into_bytes(&auth_info).unwrap(),
+            ..signed_tx()
+        };
+
+        let json = signed.to_sdk_json(None).unwrap();
+        assert_eq!(
+            json["auth_info"]["signer_infos"][0]["mode_info"],
+            serde_json::json!({
+                "multi": {
+                    "bitarray": {"extra_bits_stored": 2, "elems": "wA=="},
+                    "mode_infos": [
+                        {"single": {"mode": "SIGN_MODE_LEGACY_AMINO_JSON"}},
+                        {"single": {"mode": "SIGN_MODE_LEGACY_AMINO_JSON"}},
+                    ],
+                }
+            })
+        );
+        assert_eq!(
+            json["auth_info"]["signer_infos"][0]["public_key"]["threshold"],
+            Value::from(2)
+        );
+
+        assert_eq!(SignedTx::from_sdk_json(&json, None).unwrap(), signed);
+    }
+
+    #[test]
+    fn sdk_json_unknown_message() {
+        let mut json = sdk_signed_json();
+        json["body"]["messages"][0]["@type"] = Value::String("/chain.custom.v1.MsgCustom".into());
+
+        assert!(SignedTx::from_sdk_json(&json, None).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+// fee market queries from outside the cosmos-sdk, which aren't in cosmos_sdk_proto
+// only the parts needed for querying the current gas price are here
+// hand-written to match what prost/tonic would generate
+
+/// Skip's x/feemarket (e.g. Neutron)
+/// see https://github.com/skip-mev/feemarket/blob/main/proto/feemarket/feemarket/v1/query.proto
+pub mod feemarket {
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GasPriceRequest {
+        #[prost(string, tag = "1")]
+        pub denom: ::prost::alloc::string::String,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GasPriceResponse {
+        #[prost(message, optional, tag = "1")]
+        pub price: ::core::option::Option<cosmos_sdk_proto::cosmos::base::v1beta1::DecCoin>,
+    }
+
+    impl ::prost::Name for GasPriceRequest {
+        const NAME: &'static str = "GasPriceRequest";
+        const PACKAGE: &'static str = "feemarket.feemarket.v1";
+    }
+
+    impl ::prost::Name for GasPriceResponse {
+        const NAME: &'static str = "GasPriceResponse";
+        const PACKAGE: &'static str = "feemarket.feemarket.v1";
+    }
+
+    pub mod query_client {
+        use tonic::codegen::*;
+
+        #[derive(Debug, Clone)]
+        pub struct QueryClient<T> {
+            inner: tonic::client::Grpc<T>,
+        }
+
+        impl<T> QueryClient<T>
+        where
+            T: tonic::client::GrpcService<tonic::body::Body>,
+            T::Error: Into<StdError>,
+            T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+            <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+        {
+            pub fn new(inner: T) -> Self {
+                let inner = tonic::client::Grpc::new(inner);
+                Self { inner }
+            }
+
+            pub async fn gas_price(
+                &mut self,
+                request: impl tonic::IntoRequest<super::GasPriceRequest>,
+            ) -> std::result::Result<tonic::Response<super::GasPriceResponse>, tonic::Status>
+            {
+                self.inner.ready().await.map_err(|e| {
+                    tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+                })?;
+                let codec = tonic::codec::ProstCodec::default();
+                let path =
+                    http::uri::PathAndQuery::from_static("/feemarket.feemarket.v1.Query/GasPrice");
+                let mut req = request.into_request();
+                req.extensions_mut()
+                    .insert(GrpcMethod::new("feemarket.feemarket.v1.Query", "GasPrice"));
+                self.inner.unary(req, path, codec).await
+            }
+        }
+    }
+}
+
+/// Osmosis' EIP-1559 base fee in x/txfees
+/// see https://github.com/osmosis-labs/osmosis/blob/main/proto/osmosis/txfees/v1beta1/query.proto
+pub mod osmosis_txfees {
+    #[derive(Clone, Copy, PartialEq, ::prost::Message)]
+    pub struct QueryEipBaseFeeRequest {}
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct QueryEipBaseFeeResponse {
+        /// a cosmos-sdk Dec
+        #[prost(string, tag = "1")]
+        pub base_fee: ::prost::alloc::string::String,
+    }
+
+    impl ::prost::Name for QueryEipBaseFeeRequest {
+        const NAME: &'static str = "QueryEipBaseFeeRequest";
+        const PACKAGE: &'static str = "osmosis.txfees.v1beta1";
+    }
+
+    impl ::prost::Name for QueryEipBaseFeeResponse {
+        const NAME: &'static str = "QueryEipBaseFeeResponse";
+        const PACKAGE: &'static str = "osmosis.txfees.v1beta1";
+    }
+
+    pub mod query_client {
+        use tonic::codegen::*;
+
+        #[derive(Debug, Clone)]
+        pub struct QueryClient<T> {
+            inner: tonic::client::Grpc<T>,
+        }
+
+        impl<T> QueryClient<T>
+        where
+            T: tonic::client::GrpcService<tonic::body::Body>,
+            T::Error: Into<StdError>,
+            T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+            <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+        {
+            pub fn new(inner: T) -> Self {
+                let inner = tonic::client::Grpc::new(inner);
+                Self { inner }
+            }
+
+            pub async fn get_eip_base_fee(
+                &mut self,
+                request: impl tonic::IntoRequest<super::QueryEipBaseFeeRequest>,
+            ) -> std::result::Result<tonic::Response<super::QueryEipBaseFeeResponse>, tonic::Status>
+            {
+                self.inner.ready().await.map_err(|e| {
+                    tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+                })?;
+                let codec = tonic::codec::ProstCodec::default();
+                let path = http::uri::PathAndQuery::from_static(
+                    "/osmosis.txfees.v1beta1.Query/GetEipBaseFee",
+                );
+                let mut req = request.into_request();
+                req.extensions_mut().insert(GrpcMethod::new(
+                    "osmosis.txfees.v1beta1.Query",
+                    "GetEipBaseFee",
+                ));
+                self.inner.unary(req, path, codec).await
+            }
+        }
+    }
+}
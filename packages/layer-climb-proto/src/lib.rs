@@ -1,5 +1,8 @@
+mod fee_market;
 mod helpers;
 mod proto;
+mod tx_body;
 
+pub use fee_market::*;
 pub use helpers::*;
 pub use proto::*;
@@ -1,6 +1,6 @@
 // Exported in the root because they're commonly used
 pub use cosmos_sdk_proto::{
-    cosmos::base::v1beta1::Coin,
+    cosmos::base::v1beta1::{Coin, DecCoin},
     tendermint::google::protobuf::{Any, Duration, Timestamp},
     traits::{Message, MessageExt, Name},
 };
@@ -34,6 +34,7 @@ pub mod wasm {
 }
 
 pub mod ibc {
+    pub use ibc_proto::ibc::applications::transfer::v1 as transfer;
     pub use ibc_proto::ibc::core::channel::v1 as channel;
     pub use ibc_proto::ibc::core::client::v1 as client;
     pub use ibc_proto::ibc::core::connection::v1 as connection;
@@ -97,6 +98,7 @@ pub mod crypto {
     /// Multi-signature support.
     pub mod multisig {
         pub use cosmos_sdk_proto::cosmos::crypto::multisig::v1beta1::*;
+        pub use cosmos_sdk_proto::cosmos::crypto::multisig::LegacyAminoPubKey;
     }
     pub mod ed25519 {
         pub use cosmos_sdk_proto::cosmos::crypto::ed25519::*;
@@ -161,6 +163,7 @@ pub mod staking {
 
 /// Transactions.
 pub mod tx {
+    pub use crate::tx_body::TxBodyV053;
     pub use cosmos_sdk_proto::cosmos::tx::v1beta1::*;
     /// Transaction signing support.
     pub mod signing {
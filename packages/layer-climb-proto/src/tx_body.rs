@@ -0,0 +1,46 @@
+// cosmos-sdk 0.53 added unordered txs to TxBody, which cosmos_sdk_proto doesn't have yet
+// see https://github.com/cosmos/cosmos-sdk/blob/v0.53.0/proto/cosmos/tx/v1beta1/tx.proto
+
+/// The 0.53 `cosmos.tx.v1beta1.TxBody`, i.e. `tx::TxBody` plus the unordered tx fields
+/// it's what climb builds and decodes tx bodies with, older chains just see the new fields left at their defaults
+/// (`tx::Tx.body` is still the cosmos_sdk_proto `TxBody`, convert with `TxBodyV053::from`)
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TxBodyV053 {
+    #[prost(message, repeated, tag = "1")]
+    pub messages: ::prost::alloc::vec::Vec<crate::Any>,
+    #[prost(string, tag = "2")]
+    pub memo: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "3")]
+    pub timeout_height: u64,
+    /// if true, the sequence is not used for replay protection, and `timeout_timestamp` must be set
+    /// only supported on cosmos-sdk 0.53+
+    #[prost(bool, tag = "4")]
+    pub unordered: bool,
+    /// the block time after which the tx is no longer valid
+    /// only supported on cosmos-sdk 0.53+
+    #[prost(message, optional, tag = "5")]
+    pub timeout_timestamp: ::core::option::Option<crate::Timestamp>,
+    #[prost(message, repeated, tag = "1023")]
+    pub extension_options: ::prost::alloc::vec::Vec<crate::Any>,
+    #[prost(message, repeated, tag = "2047")]
+    pub non_critical_extension_options: ::prost::alloc::vec::Vec<crate::Any>,
+}
+
+impl ::prost::Name for TxBodyV053 {
+    const NAME: &'static str = "TxBody";
+    const PACKAGE: &'static str = "cosmos.tx.v1beta1";
+}
+
+impl From<crate::tx::TxBody> for TxBodyV053 {
+    fn from(body: crate::tx::TxBody) -> Self {
+        Self {
+            messages: body.messages,
+            memo: body.memo,
+            timeout_height: body.timeout_height,
+            unordered: false,
+            timeout_timestamp: None,
+            extension_options: body.extension_options,
+            non_critical_extension_options: body.non_critical_extension_options,
+        }
+    }
+}
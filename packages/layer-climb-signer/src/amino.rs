@@ -0,0 +1,148 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// The sign doc for SIGN_MODE_LEGACY_AMINO_JSON
+/// see https://github.com/cosmos/cosmos-sdk/blob/main/x/auth/migrations/legacytx/stdsign.go
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StdSignDoc {
+    pub account_number: String,
+    pub chain_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_height: Option<String>,
+    pub fee: StdFee,
+    pub memo: String,
+    pub msgs: Vec<AminoMsg>,
+    pub sequence: String,
+    /// only set for unordered txs (cosmos-sdk 0.53+), as RFC3339 like Go's `time.RFC3339Nano`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_timestamp: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unordered: bool,
+}
+
+impl StdSignDoc {
+    pub fn new(
+        chain_id: impl ToString,
+        account_number: u64,
+        sequence: u64,
+        fee: StdFee,
+        msgs: Vec<AminoMsg>,
+        memo: impl ToString,
+    ) -> Self {
+        Self {
+            account_number: account_number.to_string(),
+            chain_id: chain_id.to_string(),
+            timeout_height: None,
+            fee,
+            memo: memo.to_string(),
+            msgs,
+            sequence: sequence.to_string(),
+            timeout_timestamp: None,
+            unordered: false,
+        }
+    }
+
+    /// a timeout height of 0 means no timeout, and is left out of the sign doc
+    pub fn with_timeout_height(mut self, timeout_height: u64) -> Self {
+        self.timeout_height = match timeout_height {
+            0 => None,
+            height => Some(height.to_string()),
+        };
+        self
+    }
+
+    /// unordered txs expire by timestamp instead of sequence, both are left out of the sign doc when not set
+    pub fn with_unordered(
+        mut self,
+        unordered: bool,
+        timeout_timestamp: Option<layer_climb_proto::Timestamp>,
+    ) -> Result<Self> {
+        self.unordered = unordered;
+        self.timeout_timestamp = match timeout_timestamp {
+            Some(timestamp) => Some(
+                tendermint::Time::from_unix_timestamp(
+                    timestamp.seconds,
+                    timestamp.nanos.try_into()?,
+                )?
+                .to_rfc3339(),
+            ),
+            None => None,
+        };
+        Ok(self)
+    }
+
+    /// The bytes that actually get signed: sorted, compact json
+    /// with the same html-escaping as Go's encoding/json
+    pub fn to_sign_bytes(&self) -> Result<Vec<u8>> {
+        let json = serde_json::to_string(&sort_json(serde_json::to_value(self)?))?;
+
+        Ok(json
+            .replace('&', "\\u0026")
+            .replace('<', "\\u003c")
+            .replace('>', "\\u003e")
+            .into_bytes())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StdFee {
+    pub amount: Vec<AminoCoin>,
+    pub gas: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub payer: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub granter: String,
+}
+
+impl From<&layer_climb_proto::tx::Fee> for StdFee {
+    fn from(fee: &layer_climb_proto::tx::Fee) -> Self {
+        Self {
+            amount: fee.amount.iter().map(AminoCoin::from).collect(),
+            gas: fee.gas_limit.to_string(),
+            payer: fee.payer.clone(),
+            granter: fee.granter.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AminoCoin {
+    pub amount: String,
+    pub denom: String,
+}
+
+impl From<&layer_climb_proto::Coin> for AminoCoin {
+    fn from(coin: &layer_climb_proto::Coin) -> Self {
+        Self {
+            amount: coin.amount.clone(),
+            denom: coin.denom.clone(),
+        }
+    }
+}
+
+/// A message in its amino json form, e.g. `{"type": "cosmos-sdk/MsgSend", "value": {...}}`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AminoMsg {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub value: serde_json::Value,
+}
+
+fn sort_json(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_json(value)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(values) => {
+            serde_json::Value::Array(values.into_iter().map(sort_json).collect())
+        }
+        value => value,
+    }
+}
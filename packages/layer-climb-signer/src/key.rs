@@ -1,4 +1,4 @@
-use super::signer::TxSigner;
+use super::{amino::StdSignDoc, signer::TxSigner};
 use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
 use bip32::DerivationPath;
@@ -54,6 +54,10 @@ cfg_if::cfg_if! {
                 sign(self, msg).await
             }
 
+            async fn sign_amino(&self, doc: &StdSignDoc) -> Result<Vec<u8>> {
+                sign_amino(self, doc).await
+            }
+
             async fn public_key(&self) -> Result<PublicKey> {
                 public_key(self).await
             }
@@ -66,6 +70,10 @@ cfg_if::cfg_if! {
                 sign(self, msg).await
             }
 
+            async fn sign_amino(&self, doc: &StdSignDoc) -> Result<Vec<u8>> {
+                sign_amino(self, doc).await
+            }
+
             async fn public_key(&self) -> Result<PublicKey> {
                 public_key(self).await
             }
@@ -82,6 +90,15 @@ async fn sign(signer: &KeySigner, msg: &layer_climb_proto::tx::SignDoc) -> Resul
     Ok(signed.to_vec())
 }
 
+async fn sign_amino(signer: &KeySigner, doc: &StdSignDoc) -> Result<Vec<u8>> {
+    let signed: k256::ecdsa::Signature = signer
+        .key
+        .private_key()
+        .try_sign(&doc.to_sign_bytes()?)
+        .map_err(|err| anyhow!("{}", err))?;
+    Ok(signed.to_vec())
+}
+
 async fn public_key(signer: &KeySigner) -> Result<PublicKey> {
     let public_key = signer.key.public_key();
     let public_key_bytes = public_key.to_bytes();
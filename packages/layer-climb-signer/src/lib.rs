@@ -1,7 +1,11 @@
+mod amino;
 mod key;
+mod multisig;
 mod signer;
 
+pub use amino::*;
 pub use key::*;
+pub use multisig::*;
 pub use signer::*;
 
 #[cfg(feature = "web")]
@@ -0,0 +1,437 @@
+use super::{
+    amino::StdSignDoc,
+    key::PublicKey,
+    signer::{public_key_to_proto, TxSigner},
+};
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use layer_climb_proto::MessageExt;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A `LegacyAminoPubKey` threshold multisig
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MultisigPublicKey {
+    pub threshold: u32,
+    /// order matters, it must be the same order as the keys in the on-chain multisig
+    pub public_keys: Vec<PublicKey>,
+}
+
+impl MultisigPublicKey {
+    pub fn new(threshold: u32, public_keys: Vec<PublicKey>) -> Result<Self> {
+        if threshold == 0 || threshold as usize > public_keys.len() {
+            bail!(
+                "invalid multisig threshold {} for {} keys",
+                threshold,
+                public_keys.len()
+            );
+        }
+
+        Ok(Self {
+            threshold,
+            public_keys,
+        })
+    }
+
+    /// the index of this key within the multisig, if it's a member
+    pub fn position(&self, public_key: &PublicKey) -> Option<usize> {
+        self.public_keys.iter().position(|key| key == public_key)
+    }
+
+    pub fn to_proto(&self) -> Result<layer_climb_proto::crypto::multisig::LegacyAminoPubKey> {
+        Ok(layer_climb_proto::crypto::multisig::LegacyAminoPubKey {
+            threshold: self.threshold,
+            public_keys: self
+                .public_keys
+                .iter()
+                .map(public_key_to_proto)
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+
+    pub fn to_any(&self) -> Result<layer_climb_proto::Any> {
+        layer_climb_proto::proto_into_any(&self.to_proto()?)
+    }
+
+    pub fn address(
+        &self,
+        chain_config: &layer_climb_config::ChainConfig,
+    ) -> Result<layer_climb_address::Address> {
+        chain_config.address_from_multisig_pub_key(self.threshold, &self.public_keys)
+    }
+}
+
+/// One member's signature over a multisig tx's `SignDoc`
+/// this is what gets passed around between machines and then combined via `MultisigSigner::combine_signatures()`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MultisigPartialSignature {
+    pub public_key: PublicKey,
+    pub signature: Vec<u8>,
+}
+
+impl MultisigPartialSignature {
+    pub async fn sign(signer: &dyn TxSigner, doc: &layer_climb_proto::tx::SignDoc) -> Result<Self> {
+        Ok(Self {
+            public_key: signer.public_key().await?,
+            signature: signer.sign(doc).await?,
+        })
+    }
+
+    pub async fn sign_amino(signer: &dyn TxSigner, doc: &StdSignDoc) -> Result<Self> {
+        Ok(Self {
+            public_key: signer.public_key().await?,
+            signature: signer.sign_amino(doc).await?,
+        })
+    }
+}
+
+/// A TxSigner for a multisig account, so that a SigningClient and TxBuilder can use it as the sender
+///
+/// The members who will sign need to be known before the tx is built, since which keys signed
+/// is part of the `AuthInfo`, and therefore part of what each member signs over.
+///
+/// If all of those members are available locally, `sign()` will collect their signatures directly.
+/// Otherwise, each member signs the same `SignDoc` (e.g. via `MultisigPartialSignature::sign()`)
+/// and the results are put together with `combine_signatures()`
+#[derive(Clone)]
+pub struct MultisigSigner {
+    pub public_key: MultisigPublicKey,
+    /// the members who will sign, sorted by their position in the multisig
+    pub signers: Vec<PublicKey>,
+    /// signers which are available locally, used by `sign()`
+    pub local_signers: Vec<Arc<dyn TxSigner>>,
+}
+
+impl MultisigSigner {
+    pub fn new(public_key: MultisigPublicKey, signers: Vec<PublicKey>) -> Result<Self> {
+        let mut positions = signers
+            .into_iter()
+            .map(|signer| {
+                public_key
+                    .position(&signer)
+                    .map(|position| (position, signer))
+                    .ok_or_else(|| anyhow!("{signer:?} is not a member of the multisig"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        positions.sort_by_key(|(position, _)| *position);
+        positions.dedup_by_key(|(position, _)| *position);
+
+        if positions.len() < public_key.threshold as usize {
+            bail!(
+                "multisig needs {} signers, only {} given",
+                public_key.threshold,
+                positions.len()
+            );
+        }
+
+        Ok(Self {
+            public_key,
+            signers: positions.into_iter().map(|(_, signer)| signer).collect(),
+            local_signers: Vec::new(),
+        })
+    }
+
+    pub async fn new_local(
+        public_key: MultisigPublicKey,
+        local_signers: Vec<Arc<dyn TxSigner>>,
+    ) -> Result<Self> {
+        let mut signers = Vec::with_capacity(local_signers.len());
+        for signer in local_signers.iter() {
+            signers.push(signer.public_key().await?);
+        }
+
+        Ok(Self {
+            local_signers,
+            ..Self::new(public_key, signers)?
+        })
+    }
+
+    /// which members of the multisig are signing
+    pub fn bitarray(&self) -> layer_climb_proto::crypto::multisig::CompactBitArray {
+        let len = self.public_key.public_keys.len();
+        let mut elems = vec![0u8; len.div_ceil(8)];
+
+        for signer in self.signers.iter() {
+            if let Some(position) = self.public_key.position(signer) {
+                elems[position / 8] |= 1 << (7 - (position % 8));
+            }
+        }
+
+        layer_climb_proto::crypto::multisig::CompactBitArray {
+            extra_bits_stored: (len % 8) as u32,
+            elems,
+        }
+    }
+
+    /// Puts the members' signatures together into the final signature for the tx
+    pub fn combine_signatures(
+        &self,
+        partial_signatures: impl IntoIterator<Item = MultisigPartialSignature>,
+    ) -> Result<Vec<u8>> {
+        let partial_signatures: Vec<_> = partial_signatures.into_iter().collect();
+
+        // must be in the same order as the bitarray
+        let signatures = self
+            .signers
+            .iter()
+            .map(|signer| {
+                partial_signatures
+                    .iter()
+                    .find(|partial| partial.public_key == *signer)
+                    .map(|partial| partial.signature.clone())
+                    .ok_or_else(|| anyhow!("missing multisig signature from {signer:?}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(layer_climb_proto::crypto::multisig::MultiSignature { signatures }.to_bytes()?)
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        #[async_trait(?Send)]
+        impl TxSigner for MultisigSigner {
+            async fn sign(&self, doc: &layer_climb_proto::tx::SignDoc) -> Result<Vec<u8>> {
+                sign(self, doc).await
+            }
+
+            async fn sign_amino(&self, doc: &StdSignDoc) -> Result<Vec<u8>> {
+                sign_amino(self, doc).await
+            }
+
+            async fn public_key(&self) -> Result<PublicKey> {
+                bail!("a multisig does not have a single public key, use public_key_as_proto instead")
+            }
+
+            async fn public_key_as_proto(&self) -> Result<layer_climb_proto::Any> {
+                self.public_key.to_any()
+            }
+
+            async fn signer_info(&self, sequence: u64, sign_mode: layer_climb_proto::tx::signing::SignMode) -> Result<layer_climb_proto::tx::SignerInfo> {
+                signer_info(self, sequence, sign_mode)
+            }
+
+            async fn address(&self, chain_config: &layer_climb_config::ChainConfig) -> Result<layer_climb_address::Address> {
+                self.public_key.address(chain_config)
+            }
+        }
+    } else {
+        #[async_trait]
+        impl TxSigner for MultisigSigner {
+            async fn sign(&self, doc: &layer_climb_proto::tx::SignDoc) -> Result<Vec<u8>> {
+                sign(self, doc).await
+            }
+
+            async fn sign_amino(&self, doc: &StdSignDoc) -> Result<Vec<u8>> {
+                sign_amino(self, doc).await
+            }
+
+            async fn public_key(&self) -> Result<PublicKey> {
+                bail!("a multisig does not have a single public key, use public_key_as_proto instead")
+            }
+
+            async fn public_key_as_proto(&self) -> Result<layer_climb_proto::Any> {
+                self.public_key.to_any()
+            }
+
+            async fn signer_info(&self, sequence: u64, sign_mode: layer_climb_proto::tx::signing::SignMode) -> Result<layer_climb_proto::tx::SignerInfo> {
+                signer_info(self, sequence, sign_mode)
+            }
+
+            async fn address(&self, chain_config: &layer_climb_config::ChainConfig) -> Result<layer_climb_address::Address> {
+                self.public_key.address(chain_config)
+            }
+        }
+    }
+}
+
+async fn sign(signer: &MultisigSigner, doc: &layer_climb_proto::tx::SignDoc) -> Result<Vec<u8>> {
+    if signer.local_signers.is_empty() {
+        bail!("multisig has no local signers, sign with each member and use combine_signatures() instead");
+    }
+
+    let mut partial_signatures = Vec::with_capacity(signer.local_signers.len());
+    for local_signer in signer.local_signers.iter() {
+        partial_signatures.push(MultisigPartialSignature::sign(local_signer.as_ref(), doc).await?);
+    }
+
+    signer.combine_signatures(partial_signatures)
+}
+
+async fn sign_amino(signer: &MultisigSigner, doc: &StdSignDoc) -> Result<Vec<u8>> {
+    if signer.local_signers.is_empty() {
+        bail!("multisig has no local signers, sign with each member and use combine_signatures() instead");
+    }
+
+    let mut partial_signatures = Vec::with_capacity(signer.local_signers.len());
+    for local_signer in signer.local_signers.iter() {
+        partial_signatures
+            .push(MultisigPartialSignature::sign_amino(local_signer.as_ref(), doc).await?);
+    }
+
+    signer.combine_signatures(partial_signatures)
+}
+
+fn signer_info(
+    signer: &MultisigSigner,
+    sequence: u64,
+    sign_mode: layer_climb_proto::tx::signing::SignMode,
+) -> Result<layer_climb_proto::tx::SignerInfo> {
+    let mode_infos = signer
+        .signers
+        .iter()
+        .map(|_| layer_climb_proto::tx::ModeInfo {
+            sum: Some(layer_climb_proto::tx::mode_info::Sum::Single(
+                layer_climb_proto::tx::mode_info::Single {
+                    mode: sign_mode.into(),
+                },
+            )),
+        })
+        .collect();
+
+    Ok(layer_climb_proto::tx::SignerInfo {
+        public_key: Some(signer.public_key.to_any()?),
+        mode_info: Some(layer_climb_proto::tx::ModeInfo {
+            sum: Some(layer_climb_proto::tx::mode_info::Sum::Multi(
+                layer_climb_proto::tx::mode_info::Multi {
+                    bitarray: Some(signer.bitarray()),
+                    mode_infos,
+                },
+            )),
+        }),
+        sequence,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use layer_climb_proto::Message;
+
+    fn key(n: u8) -> PublicKey {
+        let signing_key = k256::ecdsa::SigningKey::from_slice(&[n; 32]).unwrap();
+        PublicKey::from_raw_secp256k1(&signing_key.verifying_key().to_sec1_bytes()).unwrap()
+    }
+
+    fn multisig(threshold: u32, len: u8) -> MultisigPublicKey {
+        MultisigPublicKey::new(threshold, (1..=len).map(key).collect()).unwrap()
+    }
+
+    fn bitarray(len: u8, positions: &[u8]) -> layer_climb_proto::crypto::multisig::CompactBitArray {
+        let signers = positions.iter().map(|position| key(position + 1)).collect();
+        MultisigSigner::new(multisig(1, len), signers)
+            .unwrap()
+            .bitarray()
+    }
+
+    fn partial(public_key: PublicKey, signature: &[u8]) -> MultisigPartialSignature {
+        MultisigPartialSignature {
+            public_key,
+            signature: signature.to_vec(),
+        }
+    }
+
+    #[test]
+    fn bitarray_bit_order() {
+        // same as the sdk's CompactBitArray, i.e. big-endian within each byte
+        let one = bitarray(1, &[0]);
+        assert_eq!(one.elems, vec![0b1000_0000]);
+        assert_eq!(one.extra_bits_stored, 1);
+
+        let seven = bitarray(7, &[0, 6]);
+        assert_eq!(seven.elems, vec![0b1000_0010]);
+        assert_eq!(seven.extra_bits_stored, 7);
+
+        let eight = bitarray(8, &[1, 7]);
+        assert_eq!(eight.elems, vec![0b0100_0001]);
+        assert_eq!(eight.extra_bits_stored, 0);
+
+        let nine = bitarray(9, &[8, 0]);
+        assert_eq!(nine.elems, vec![0b1000_0000, 0b1000_0000]);
+        assert_eq!(nine.extra_bits_stored, 1);
+    }
+
+    #[test]
+    fn combine_signatures_in_key_order() {
+        let signer = MultisigSigner::new(multisig(2, 3), vec![key(3), key(1)]).unwrap();
+        assert_eq!(signer.signers, vec![key(1), key(3)]);
+
+        // out of order, and with a signature from a member who isn't signing this tx
+        let signature = signer
+            .combine_signatures(vec![
+                partial(key(3), b"third"),
+                partial(key(2), b"second"),
+                partial(key(1), b"first"),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            layer_climb_proto::crypto::multisig::MultiSignature::decode(signature.as_slice())
+                .unwrap()
+                .signatures,
+            vec![b"first".to_vec(), b"third".to_vec()]
+        );
+    }
+
+    #[test]
+    fn missing_signers() {
+        let signer = MultisigSigner::new(multisig(2, 3), vec![key(1), key(2)]).unwrap();
+        assert!(signer
+            .combine_signatures(vec![partial(key(1), b"first")])
+            .is_err());
+        assert!(signer
+            .combine_signatures(vec![partial(key(1), b"first"), partial(key(3), b"third")])
+            .is_err());
+
+        // below the threshold, including via duplicates
+        assert!(MultisigSigner::new(multisig(2, 3), vec![key(1)]).is_err());
+        assert!(MultisigSigner::new(multisig(2, 3), vec![key(1), key(1)]).is_err());
+        // not a member
+        assert!(MultisigSigner::new(multisig(1, 3), vec![key(4)]).is_err());
+
+        assert!(MultisigPublicKey::new(0, vec![key(1)]).is_err());
+        assert!(MultisigPublicKey::new(2, vec![key(1)]).is_err());
+    }
+
+    #[test]
+    fn signer_info_mode_info() {
+        let signer = MultisigSigner::new(multisig(2, 3), vec![key(1), key(3)]).unwrap();
+        let signer_info = signer_info(
+            &signer,
+            5,
+            layer_climb_proto::tx::signing::SignMode::LegacyAminoJson,
+        )
+        .unwrap();
+
+        assert_eq!(signer_info.sequence, 5);
+        assert_eq!(
+            signer_info.public_key.unwrap().type_url,
+            "/cosmos.crypto.multisig.LegacyAminoPubKey"
+        );
+
+        let single = layer_climb_proto::tx::ModeInfo {
+            sum: Some(layer_climb_proto::tx::mode_info::Sum::Single(
+                layer_climb_proto::tx::mode_info::Single {
+                    mode: layer_climb_proto::tx::signing::SignMode::LegacyAminoJson.into(),
+                },
+            )),
+        };
+
+        // one mode info per signing member, not per key
+        assert_eq!(
+            signer_info.mode_info.unwrap(),
+            layer_climb_proto::tx::ModeInfo {
+                sum: Some(layer_climb_proto::tx::mode_info::Sum::Multi(
+                    layer_climb_proto::tx::mode_info::Multi {
+                        bitarray: Some(layer_climb_proto::crypto::multisig::CompactBitArray {
+                            extra_bits_stored: 3,
+                            elems: vec![0b1010_0000],
+                        }),
+                        mode_infos: vec![single.clone(), single],
+                    },
+                )),
+            }
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use super::key::PublicKey;
+use super::{amino::StdSignDoc, key::PublicKey};
 use anyhow::{bail, Result};
 use async_trait::async_trait;
 use layer_climb_proto::MessageExt;
@@ -9,6 +9,10 @@ cfg_if::cfg_if! {
         #[async_trait(?Send)]
         pub trait TxSigner: Send + Sync {
             async fn sign(&self, doc: &layer_climb_proto::tx::SignDoc) -> Result<Vec<u8>>;
+            /// For SIGN_MODE_LEGACY_AMINO_JSON, not all signers support it
+            async fn sign_amino(&self, _doc: &StdSignDoc) -> Result<Vec<u8>> {
+                bail!("this signer does not support amino json signing")
+            }
             async fn public_key(&self) -> Result<PublicKey>;
             async fn public_key_as_proto(&self) -> Result<layer_climb_proto::Any> {
                 public_key_to_proto(&self.public_key().await?)
@@ -25,6 +29,10 @@ cfg_if::cfg_if! {
         #[async_trait]
         pub trait TxSigner: Send + Sync {
             async fn sign(&self, doc: &layer_climb_proto::tx::SignDoc) -> Result<Vec<u8>>;
+            /// For SIGN_MODE_LEGACY_AMINO_JSON, not all signers support it
+            async fn sign_amino(&self, _doc: &StdSignDoc) -> Result<Vec<u8>> {
+                bail!("this signer does not support amino json signing")
+            }
             async fn public_key(&self) -> Result<PublicKey>;
             async fn public_key_as_proto(&self) -> Result<layer_climb_proto::Any> {
                 public_key_to_proto(&self.public_key().await?)
@@ -40,7 +48,7 @@ cfg_if::cfg_if! {
     }
 }
 
-fn public_key_to_proto(public_key: &PublicKey) -> Result<layer_climb_proto::Any> {
+pub(crate) fn public_key_to_proto(public_key: &PublicKey) -> Result<layer_climb_proto::Any> {
     let value = match public_key {
         tendermint::PublicKey::Ed25519(_) => layer_climb_proto::crypto::ed25519::PubKey {
             key: public_key.to_bytes(),
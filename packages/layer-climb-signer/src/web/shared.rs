@@ -9,6 +9,12 @@ pub struct WebChainConfig {
     pub grpc_endpoint: Option<String>,
     // if not specified, will fallback to `grpc_endpoint`
     pub grpc_web_endpoint: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rpc_endpoints: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grpc_endpoints: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grpc_web_endpoints: Vec<String>,
     // needed for wallets like Keplr
     pub rest_endpoint: Option<String>,
     // not micro-units, e.g. 0.025 would be a typical value
@@ -24,6 +30,9 @@ impl From<WebChainConfig> for ChainConfig {
             rpc_endpoint: web_chain_config.rpc_endpoint,
             grpc_endpoint: web_chain_config.grpc_endpoint,
             grpc_web_endpoint: web_chain_config.grpc_web_endpoint,
+            rpc_endpoints: web_chain_config.rpc_endpoints,
+            grpc_endpoints: web_chain_config.grpc_endpoints,
+            grpc_web_endpoints: web_chain_config.grpc_web_endpoints,
             gas_price: web_chain_config.gas_price,
             gas_denom: web_chain_config.gas_denom,
             address_kind: web_chain_config.address_kind,
@@ -46,6 +55,9 @@ impl From<ChainConfig> for WebChainConfig {
             rest_endpoint,
             grpc_endpoint: chain_config.grpc_endpoint,
             grpc_web_endpoint: chain_config.grpc_web_endpoint,
+            rpc_endpoints: chain_config.rpc_endpoints,
+            grpc_endpoints: chain_config.grpc_endpoints,
+            grpc_web_endpoints: chain_config.grpc_web_endpoints,
             gas_price: chain_config.gas_price,
             gas_denom: chain_config.gas_denom,
             address_kind: chain_config.address_kind,